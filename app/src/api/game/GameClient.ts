@@ -71,6 +71,17 @@ export interface Fleet {
   ships: Ship[];
 }
 
+export interface GameRules {
+  win_condition: WinCondition;
+  shots_per_turn: number;
+  max_shots: number | null;
+  wrap_edges: boolean;
+  extra_turn_on_hit: boolean;
+  extra_turn_on_sink: boolean;
+  reveal_on_sink: boolean;
+  shot_timeout_ms: number;
+}
+
 export interface GameState {
   lobby_context_id: string;
   match_id: string;
@@ -96,6 +107,12 @@ export interface PendingShot {
   y: number;
   shooter: CalimeroBytes;
   target: CalimeroBytes;
+  nonce: number | null;
+}
+
+export interface PlacementRules {
+  board_size: number;
+  fleet: number[];
 }
 
 export interface PlayerBoard {
@@ -125,6 +142,8 @@ export interface ValidationInput {
   ships: Coordinate[][] | null;
 }
 
+export type WinCondition = 'SinkAll' | { FirstToHits: number };
+
 
 
 
@@ -257,7 +276,7 @@ export class GameClient {
   /**
    * init
    */
-  public async init(params: { player1: string; player2: string; lobby_context_id: string | null; match_id: string }): Promise<void> {
+  public async init(params: { player1: string; player2: string; lobby_context_id: string | null; match_id: string; game_rules?: GameRules | null; seed?: number | null; first_mover?: string | null; placement_rules?: PlacementRules | null }): Promise<void> {
     const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'init', argsJson: params, executorPublicKey: this._executorPublicKey });
     return response as void;
   }
@@ -270,6 +289,38 @@ export class GameClient {
     return response as void;
   }
 
+  /**
+   * place_ship
+   */
+  public async placeShip(params: { match_id: string; ship: string }): Promise<void> {
+    const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'place_ship', argsJson: params, executorPublicKey: this._executorPublicKey });
+    return response as void;
+  }
+
+  /**
+   * undo_last_ship
+   */
+  public async undoLastShip(params: { match_id: string }): Promise<void> {
+    const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'undo_last_ship', argsJson: params, executorPublicKey: this._executorPublicKey });
+    return response as void;
+  }
+
+  /**
+   * get_draft_ships
+   */
+  public async getDraftShips(params: { match_id: string }): Promise<string[]> {
+    const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'get_draft_ships', argsJson: params, executorPublicKey: this._executorPublicKey });
+    return response as string[];
+  }
+
+  /**
+   * confirm_placement
+   */
+  public async confirmPlacement(params: { match_id: string }): Promise<void> {
+    const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'confirm_placement', argsJson: params, executorPublicKey: this._executorPublicKey });
+    return response as void;
+  }
+
   /**
    * propose_shot
    */
@@ -14,6 +14,17 @@ export interface Event_MatchIdCollision {
   attempted_id: string;
 }
 
+export interface GameRules {
+  win_condition: WinCondition;
+  shots_per_turn: number;
+  max_shots: number | null;
+  wrap_edges: boolean;
+  extra_turn_on_hit: boolean;
+  extra_turn_on_sink: boolean;
+  reveal_on_sink: boolean;
+  shot_timeout_ms: number;
+}
+
 export interface LobbyState {
   created_ms: number;
   matches: Record<string, MatchSummary>;
@@ -38,6 +49,26 @@ export interface MatchSummary {
   context_id: string | null;
   winner: string | null;
   created_ms: number;
+  visibility: Visibility;
+  first_mover: string;
+  name: string | null;
+  turn: string | null;
+  turn_ms: number;
+  template: string | null;
+}
+
+export interface MatchTemplate {
+  name: string;
+  game_rules: GameRules;
+  placement_rules: PlacementRules;
+  visibility: Visibility;
+  registered_by: string;
+  registered_ms: number;
+}
+
+export interface PlacementRules {
+  board_size: number;
+  fleet: number[];
 }
 
 export interface PlayerStats {
@@ -51,6 +82,10 @@ export interface PlayerStatsView {
   games_played: number;
 }
 
+export type Visibility = 'Public' | 'Private';
+
+export type WinCondition = 'SinkAll' | { FirstToHits: number };
+
 
 
 
@@ -85,11 +120,35 @@ export class LobbyClient {
   /**
    * create_match
    */
-  public async createMatch(params: { player2: string }): Promise<string> {
+  public async createMatch(params: { player2: string; visibility?: Visibility | null; first_mover?: string | null; name?: string | null }): Promise<string> {
     const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'create_match', argsJson: params, executorPublicKey: this._executorPublicKey });
     return response as string;
   }
 
+  /**
+   * register_template
+   */
+  public async registerTemplate(params: { name: string; game_rules: GameRules | null; placement_rules: PlacementRules | null; visibility: Visibility | null }): Promise<void> {
+    const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'register_template', argsJson: params, executorPublicKey: this._executorPublicKey });
+    return response as void;
+  }
+
+  /**
+   * get_template
+   */
+  public async getTemplate(params: { name: string }): Promise<MatchTemplate | null> {
+    const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'get_template', argsJson: params, executorPublicKey: this._executorPublicKey });
+    return response as MatchTemplate | null;
+  }
+
+  /**
+   * create_match_from_template
+   */
+  public async createMatchFromTemplate(params: { name: string; player2: string }): Promise<string> {
+    const response = await this._mero.rpc.execute({ contextId: this._contextId, method: 'create_match_from_template', argsJson: params, executorPublicKey: this._executorPublicKey });
+    return response as string;
+  }
+
   /**
    * set_match_context_id
    */
@@ -24,6 +24,8 @@ interface LobbyViewProps {
   player2: string;
   creatingMatch: boolean;
   onPlayer2Change: (v: string) => void;
+  templateName: string;
+  onTemplateNameChange: (v: string) => void;
   onCreateMatch: () => void;
 
   // Match list
@@ -34,7 +36,7 @@ interface LobbyViewProps {
 export default function LobbyView({
   lobbyAlias, isAdmin, members, selfIdentity, executorPublicKey,
   inviteLoading, invitationJson, onCreateInvitation, onDismissInvitation,
-  player2, creatingMatch, onPlayer2Change, onCreateMatch,
+  player2, creatingMatch, onPlayer2Change, templateName, onTemplateNameChange, onCreateMatch,
   matches, onOpenGame,
 }: LobbyViewProps) {
   return (
@@ -127,6 +129,12 @@ export default function LobbyView({
               value={player2}
               onChange={(e) => onPlayer2Change(e.target.value)}
             />
+            <Input
+              type="text"
+              placeholder="Template (optional)"
+              value={templateName}
+              onChange={(e) => onTemplateNameChange(e.target.value)}
+            />
             <button type="submit" className="btn-deploy" disabled={creatingMatch}>
               {creatingMatch ? 'Creating...' : 'Challenge'}
             </button>
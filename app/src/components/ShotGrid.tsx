@@ -14,7 +14,7 @@ interface ShotGridProps {
 /**
  * Interactive shots grid — the opponent's board from your perspective.
  * Click empty cells to select a target when it's your turn.
- * Cell values: 0=empty, 2=hit, 3=miss, 4=pending
+ * Cell values: 0=empty, 2=hit, 3=miss, 4=pending, 5=hit on a sunk ship
  */
 export default function ShotGrid({
   size, shots, isMyTurn, selectedX, selectedY, onCellClick,
@@ -23,7 +23,8 @@ export default function ShotGrid({
     const isSelected = selectedX === x && selectedY === y;
     if (isSelected) return 'cell cell-selected';
     switch (val) {
-      case 2: return 'cell cell-hit';
+      case 2:
+      case 5: return 'cell cell-hit';
       case 3: return 'cell cell-miss';
       case 4: return 'cell cell-pending';
       default: return `cell cell-empty${isMyTurn && val === 0 ? ' cell-clickable' : ''}`;
@@ -78,6 +78,14 @@ function toGameEvent(
 
       return { type: 'ShotProposed', id, x, y };
     }
+    case 'ShotCancelled': {
+      const { x, y } = payload;
+      if (!id || typeof x !== 'number' || typeof y !== 'number') {
+        return null;
+      }
+
+      return { type: 'ShotCancelled', id, x, y };
+    }
     case 'ShotFired': {
       const { x, y, result } = payload;
       if (
@@ -99,6 +107,11 @@ function toGameEvent(
       return { type: 'MatchListUpdated', id };
     case 'PlayerStatsUpdated':
       return { type: 'PlayerStatsUpdated', id };
+    case 'MatchForfeited': {
+      const player = typeof payload.player === 'string' ? payload.player : '';
+      if (!id || !player) return null;
+      return { type: 'MatchForfeited', id, player };
+    }
     case 'MatchIdCollision': {
       const attempted_id =
         typeof payload.attempted_id === 'string' ? payload.attempted_id : '';
@@ -204,8 +217,10 @@ export function getGameEventEffects(event: AllGameEvents): {
     case 'ShipsPlaced':
     case 'Winner':
     case 'MatchEnded':
+    case 'MatchForfeited':
       return { board: 'debounced', turn: 'none' };
     case 'ShotProposed':
+    case 'ShotCancelled':
     case 'ShotFired':
       return { board: 'immediate', turn: 'immediate' };
     case 'MatchListUpdated':
@@ -95,3 +95,9 @@ export function validateFleetPayload(ships: string[]): string | null {
 
   return null;
 }
+
+/** A random match seed, kept below 2^53 so it survives the JSON round trip as a number. */
+export function randomSeed(): number {
+  const [hi, lo] = crypto.getRandomValues(new Uint32Array(2));
+  return (hi & 0x1fffff) * 0x100000000 + lo;
+}
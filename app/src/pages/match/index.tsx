@@ -13,7 +13,7 @@ import type { MatchSummary } from '../../api/lobby/LobbyClient';
 import type { AllGameEvents } from '../../types/events';
 import { useGameSubscriptions } from '../../hooks/useGameSubscriptions';
 import { useBattleshipsLobby } from '../../hooks/useBattleshipsLobby';
-import { resolveEffectiveMatchId, SHIP_TARGETS, validateFleetPayload } from './config';
+import { randomSeed, resolveEffectiveMatchId, SHIP_TARGETS, validateFleetPayload } from './config';
 
 import NavBar from '../../components/NavBar';
 import LobbySelect from '../../components/LobbySelect';
@@ -65,6 +65,7 @@ export default function MatchPage() {
   const [matchId, setMatchId] = useState<string>('');
   const [runtimeMatchId, setRuntimeMatchId] = useState<string | null>(null);
   const [player2, setPlayer2] = useState<string>('');
+  const [templateName, setTemplateName] = useState<string>('');
   const [myMatches, setMyMatches] = useState<MatchSummary[]>([]);
   const [creatingMatch, setCreatingMatch] = useState(false);
 
@@ -244,10 +245,10 @@ export default function MatchPage() {
       if (event.type === 'ShotProposed' && !isMyTurn && typeof event.x === 'number' && typeof event.y === 'number') {
         setPendingShot({ x: event.x, y: event.y });
       }
-      if (event.type === 'ShotFired' || event.type === 'MatchEnded' || event.type === 'Winner') {
+      if (event.type === 'ShotFired' || event.type === 'ShotCancelled' || event.type === 'MatchEnded' || event.type === 'Winner') {
         setPendingShot(null);
       }
-      if (event.type === 'MatchListUpdated' || event.type === 'MatchCreated' || event.type === 'MatchEnded' || event.type === 'Winner') {
+      if (event.type === 'MatchListUpdated' || event.type === 'MatchCreated' || event.type === 'MatchForfeited' || event.type === 'MatchEnded' || event.type === 'Winner') {
         refreshMatchList();
       }
     },
@@ -398,14 +399,34 @@ export default function MatchPage() {
     }
     setCreatingMatch(true);
     try {
-      const id = await lobbyApi.createMatch({ player2 });
+      // A template fixes the match's rules; they go to the game context's init below.
+      const name = templateName.trim();
+      const template = name ? await lobbyApi.getTemplate({ name }) : null;
+      if (name && !template) {
+        show({ title: `No template named "${name}"`, variant: 'error' });
+        return;
+      }
+      const id = template
+        ? await lobbyApi.createMatchFromTemplate({ name, player2 })
+        : await lobbyApi.createMatch({ player2 });
       show({ title: `Match allocated: ${id}`, variant: 'success' });
 
       const { groupId: matchSubgroupId } = await mero.admin.createGroupInNamespace(lobby.namespaceId, { alias: `match-${id}` });
       await mero.admin.addGroupMembers(matchSubgroupId, { members: [{ identity: player2, role: 'Member' }] });
 
       const executorKey = lobby.executorPublicKey ?? contextIdentity;
-      const initParams = JSON.stringify({ player1: executorKey, player2, lobby_context_id: currentContext.contextId, match_id: id });
+      // The lobby settles who opens; the game context must start on the same player.
+      const summary = (await lobbyApi.getMatches()).find((m) => m.match_id === id);
+      const initParams = JSON.stringify({
+        player1: executorKey,
+        player2,
+        lobby_context_id: currentContext.contextId,
+        match_id: id,
+        game_rules: template?.game_rules ?? null,
+        seed: randomSeed(),
+        first_mover: summary?.first_mover ?? null,
+        placement_rules: template?.placement_rules ?? null,
+      });
       const initBytes = Array.from(new TextEncoder().encode(initParams));
 
       const { contextId: newContextId } = await mero.admin.createContext({
@@ -430,7 +451,7 @@ export default function MatchPage() {
     } finally {
       setCreatingMatch(false);
     }
-  }, [lobbyApi, mero, currentContext, player2, lobby.executorPublicKey, lobby.namespaceId, contextIdentity, show, navigate]);
+  }, [lobbyApi, mero, currentContext, player2, templateName, lobby.executorPublicKey, lobby.namespaceId, contextIdentity, show, navigate]);
 
   const openGame = useCallback((id: string, contextId: string) => {
     setMatchId(id);
@@ -705,6 +726,8 @@ export default function MatchPage() {
               player2={player2}
               creatingMatch={creatingMatch}
               onPlayer2Change={setPlayer2}
+              templateName={templateName}
+              onTemplateNameChange={setTemplateName}
               onCreateMatch={createMatch}
               matches={myMatches}
               onOpenGame={openGame}
@@ -10,6 +10,7 @@ export type LobbyEventType =
   | 'MatchCreated'
   | 'MatchIdCollision'
   | 'MatchListUpdated'
+  | 'MatchForfeited'
   | 'PlayerStatsUpdated';
 
 export type MatchEventType =
@@ -19,6 +20,7 @@ export type MatchEventType =
   | 'AuditPassed'
   | 'AuditFailed'
   | 'ShotProposed'
+  | 'ShotCancelled'
   | 'ShotFired'
   | 'Winner'
   | 'MatchEnded';
@@ -50,6 +52,14 @@ export interface ShotProposedEvent extends GameEvent {
   y: number;
 }
 
+/** The shooter withdrew their pending shot before it was acknowledged. */
+export interface ShotCancelledEvent extends GameEvent {
+  type: 'ShotCancelled';
+  id: string;
+  x: number;
+  y: number;
+}
+
 export interface ShotFiredEvent extends GameEvent {
   type: 'ShotFired';
   id: string;
@@ -72,6 +82,14 @@ export interface MatchListUpdatedEvent extends GameEvent {
   type: 'MatchListUpdated';
 }
 
+/** Lobby-side: `player` forfeited match `id` (e.g. via `forfeit_all`). */
+export interface MatchForfeitedEvent extends GameEvent {
+  type: 'MatchForfeited';
+  id: string;
+  /** Base58 of the forfeiting player's PublicKey. */
+  player: string;
+}
+
 export interface PlayerStatsUpdatedEvent extends GameEvent {
   type: 'PlayerStatsUpdated';
 }
@@ -121,6 +139,7 @@ export type LobbyEvent =
   | MatchCreatedEvent
   | MatchIdCollisionEvent
   | MatchListUpdatedEvent
+  | MatchForfeitedEvent
   | PlayerStatsUpdatedEvent;
 
 export type MatchEvent =
@@ -130,6 +149,7 @@ export type MatchEvent =
   | AuditPassedEvent
   | AuditFailedEvent
   | ShotProposedEvent
+  | ShotCancelledEvent
   | ShotFiredEvent
   | WinnerEvent
   | MatchEndedEvent;
@@ -140,6 +160,7 @@ const LOBBY_EVENT_TYPES: ReadonlySet<string> = new Set<LobbyEventType>([
   'MatchCreated',
   'MatchIdCollision',
   'MatchListUpdated',
+  'MatchForfeited',
   'PlayerStatsUpdated',
 ]);
 
@@ -8,12 +8,17 @@ fn main() {
 
     let module_files = [
         "lib.rs",
+        "density.rs",
         "events.rs",
+        "notation.rs",
         "audit.rs",
         "players.rs",
         "board.rs",
         "ships.rs",
         "validation.rs",
+        "rules.rs",
+        // Rule types are defined in the types crate; see `rules.rs`.
+        "../../types/src/rules.rs",
     ];
 
     for name in &module_files {
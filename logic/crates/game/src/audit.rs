@@ -9,7 +9,7 @@
 use calimero_storage::collections::{LwwRegister, UnorderedMap};
 use sha2::{Digest, Sha256};
 
-use crate::board::{Cell, BOARD_SIZE};
+use crate::board::{Board, Cell};
 use crate::is_ship_cell;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -49,10 +49,11 @@ pub fn verify_commitment(board_bytes: &[u8], salt: &[u8; 16], expected: &[u8; 32
     &got == expected
 }
 
-/// Replay every recorded shot against the revealed board. A `Hit` at a
-/// non-ship cell or a `Miss` at a ship cell is a lie.
+/// Replay every recorded shot against the revealed `size`x`size` board. A
+/// `Hit` at a non-ship cell or a `Miss` at a ship cell is a lie.
 pub fn replay_shots(
     own_board_cells: &[u8],
+    size: u8,
     shots_against_me: &UnorderedMap<[u8; 1], LwwRegister<u8>>,
 ) -> Result<(), AuditFailure> {
     // Snapshot keys so we don't borrow across the map.
@@ -66,9 +67,9 @@ pub fn replay_shots(
             _ => continue,
         };
         let idx_flat = key[0];
-        let x = idx_flat % BOARD_SIZE;
-        let y = idx_flat / BOARD_SIZE;
-        let idx = (y as usize) * (BOARD_SIZE as usize) + (x as usize);
+        let x = idx_flat % size;
+        let y = idx_flat / size;
+        let idx = Board::idx(size, x, y);
         if idx >= own_board_cells.len() {
             continue;
         }
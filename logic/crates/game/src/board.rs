@@ -6,15 +6,18 @@
 //! ## Key Types
 //!
 //! - **`Coordinate`** - Represents a position on the board with x,y coordinates
-//! - **`Cell`** - Represents the state of a board cell (Empty, Ship, Hit, Miss, Pending)
+//! - **`Cell`** - Represents the state of a board cell (Empty, Ship, Hit, Miss, Pending, Sunk)
 //! - **`Board`** - Represents the game board as a flat vector of cells
+//! - **`Orientation`** - Direction a straight ship runs in (see `line_coordinates`)
+//! - **`CellCodec`** - Numeric cell codes for clients of other implementations
 //!
 //! ## Board Layout
 //!
-//! The board is a 10x10 grid where:
-//! - Coordinates are 0-indexed (0-9 for both x and y)
+//! The board is a square grid, 10x10 unless the match picks another size:
+//! - Coordinates are 0-indexed (0..size for both x and y)
 //! - Cells are stored in row-major order (y * width + x)
-//! - The board size is defined by the `BOARD_SIZE` constant
+//! - The classic size is the `BOARD_SIZE` constant; every function that
+//!   needs the size takes it as a parameter
 //!
 //! ## Usage Examples
 //!
@@ -22,8 +25,8 @@
 //! ```rust
 //! use battleship::board::{Coordinate, BOARD_SIZE};
 //!
-//! let coord = Coordinate::new(5, 3)?; // (5, 3) position
-//! assert!(coord.is_valid());
+//! let coord = Coordinate::new(5, 3, BOARD_SIZE)?; // (5, 3) position
+//! assert!(coord.is_valid(BOARD_SIZE));
 //! ```
 //!
 //! ### Working with Boards
@@ -39,31 +42,32 @@
 use battleships_types::GameError;
 use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use calimero_sdk::serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 // ============================================================================
 // BOARD MODULE - Everything related to game boards and coordinates
 // ============================================================================
 
 /// Standard board size for battleship (10x10 grid)
-pub const BOARD_SIZE: u8 = 10;
+pub const BOARD_SIZE: u8 = crate::rules::CLASSIC_BOARD_SIZE;
 
 /// Represents a coordinate position on the game board
 ///
-/// Coordinates are 0-indexed and must be within the board bounds (0 to BOARD_SIZE-1).
+/// Coordinates are 0-indexed and must be within the board bounds (0 to size-1).
 /// This struct implements ordering traits to allow use in collections like BTreeSet.
 ///
 /// # Fields
-/// * `x` - The x-coordinate (column, 0-9)
-/// * `y` - The y-coordinate (row, 0-9)
+/// * `x` - The x-coordinate (column, 0..size)
+/// * `y` - The y-coordinate (row, 0..size)
 ///
 /// # Example
 /// ```rust
-/// use battleship::board::Coordinate;
+/// use battleship::board::{Coordinate, BOARD_SIZE};
 ///
-/// let coord = Coordinate::new(5, 3)?;
+/// let coord = Coordinate::new(5, 3, BOARD_SIZE)?;
 /// assert_eq!(coord.x, 5);
 /// assert_eq!(coord.y, 3);
-/// assert!(coord.is_valid());
+/// assert!(coord.is_valid(BOARD_SIZE));
 /// ```
 #[derive(
     Debug,
@@ -81,28 +85,120 @@ pub const BOARD_SIZE: u8 = 10;
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
 pub struct Coordinate {
-    /// The x-coordinate (column, 0-9)
+    /// The x-coordinate (column, 0..size)
     pub x: u8,
-    /// The y-coordinate (row, 0-9)
+    /// The y-coordinate (row, 0..size)
     pub y: u8,
 }
 
 impl Coordinate {
-    pub fn new(x: u8, y: u8) -> Result<Coordinate, GameError> {
-        if x >= BOARD_SIZE || y >= BOARD_SIZE {
+    /// `(x, y)`, if it is on a `size`x`size` board.
+    pub fn new(x: u8, y: u8, size: u8) -> Result<Coordinate, GameError> {
+        if !Board::in_bounds(size, x, y) {
             return Err(GameError::Invalid("coordinate out of bounds".into()));
         }
         Ok(Coordinate { x, y })
     }
 
-    pub fn is_valid(&self) -> bool {
-        self.x < BOARD_SIZE && self.y < BOARD_SIZE
+    pub fn is_valid(&self, size: u8) -> bool {
+        Board::in_bounds(size, self.x, self.y)
     }
+
+    /// The cell `(dx, dy)` away on a `size`x`size` board, or `None` if that
+    /// falls off the board.
+    pub fn offset(&self, dx: i8, dy: i8, size: u8) -> Option<Coordinate> {
+        let x = self.x.checked_add_signed(dx)?;
+        let y = self.y.checked_add_signed(dy)?;
+        Board::in_bounds(size, x, y).then_some(Coordinate { x, y })
+    }
+
+    /// Whether `other` is one of this cell's eight neighbours on a
+    /// `size`x`size` board, across the edges too when `wrap` is set. This
+    /// is the rule ships may not break: no two of them touch, not even
+    /// diagonally. A cell doesn't touch itself; that is an overlap.
+    pub fn touches(&self, other: &Coordinate, size: u8, wrap: bool) -> bool {
+        let gap = |a: u8, b: u8| {
+            let d = a.abs_diff(b);
+            if wrap {
+                d.min(size.saturating_sub(d))
+            } else {
+                d
+            }
+        };
+        self != other && gap(self.x, other.x) <= 1 && gap(self.y, other.y) <= 1
+    }
+}
+
+/// Direction a straight ship extends from its top/left cell.
+#[derive(
+    Debug, Clone, Copy, BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum Orientation {
+    /// Increasing x
+    Horizontal,
+    /// Increasing y
+    Vertical,
+}
+
+/// Cells of a straight line of `length` cells starting at `start` and
+/// running in `orientation`, on a `size`x`size` board. The shared primitive
+/// behind the ship shorthand and anything else that walks N cells in a
+/// direction. Fails with "ship off board" rather than clamping or wrapping.
+pub fn line_coordinates(
+    start: Coordinate,
+    orientation: Orientation,
+    length: u8,
+    size: u8,
+) -> Result<Vec<Coordinate>, GameError> {
+    let (dx, dy) = match orientation {
+        Orientation::Horizontal => (1u16, 0u16),
+        Orientation::Vertical => (0, 1),
+    };
+    (0..length as u16)
+        .map(|i| {
+            let x = start.x as u16 + dx * i;
+            let y = start.y as u16 + dy * i;
+            if x >= size as u16 || y >= size as u16 {
+                return Err(GameError::Invalid("ship off board".into()));
+            }
+            Ok(Coordinate {
+                x: x as u8,
+                y: y as u8,
+            })
+        })
+        .collect()
+}
+
+/// Whether the cells of a straight line run without gaps on a `size`-wide
+/// board. With `wrap` the board is a torus, so a line may leave one edge
+/// and continue from the opposite one (`8,0;9,0;0,0`). Straightness is the
+/// caller's to check.
+pub fn is_contiguous_line(coords: &[Coordinate], size: u8, wrap: bool) -> bool {
+    if coords.len() <= 1 {
+        return true;
+    }
+    let same_x = coords.iter().all(|c| c.x == coords[0].x);
+    let mut along: Vec<i16> = coords
+        .iter()
+        .map(|c| if same_x { c.y } else { c.x } as i16)
+        .collect();
+    along.sort_unstable();
+    let mut gaps = along.windows(2).filter(|w| w[1] - w[0] != 1).count();
+    if !wrap {
+        return gaps == 0;
+    }
+    // On a ring the line may be broken once, where it crosses the edge.
+    if along[0] + size as i16 - along[along.len() - 1] != 1 {
+        gaps += 1;
+    }
+    gaps <= 1
 }
 
 /// Represents the state of a cell on the game board
 ///
-/// Each cell can be in one of five states, representing different game conditions.
+/// Each cell can be in one of six states, representing different game conditions.
 /// The enum provides conversion methods to/from u8 for serialization.
 ///
 /// # Variants
@@ -111,6 +207,7 @@ impl Coordinate {
 /// * `Hit` - Cell was shot and contains a hit ship
 /// * `Miss` - Cell was shot but was empty
 /// * `Pending` - Cell has a pending shot (not yet resolved)
+/// * `Sunk` - Cell was hit and its whole ship has been sunk
 ///
 /// # Example
 /// ```rust
@@ -136,6 +233,9 @@ pub enum Cell {
     Miss,
     /// Cell has a pending shot (not yet resolved)
     Pending,
+    /// Cell was hit and its whole ship has been sunk. Only shot boards
+    /// shown to clients use it; stored shots stay `Hit`.
+    Sunk,
 }
 
 impl Cell {
@@ -146,6 +246,7 @@ impl Cell {
             Cell::Hit => 2,
             Cell::Miss => 3,
             Cell::Pending => 4,
+            Cell::Sunk => 5,
         }
     }
 
@@ -155,11 +256,122 @@ impl Cell {
             2 => Cell::Hit,
             3 => Cell::Miss,
             4 => Cell::Pending,
+            5 => Cell::Sunk,
+            _ => Cell::Empty,
+        }
+    }
+
+    /// Neither a ship nor a shot.
+    pub fn is_empty(self) -> bool {
+        self == Cell::Empty
+    }
+
+    /// An intact (not yet hit) ship cell.
+    pub fn is_ship(self) -> bool {
+        self == Cell::Ship
+    }
+
+    /// A shot that has been resolved, either way: `Hit` (or `Sunk`) or
+    /// `Miss`. Such a cell can't be fired at again.
+    pub fn is_resolved_shot(self) -> bool {
+        matches!(self, Cell::Hit | Cell::Miss | Cell::Sunk)
+    }
+
+    /// A shot that is waiting for the defender's acknowledgement.
+    pub fn is_pending(self) -> bool {
+        self == Cell::Pending
+    }
+}
+
+/// Maps cells to and from the numeric codes a client expects, so board
+/// views can be produced for clients built against other battleship
+/// backends without every consumer remapping.
+pub trait CellCodec {
+    fn encode(&self, cell: Cell) -> u8;
+    fn decode(&self, value: u8) -> Cell;
+}
+
+/// This crate's own codes: `Cell::to_u8` / `Cell::from_u8`.
+pub struct NativeCodec;
+
+impl CellCodec for NativeCodec {
+    fn encode(&self, cell: Cell) -> u8 {
+        cell.to_u8()
+    }
+
+    fn decode(&self, value: u8) -> Cell {
+        Cell::from_u8(value)
+    }
+}
+
+/// Shot-first codes: 0=water, 1=hit, 2=miss, 3=ship, 4=pending. Unknown
+/// codes decode as water. These clients know no sunk marker, so `Sunk`
+/// encodes as a hit.
+pub struct HitMissCodec;
+
+impl CellCodec for HitMissCodec {
+    fn encode(&self, cell: Cell) -> u8 {
+        match cell {
+            Cell::Empty => 0,
+            Cell::Hit | Cell::Sunk => 1,
+            Cell::Miss => 2,
+            Cell::Ship => 3,
+            Cell::Pending => 4,
+        }
+    }
+
+    fn decode(&self, value: u8) -> Cell {
+        match value {
+            1 => Cell::Hit,
+            2 => Cell::Miss,
+            3 => Cell::Ship,
+            4 => Cell::Pending,
             _ => Cell::Empty,
         }
     }
 }
 
+/// A `CellCodec` a client can ask for by name.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum CellEncoding {
+    /// `NativeCodec`
+    #[default]
+    Native,
+    /// `HitMissCodec`
+    HitMiss,
+}
+
+impl CellEncoding {
+    pub fn codec(self) -> &'static dyn CellCodec {
+        match self {
+            CellEncoding::Native => &NativeCodec,
+            CellEncoding::HitMiss => &HitMissCodec,
+        }
+    }
+
+    /// Re-encodes natively coded `cells` under this encoding.
+    pub fn recode(self, cells: &[u8]) -> Vec<u8> {
+        let codec = self.codec();
+        cells
+            .iter()
+            .map(|&value| codec.encode(Cell::from_u8(value)))
+            .collect()
+    }
+}
+
 /// Represents a game board as a flat vector of cells
 ///
 /// The board is stored as a flat vector in row-major order (y * width + x).
@@ -168,8 +380,10 @@ impl Cell {
 ///
 /// # Storage Format
 /// The board is stored as `Vec<u8>` where each element represents a cell state:
-/// - Index calculation: `y * BOARD_SIZE + x`
-/// - Cell values: 0=Empty, 1=Ship, 2=Hit, 3=Miss, 4=Pending
+/// - Index calculation: `y * size + x`
+/// - Cell values: 0=Empty, 1=Ship, 2=Hit, 3=Miss, 4=Pending, 5=Sunk
+/// - `Sunk` is never stored; under `reveal_on_sink`, shot views report it
+///   for every hit cell of a ship that has been sunk
 ///
 /// # Example
 /// ```rust
@@ -190,6 +404,27 @@ impl Board {
         Board(vec![0; (size as usize) * (size as usize)])
     }
 
+    /// Rejects a board whose cell vector isn't `size * size` long. Boards
+    /// coming out of storage or an imported seed are only as trustworthy as
+    /// their bytes, and `get`/`set` index without bounds checks.
+    pub fn check_len(&self, size: u8) -> Result<(), GameError> {
+        let expected = (size as usize) * (size as usize);
+        if self.0.len() != expected {
+            return Err(GameError::Invalid(format!(
+                "board has {} cells, expected {}",
+                self.0.len(),
+                expected
+            )));
+        }
+        Ok(())
+    }
+
+    /// Side length of the square board these cells form. Only meaningful
+    /// once `check_len` has confirmed the board is square.
+    pub fn side(&self) -> u8 {
+        self.0.len().isqrt() as u8
+    }
+
     pub fn idx(size: u8, x: u8, y: u8) -> usize {
         (y as usize) * (size as usize) + (x as usize)
     }
@@ -206,35 +441,598 @@ impl Board {
         self.0[Board::idx(size, x, y)] = cell.to_u8();
     }
 
-    pub fn is_adjacent_violation(&self, size: u8, x: u8, y: u8) -> bool {
-        let xi = x as i16;
-        let yi = y as i16;
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                if dx == 0 && dy == 0 {
-                    continue;
+    /// Writes `cell` only if `(x, y)` is currently `Empty`; returns whether
+    /// the write happened. Placement uses this so an overlap that slips past
+    /// validation fails loudly instead of silently merging two ships.
+    pub fn set_if_empty(&mut self, size: u8, x: u8, y: u8, cell: Cell) -> bool {
+        if self.get(size, x, y) != Cell::Empty {
+            return false;
+        }
+        self.set(size, x, y, cell);
+        true
+    }
+
+    /// Run-length encodes the board for bandwidth-sensitive clients. A run
+    /// of `Empty` cells becomes the pair `[0, run_len]` (runs longer than
+    /// 255 are split); every other cell is copied as its own byte. A fresh
+    /// 10x10 board packs into 2 bytes.
+    pub fn to_packed(&self) -> Vec<u8> {
+        let mut packed = Vec::new();
+        let mut run: u8 = 0;
+        for &cell in &self.0 {
+            if cell == Cell::Empty.to_u8() {
+                if run == u8::MAX {
+                    packed.extend_from_slice(&[0, run]);
+                    run = 0;
                 }
-                let nx = xi + dx;
-                let ny = yi + dy;
-                if nx < 0 || ny < 0 {
-                    continue;
+                run += 1;
+                continue;
+            }
+            if run > 0 {
+                packed.extend_from_slice(&[0, run]);
+                run = 0;
+            }
+            packed.push(cell);
+        }
+        if run > 0 {
+            packed.extend_from_slice(&[0, run]);
+        }
+        packed
+    }
+
+    /// Inverse of `to_packed`. Rejects truncated input and zero-length runs;
+    /// callers still need `check_len` to pin the board size.
+    pub fn from_packed(packed: &[u8]) -> Result<Board, GameError> {
+        let mut cells = Vec::new();
+        let mut bytes = packed.iter();
+        while let Some(&b) = bytes.next() {
+            if b != Cell::Empty.to_u8() {
+                cells.push(b);
+                continue;
+            }
+            match bytes.next() {
+                Some(&run) if run > 0 => cells.resize(cells.len() + run as usize, 0),
+                _ => return Err(GameError::Invalid("malformed packed board".into())),
+            }
+        }
+        Ok(Board(cells))
+    }
+
+    /// Stable hash of the cells, the same on every node and build, so a
+    /// polling client can tell whether a board changed without diffing it.
+    pub fn fingerprint(&self) -> u64 {
+        digest_u64(&self.0)
+    }
+
+    /// `fingerprint` reading `Pending` cells as the `Empty` they sit on, so
+    /// a shot waiting for acknowledgement doesn't register as a change;
+    /// only settled cells count.
+    pub fn settled_fingerprint(&self) -> u64 {
+        let empty = Cell::Empty.to_u8();
+        let settled: Vec<u8> = self
+            .0
+            .iter()
+            .map(|&c| {
+                if Cell::from_u8(c).is_pending() {
+                    empty
+                } else {
+                    c
                 }
-                let nxu = nx as u8;
-                let nyu = ny as u8;
-                if nxu >= size || nyu >= size {
+            })
+            .collect();
+        digest_u64(&settled)
+    }
+
+    /// Resets every cell to `Empty` in place, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.fill(Cell::Empty);
+    }
+
+    /// Sets every cell to `cell` in place. Works on whatever length the
+    /// backing vector has, so it needs no size argument.
+    pub fn fill(&mut self, cell: Cell) {
+        self.0.fill(cell.to_u8());
+    }
+
+    /// The board turned a quarter clockwise: the top row becomes the right
+    /// column. Four turns give back the original.
+    pub fn rotate90(&self, size: u8) -> Board {
+        let last = size.saturating_sub(1);
+        self.remap(size, |x, y| (y, last - x))
+    }
+
+    /// The board mirrored left to right. Its own inverse.
+    pub fn flip_horizontal(&self, size: u8) -> Board {
+        let last = size.saturating_sub(1);
+        self.remap(size, |x, y| (last - x, y))
+    }
+
+    /// The board mirrored top to bottom. Its own inverse.
+    pub fn flip_vertical(&self, size: u8) -> Board {
+        let last = size.saturating_sub(1);
+        self.remap(size, |x, y| (x, last - y))
+    }
+
+    /// New board whose cell `(x, y)` is this board's cell `from(x, y)`.
+    fn remap(&self, size: u8, from: impl Fn(u8, u8) -> (u8, u8)) -> Board {
+        let mut out = Board::new_zeroed(size);
+        for y in 0..size {
+            for x in 0..size {
+                let (sx, sy) = from(x, y);
+                out.0[Board::idx(size, x, y)] = self.0[Board::idx(size, sx, sy)];
+            }
+        }
+        out
+    }
+
+    /// In-bounds neighbours of `(x, y)` on a `size`x`size` board, in
+    /// row-major order of offset: the four orthogonal ones, plus the four
+    /// diagonal ones when `diagonal` is set. With `wrap` the board is a
+    /// torus and neighbours past an edge come from the opposite one.
+    /// Never allocates.
+    pub(crate) fn neighbor_coords(
+        size: u8,
+        x: u8,
+        y: u8,
+        diagonal: bool,
+        wrap: bool,
+    ) -> impl Iterator<Item = Coordinate> {
+        (-1i8..=1)
+            .flat_map(|dy| (-1i8..=1).map(move |dx| (dx, dy)))
+            .filter(move |&(dx, dy)| (dx, dy) != (0, 0) && (diagonal || dx == 0 || dy == 0))
+            .filter_map(move |(dx, dy)| {
+                if !wrap {
+                    return Coordinate { x, y }.offset(dx, dy, size);
+                }
+                let step = |v: u8, d: i8| (i16::from(v) + i16::from(d)).rem_euclid(i16::from(size));
+                Some(Coordinate {
+                    x: step(x, dx) as u8,
+                    y: step(y, dy) as u8,
+                })
+            })
+    }
+
+    /// Every in-bounds neighbour of `(x, y)` with its current cell, in
+    /// row-major order. Diagonal neighbours are included when `diagonal` is
+    /// set, so corner cells yield 3 (or 2 orthogonal) entries; on a
+    /// wrapping board every cell has the full 8 (or 4).
+    pub fn neighbors_of(
+        &self,
+        size: u8,
+        x: u8,
+        y: u8,
+        diagonal: bool,
+        wrap: bool,
+    ) -> Vec<(Coordinate, Cell)> {
+        let mut out = Vec::with_capacity(if diagonal { 8 } else { 4 });
+        out.extend(
+            Board::neighbor_coords(size, x, y, diagonal, wrap)
+                .map(|c| (c, self.get(size, c.x, c.y))),
+        );
+        out
+    }
+
+    /// Whether a `Ship` cell of this board touches `(x, y)`, by the rule of
+    /// `Coordinate::touches`.
+    pub fn is_adjacent_violation(&self, size: u8, x: u8, y: u8, wrap: bool) -> bool {
+        // Walks the neighbours lazily: this runs for every cell of every
+        // ship on the placement path.
+        Board::neighbor_coords(size, x, y, true, wrap).any(|c| self.get(size, c.x, c.y).is_ship())
+    }
+
+    /// Cells of the ship covering `(x, y)`, found by flood-filling
+    /// orthogonally over `Ship` and `Hit` cells. Because ships may not touch
+    /// (not even diagonally), each connected group is exactly one ship.
+    /// Returns an empty vector when `(x, y)` holds no ship. With `wrap` a
+    /// ship crossing the board edge is one group.
+    pub fn ship_group_at(&self, size: u8, x: u8, y: u8, wrap: bool) -> Vec<Coordinate> {
+        let is_ship_part =
+            |cx: u8, cy: u8| matches!(self.get(size, cx, cy), Cell::Ship | Cell::Hit);
+        if !Board::in_bounds(size, x, y) || !is_ship_part(x, y) {
+            return Vec::new();
+        }
+        let mut group = vec![Coordinate { x, y }];
+        let mut i = 0;
+        while i < group.len() {
+            let c = group[i];
+            for next in Board::neighbor_coords(size, c.x, c.y, false, wrap) {
+                if is_ship_part(next.x, next.y) && !group.contains(&next) {
+                    group.push(next);
+                }
+            }
+            i += 1;
+        }
+        group.sort();
+        group
+    }
+
+    /// Every ship on the board as its group of cells (see `ship_group_at`),
+    /// ordered by each group's first cell in row-major order.
+    pub fn connected_ship_groups(&self, size: u8, wrap: bool) -> Vec<Vec<Coordinate>> {
+        let mut groups: Vec<Vec<Coordinate>> = Vec::new();
+        for y in 0..size {
+            for x in 0..size {
+                let here = Coordinate { x, y };
+                if groups.iter().any(|g| g.contains(&here)) {
                     continue;
                 }
-                if matches!(self.get(size, nxu, nyu), Cell::Ship) {
-                    return true;
+                let group = self.ship_group_at(size, x, y, wrap);
+                if !group.is_empty() {
+                    groups.push(group);
+                }
+            }
+        }
+        groups
+    }
+
+    /// First pair of ship cells from different ships that touch, or `None`
+    /// if every ship keeps its distance. Checks a whole board, so it covers
+    /// imported or reconstructed boards that never went through placement.
+    ///
+    /// Ships are told apart with `connected_ship_groups`: cells within a
+    /// straight group are one ship and may touch. A group that bends is two
+    /// ships pressed together, reported at the bend. Otherwise two groups
+    /// touching at a corner (when `diagonal` is set) are the violation.
+    pub fn has_adjacency_violations(
+        &self,
+        size: u8,
+        diagonal: bool,
+        wrap: bool,
+    ) -> Option<(Coordinate, Coordinate)> {
+        let groups = self.connected_ship_groups(size, wrap);
+        for (i, group) in groups.iter().enumerate() {
+            for &c in group {
+                let (across, down): (Vec<_>, Vec<_>) =
+                    Board::neighbor_coords(size, c.x, c.y, false, wrap)
+                        .filter(|n| *n != c && group.contains(n))
+                        .partition(|n| n.y == c.y);
+                if let (false, Some(&below)) = (across.is_empty(), down.first()) {
+                    return Some((c, below));
+                }
+                let touching = Board::neighbor_coords(size, c.x, c.y, diagonal, wrap).find(|n| {
+                    groups
+                        .iter()
+                        .position(|g| g.contains(n))
+                        .is_some_and(|j| j != i)
+                });
+                if let Some(n) = touching {
+                    return Some((c, n));
                 }
             }
         }
-        false
+        None
     }
 }
 
+/// First eight bytes of the SHA-256 of `bytes`.
+fn digest_u64(bytes: &[u8]) -> u64 {
+    let digest = Sha256::digest(bytes);
+    let mut head = [0u8; 8];
+    head.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(head)
+}
+
 // ============================================================================
 // BOARD VIEWS - Data transfer objects for API responses
 // ============================================================================
 
 // OwnBoardView and ShotsView are now defined in lib.rs for ABI compatibility
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codecs_round_trip_every_cell() {
+        let cells = [
+            Cell::Empty,
+            Cell::Ship,
+            Cell::Hit,
+            Cell::Miss,
+            Cell::Pending,
+        ];
+        for encoding in [CellEncoding::Native, CellEncoding::HitMiss] {
+            let codec = encoding.codec();
+            for cell in cells {
+                assert_eq!(codec.decode(codec.encode(cell)), cell);
+            }
+        }
+        let native: Vec<u8> = cells.iter().map(|c| c.to_u8()).collect();
+        assert_eq!(CellEncoding::Native.recode(&native), native);
+        assert_eq!(CellEncoding::HitMiss.recode(&native), vec![0, 3, 1, 2, 4]);
+
+        // Sunk has a native code of its own and reads as a hit elsewhere.
+        assert_eq!(Cell::from_u8(Cell::Sunk.to_u8()), Cell::Sunk);
+        assert_eq!(CellEncoding::HitMiss.recode(&[5]), vec![1]);
+    }
+
+    #[test]
+    fn coordinate_new_is_bounds_checked_against_the_board_size() {
+        assert_eq!(
+            Coordinate::new(3, 9, BOARD_SIZE).unwrap(),
+            Coordinate { x: 3, y: 9 }
+        );
+        assert!(matches!(
+            Coordinate::new(BOARD_SIZE, 0, BOARD_SIZE),
+            Err(GameError::Invalid(_))
+        ));
+        assert!(Coordinate::new(7, 7, 8).is_ok());
+        assert!(Coordinate::new(3, 8, 8).is_err());
+        assert!(!Coordinate { x: 8, y: 0 }.is_valid(8));
+    }
+
+    #[test]
+    fn offset_stays_on_the_board() {
+        let at = |x, y| Coordinate { x, y };
+        assert_eq!(at(0, 0).offset(-1, 0, BOARD_SIZE), None);
+        assert_eq!(at(0, 0).offset(0, -1, BOARD_SIZE), None);
+        assert_eq!(at(0, 0).offset(1, 1, BOARD_SIZE), Some(at(1, 1)));
+        assert_eq!(at(9, 9).offset(1, 0, BOARD_SIZE), None);
+        assert_eq!(at(9, 9).offset(-9, -9, BOARD_SIZE), Some(at(0, 0)));
+        assert_eq!(at(3, 3).offset(0, 0, BOARD_SIZE), Some(at(3, 3)));
+        assert_eq!(at(3, 3).offset(2, 0, 5), None);
+        assert_eq!(at(250, 0).offset(i8::MAX, 0, u8::MAX), None);
+    }
+
+    #[test]
+    fn fill_and_clear_touch_every_cell_in_place() {
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        board.fill(Cell::Miss);
+        assert!(board.0.iter().all(|&c| c == Cell::Miss.to_u8()));
+        board.clear();
+        assert_eq!(board.0.len(), 100);
+        assert!(board.0.iter().all(|&c| c == Cell::Empty.to_u8()));
+    }
+
+    #[test]
+    fn touching_cells_match_the_board_rule() {
+        let occupied = [Coordinate { x: 0, y: 3 }, Coordinate { x: 9, y: 9 }];
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        for c in &occupied {
+            board.set(BOARD_SIZE, c.x, c.y, Cell::Ship);
+        }
+        for wrap in [false, true] {
+            for y in 0..BOARD_SIZE {
+                for x in 0..BOARD_SIZE {
+                    let cell = Coordinate { x, y };
+                    if occupied.contains(&cell) {
+                        continue;
+                    }
+                    assert_eq!(
+                        occupied.iter().any(|c| c.touches(&cell, BOARD_SIZE, wrap)),
+                        board.is_adjacent_violation(BOARD_SIZE, x, y, wrap),
+                        "({x},{y}) wrap={wrap}"
+                    );
+                }
+            }
+        }
+        assert!(!occupied[0].touches(&occupied[0], BOARD_SIZE, false));
+    }
+
+    #[test]
+    fn neighbors_of_clips_to_the_board_and_reports_cells() {
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        board.set(BOARD_SIZE, 1, 1, Cell::Ship);
+        board.set(BOARD_SIZE, 1, 0, Cell::Miss);
+
+        let corner = board.neighbors_of(BOARD_SIZE, 0, 0, true, false);
+        assert_eq!(
+            corner,
+            vec![
+                (Coordinate { x: 1, y: 0 }, Cell::Miss),
+                (Coordinate { x: 0, y: 1 }, Cell::Empty),
+                (Coordinate { x: 1, y: 1 }, Cell::Ship),
+            ]
+        );
+        assert_eq!(board.neighbors_of(BOARD_SIZE, 0, 0, false, false).len(), 2);
+        assert_eq!(board.neighbors_of(BOARD_SIZE, 5, 5, true, false).len(), 8);
+        assert_eq!(board.neighbors_of(BOARD_SIZE, 9, 5, false, false).len(), 3);
+    }
+
+    #[test]
+    fn wrapping_boards_join_opposite_edges() {
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        board.set(BOARD_SIZE, 9, 9, Cell::Ship);
+        let corner = board.neighbors_of(BOARD_SIZE, 0, 0, true, true);
+        assert_eq!(corner.len(), 8);
+        assert!(corner.contains(&(Coordinate { x: 9, y: 9 }, Cell::Ship)));
+        assert!(board.is_adjacent_violation(BOARD_SIZE, 0, 0, true));
+        assert!(!board.is_adjacent_violation(BOARD_SIZE, 0, 0, false));
+
+        for x in [8, 9, 0] {
+            board.set(BOARD_SIZE, x, 4, Cell::Ship);
+        }
+        assert_eq!(board.ship_group_at(BOARD_SIZE, 0, 4, true).len(), 3);
+        assert_eq!(board.ship_group_at(BOARD_SIZE, 0, 4, false).len(), 1);
+
+        let across = [(8, 4), (9, 4), (0, 4)].map(|(x, y)| Coordinate { x, y });
+        assert!(is_contiguous_line(&across, BOARD_SIZE, true));
+        assert!(!is_contiguous_line(&across, BOARD_SIZE, false));
+        let gapped = [(8, 4), (0, 4), (1, 4), (3, 4)].map(|(x, y)| Coordinate { x, y });
+        assert!(!is_contiguous_line(&gapped, BOARD_SIZE, true));
+        let inside = [(2, 4), (3, 4), (4, 4)].map(|(x, y)| Coordinate { x, y });
+        assert!(is_contiguous_line(&inside, BOARD_SIZE, true));
+        assert!(is_contiguous_line(&inside, BOARD_SIZE, false));
+    }
+
+    #[test]
+    fn adjacency_violations_tell_ships_apart() {
+        let at = |x, y| Coordinate { x, y };
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        for x in 0..3 {
+            board.set(BOARD_SIZE, x, 0, Cell::Ship);
+        }
+        board.set(BOARD_SIZE, 5, 5, Cell::Hit);
+        board.set(BOARD_SIZE, 5, 6, Cell::Ship);
+        assert_eq!(
+            board.has_adjacency_violations(BOARD_SIZE, true, false),
+            None
+        );
+
+        // Corner to corner: only a violation when diagonals count.
+        board.set(BOARD_SIZE, 6, 7, Cell::Ship);
+        assert_eq!(
+            board.has_adjacency_violations(BOARD_SIZE, true, false),
+            Some((at(5, 6), at(6, 7)))
+        );
+        assert_eq!(
+            board.has_adjacency_violations(BOARD_SIZE, false, false),
+            None
+        );
+
+        // A bend means two ships side by side.
+        board.set(BOARD_SIZE, 6, 7, Cell::Empty);
+        board.set(BOARD_SIZE, 2, 1, Cell::Ship);
+        assert_eq!(
+            board.has_adjacency_violations(BOARD_SIZE, false, false),
+            Some((at(2, 0), at(2, 1)))
+        );
+
+        // Across the edge only on a wrapping board.
+        let mut edge = Board::new_zeroed(BOARD_SIZE);
+        edge.set(BOARD_SIZE, 0, 0, Cell::Ship);
+        edge.set(BOARD_SIZE, 9, 9, Cell::Ship);
+        assert_eq!(edge.has_adjacency_violations(BOARD_SIZE, true, false), None);
+        assert!(edge
+            .has_adjacency_violations(BOARD_SIZE, true, true)
+            .is_some());
+    }
+
+    #[test]
+    fn fingerprints_track_settled_cells() {
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        let (fresh, fresh_settled) = (board.fingerprint(), board.settled_fingerprint());
+        assert_eq!(fresh, Board::new_zeroed(BOARD_SIZE).fingerprint());
+
+        board.set(BOARD_SIZE, 4, 4, Cell::Pending);
+        assert_ne!(board.fingerprint(), fresh);
+        assert_eq!(board.settled_fingerprint(), fresh_settled);
+
+        board.set(BOARD_SIZE, 4, 4, Cell::Miss);
+        assert_ne!(board.settled_fingerprint(), fresh_settled);
+        let mut elsewhere = Board::new_zeroed(BOARD_SIZE);
+        elsewhere.set(BOARD_SIZE, 4, 5, Cell::Miss);
+        assert_ne!(elsewhere.settled_fingerprint(), board.settled_fingerprint());
+    }
+
+    #[test]
+    fn cell_predicates_partition_the_states() {
+        let all = [
+            Cell::Empty,
+            Cell::Ship,
+            Cell::Hit,
+            Cell::Miss,
+            Cell::Pending,
+            Cell::Sunk,
+        ];
+        for cell in all {
+            let flags = [
+                cell.is_empty(),
+                cell.is_ship(),
+                cell.is_resolved_shot(),
+                cell.is_pending(),
+            ];
+            assert_eq!(flags.iter().filter(|&&f| f).count(), 1, "{cell:?}");
+        }
+        assert!(Cell::Hit.is_resolved_shot() && Cell::Miss.is_resolved_shot());
+    }
+
+    #[test]
+    fn packed_board_round_trips() {
+        let empty = Board::new_zeroed(BOARD_SIZE);
+        assert_eq!(empty.to_packed(), vec![0, 100]);
+
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        board.set(BOARD_SIZE, 0, 0, Cell::Ship);
+        board.set(BOARD_SIZE, 1, 0, Cell::Hit);
+        board.set(BOARD_SIZE, 5, 5, Cell::Miss);
+        board.set(BOARD_SIZE, 9, 9, Cell::Pending);
+        let packed = board.to_packed();
+        assert!(packed.len() < 10);
+        assert_eq!(Board::from_packed(&packed).unwrap().0, board.0);
+
+        let long = Board(vec![0; 600]);
+        assert_eq!(Board::from_packed(&long.to_packed()).unwrap().0, long.0);
+
+        assert!(Board::from_packed(&[1, 0]).is_err());
+        assert!(Board::from_packed(&[0, 0]).is_err());
+    }
+
+    #[test]
+    fn set_if_empty_refuses_to_clobber() {
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        assert!(board.set_if_empty(BOARD_SIZE, 2, 2, Cell::Ship));
+        // A second ship stamped over the first, as if validation had let the
+        // overlap through.
+        assert!(!board.set_if_empty(BOARD_SIZE, 2, 2, Cell::Ship));
+        board.set(BOARD_SIZE, 3, 3, Cell::Miss);
+        assert!(!board.set_if_empty(BOARD_SIZE, 3, 3, Cell::Ship));
+        assert_eq!(board.get(BOARD_SIZE, 3, 3), Cell::Miss);
+    }
+
+    #[test]
+    fn line_coordinates_walks_and_bounds_checks() {
+        let start = Coordinate { x: 6, y: 2 };
+        assert_eq!(
+            line_coordinates(start, Orientation::Horizontal, 4, BOARD_SIZE).unwrap(),
+            vec![
+                Coordinate { x: 6, y: 2 },
+                Coordinate { x: 7, y: 2 },
+                Coordinate { x: 8, y: 2 },
+                Coordinate { x: 9, y: 2 },
+            ]
+        );
+        assert!(line_coordinates(start, Orientation::Horizontal, 5, BOARD_SIZE).is_err());
+        assert_eq!(
+            line_coordinates(start, Orientation::Vertical, 8, BOARD_SIZE)
+                .unwrap()
+                .len(),
+            8
+        );
+        // Smaller boards bound tighter.
+        assert!(line_coordinates(start, Orientation::Vertical, 3, 4).is_err());
+        assert!(
+            line_coordinates(start, Orientation::Vertical, 0, BOARD_SIZE)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rotations_and_flips_round_trip() {
+        let size = 4;
+        let mut board = Board::new_zeroed(size);
+        // An L in the top-left corner, so every symmetry moves it.
+        board.set(size, 0, 0, Cell::Ship);
+        board.set(size, 1, 0, Cell::Ship);
+        board.set(size, 0, 1, Cell::Hit);
+
+        let turned = board.rotate90(size);
+        assert_eq!(turned.get(size, 3, 0), Cell::Ship);
+        assert_eq!(turned.get(size, 3, 1), Cell::Ship);
+        assert_eq!(turned.get(size, 2, 0), Cell::Hit);
+
+        let mut full = board.clone();
+        for _ in 0..4 {
+            full = full.rotate90(size);
+        }
+        assert_eq!(full.0, board.0);
+        assert_eq!(board.flip_horizontal(size).flip_horizontal(size).0, board.0);
+        assert_eq!(board.flip_vertical(size).flip_vertical(size).0, board.0);
+        // Both flips together are a half turn.
+        assert_eq!(
+            board.flip_horizontal(size).flip_vertical(size).0,
+            board.rotate90(size).rotate90(size).0
+        );
+        assert_eq!(board.flip_horizontal(size).get(size, 3, 0), Cell::Ship);
+        assert_eq!(board.flip_vertical(size).get(size, 0, 2), Cell::Hit);
+    }
+
+    #[test]
+    fn check_len_rejects_wrong_sized_boards() {
+        assert!(Board::new_zeroed(BOARD_SIZE).check_len(BOARD_SIZE).is_ok());
+        assert!(Board(vec![0; 99]).check_len(BOARD_SIZE).is_err());
+        assert!(Board(vec![0; 101]).check_len(BOARD_SIZE).is_err());
+        assert!(Board(Vec::new()).check_len(BOARD_SIZE).is_err());
+    }
+}
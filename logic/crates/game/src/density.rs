@@ -0,0 +1,113 @@
+//! # Density Module
+//!
+//! Hit-density heatmap for bots: how likely each unfired cell of the
+//! opponent's board is to hold a ship, given what the shooter has seen.
+//!
+//! Every ship still afloat is laid in every position it could legally
+//! occupy: a straight line over cells that aren't known misses or sunk
+//! ships, and not touching a sunk ship (ships never touch). Each unfired
+//! cell a position covers scores that position's weight. Positions that
+//! run through unsunk hits are weighted up, since the ship under a hit is
+//! certainly nearby, which steers the map from hunting to finishing a
+//! ship off. Ships afloat aren't placed jointly, so the map is a
+//! heuristic rather than an exact posterior.
+
+use crate::board::{Board, Cell, Coordinate};
+
+/// Extra weight a position earns for each unsunk hit it covers.
+const HIT_WEIGHT: u32 = 20;
+
+/// Relative likelihood of a ship at each cell of a `size`x`size` target
+/// board, row-major. `shots` holds the shooter's view of each cell (a
+/// `Cell` code) and `sunk` a non-zero value on cells of sunk ships;
+/// `afloat` lists the lengths of the ships not yet sunk. Fired cells score
+/// 0; the rest sum to 1, or are all 0 when no ship fits anywhere.
+pub fn hit_density(shots: &[u8], sunk: &[u8], size: u8, afloat: &[u8], wrap: bool) -> Vec<f32> {
+    let n = usize::from(size) * usize::from(size);
+    let cell = |c: Coordinate| Board::idx(size, c.x, c.y);
+    let sunk_at = |c: Coordinate| sunk.get(cell(c)).is_some_and(|&s| s > 0);
+    // Misses, sunk ships and the cells around them (ships never touch)
+    // can't hold a ship afloat.
+    let mut blocked = vec![false; n];
+    for y in 0..size {
+        for x in 0..size {
+            let here = Coordinate { x, y };
+            if Cell::from_u8(shots[cell(here)]) == Cell::Miss {
+                blocked[cell(here)] = true;
+            }
+            if sunk_at(here) {
+                blocked[cell(here)] = true;
+                for c in Board::neighbor_coords(size, x, y, true, wrap) {
+                    blocked[cell(c)] = true;
+                }
+            }
+        }
+    }
+
+    let mut weights = vec![0u32; n];
+    for &len in afloat {
+        for y in 0..size {
+            for x in 0..size {
+                for (dx, dy) in [(1u8, 0u8), (0, 1)] {
+                    let Some(line) = line(size, x, y, dx, dy, len, wrap) else {
+                        continue;
+                    };
+                    if line.iter().any(|&c| blocked[cell(c)]) {
+                        continue;
+                    }
+                    let hits = line
+                        .iter()
+                        .filter(|&&c| Cell::from_u8(shots[cell(c)]) == Cell::Hit)
+                        .count() as u32;
+                    let weight = 1 + hits * HIT_WEIGHT;
+                    for &c in &line {
+                        if Cell::from_u8(shots[cell(c)]).is_empty() {
+                            weights[cell(c)] += weight;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let total: u64 = weights.iter().map(|&w| u64::from(w)).sum();
+    weights
+        .into_iter()
+        .map(|w| {
+            if total == 0 {
+                0.0
+            } else {
+                (f64::from(w) / total as f64) as f32
+            }
+        })
+        .collect()
+}
+
+/// The `len` cells from `(x, y)` stepping by `(dx, dy)`, or `None` if the
+/// line runs off the board. With `wrap` it continues from the opposite
+/// edge instead, as long as it doesn't come back round onto itself.
+fn line(size: u8, x: u8, y: u8, dx: u8, dy: u8, len: u8, wrap: bool) -> Option<Vec<Coordinate>> {
+    if wrap && len > size {
+        return None;
+    }
+    (0..len)
+        .map(|i| {
+            let (cx, cy) = (
+                u16::from(x) + u16::from(dx * i),
+                u16::from(y) + u16::from(dy * i),
+            );
+            let size = u16::from(size);
+            match (cx < size && cy < size, wrap) {
+                (true, _) => Some(Coordinate {
+                    x: cx as u8,
+                    y: cy as u8,
+                }),
+                (false, true) => Some(Coordinate {
+                    x: (cx % size) as u8,
+                    y: (cy % size) as u8,
+                }),
+                (false, false) => None,
+            }
+        })
+        .collect()
+}
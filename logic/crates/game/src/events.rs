@@ -1,5 +1,24 @@
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_sdk::serde::{Deserialize, Serialize};
+
 #[calimero_sdk::app::event]
 pub enum Event<'a> {
+    /// One ship of a player's fleet was placed; `remaining` more are
+    /// needed. Emitted by `place_ship` as a draft grows, or for the whole
+    /// fleet at once by `place_ships`; `ShipsPlaced` follows once the fleet
+    /// is confirmed.
+    ShipPlaced {
+        id: &'a str,
+        length: u8,
+        remaining: u32,
+    },
+    /// `undo_last_ship` took a ship off a player's draft; `remaining` more
+    /// are needed again.
+    ShipRemoved {
+        id: &'a str,
+        length: u8,
+        remaining: u32,
+    },
     /// A player placed their ships.
     ShipsPlaced { id: &'a str },
     /// A player's SHA256 board commitment has been recorded.
@@ -20,6 +39,8 @@ pub enum Event<'a> {
     },
     /// A player proposed a shot.
     ShotProposed { id: &'a str, x: u8, y: u8 },
+    /// The shooter withdrew their pending shot; the cell is no longer incoming.
+    ShotCancelled { id: &'a str, x: u8, y: u8 },
     /// A shot was resolved.
     ShotFired {
         id: &'a str,
@@ -31,4 +52,167 @@ pub enum Event<'a> {
     Winner { id: &'a str },
     /// The match ended.
     MatchEnded { id: &'a str },
+    /// A player moved their seat in the match to a new key.
+    PlayerMigrated {
+        id: &'a str,
+        from: &'a str,
+        to: &'a str,
+    },
+    /// A player paused the match; shots wait until it is resumed.
+    MatchPaused { id: &'a str, player: &'a str },
+    /// A player resumed a paused match.
+    MatchResumed { id: &'a str, player: &'a str },
+}
+
+/// Owned mirror of [`Event`], kept in the match's recent-events buffer so a
+/// client that missed emissions can catch up with `get_recent_events`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum EventRecord {
+    ShipPlaced {
+        id: String,
+        length: u8,
+        remaining: u32,
+    },
+    ShipRemoved {
+        id: String,
+        length: u8,
+        remaining: u32,
+    },
+    ShipsPlaced {
+        id: String,
+    },
+    BoardCommitted {
+        id: String,
+        player: String,
+        commitment: String,
+    },
+    BoardRevealed {
+        id: String,
+        player: String,
+    },
+    AuditPassed {
+        id: String,
+        player: String,
+    },
+    AuditFailed {
+        id: String,
+        player: String,
+        reason: String,
+    },
+    ShotProposed {
+        id: String,
+        x: u8,
+        y: u8,
+    },
+    ShotCancelled {
+        id: String,
+        x: u8,
+        y: u8,
+    },
+    ShotFired {
+        id: String,
+        x: u8,
+        y: u8,
+        result: String,
+    },
+    Winner {
+        id: String,
+    },
+    MatchEnded {
+        id: String,
+    },
+    PlayerMigrated {
+        id: String,
+        from: String,
+        to: String,
+    },
+    MatchPaused {
+        id: String,
+        player: String,
+    },
+    MatchResumed {
+        id: String,
+        player: String,
+    },
+}
+
+impl From<&Event<'_>> for EventRecord {
+    fn from(event: &Event<'_>) -> Self {
+        match *event {
+            Event::ShipPlaced {
+                id,
+                length,
+                remaining,
+            } => EventRecord::ShipPlaced {
+                id: id.into(),
+                length,
+                remaining,
+            },
+            Event::ShipRemoved {
+                id,
+                length,
+                remaining,
+            } => EventRecord::ShipRemoved {
+                id: id.into(),
+                length,
+                remaining,
+            },
+            Event::ShipsPlaced { id } => EventRecord::ShipsPlaced { id: id.into() },
+            Event::BoardCommitted {
+                id,
+                player,
+                commitment,
+            } => EventRecord::BoardCommitted {
+                id: id.into(),
+                player: player.into(),
+                commitment: commitment.into(),
+            },
+            Event::BoardRevealed { id, player } => EventRecord::BoardRevealed {
+                id: id.into(),
+                player: player.into(),
+            },
+            Event::AuditPassed { id, player } => EventRecord::AuditPassed {
+                id: id.into(),
+                player: player.into(),
+            },
+            Event::AuditFailed { id, player, reason } => EventRecord::AuditFailed {
+                id: id.into(),
+                player: player.into(),
+                reason: reason.into(),
+            },
+            Event::ShotProposed { id, x, y } => EventRecord::ShotProposed {
+                id: id.into(),
+                x,
+                y,
+            },
+            Event::ShotCancelled { id, x, y } => EventRecord::ShotCancelled {
+                id: id.into(),
+                x,
+                y,
+            },
+            Event::ShotFired { id, x, y, result } => EventRecord::ShotFired {
+                id: id.into(),
+                x,
+                y,
+                result: result.into(),
+            },
+            Event::Winner { id } => EventRecord::Winner { id: id.into() },
+            Event::MatchEnded { id } => EventRecord::MatchEnded { id: id.into() },
+            Event::PlayerMigrated { id, from, to } => EventRecord::PlayerMigrated {
+                id: id.into(),
+                from: from.into(),
+                to: to.into(),
+            },
+            Event::MatchPaused { id, player } => EventRecord::MatchPaused {
+                id: id.into(),
+                player: player.into(),
+            },
+            Event::MatchResumed { id, player } => EventRecord::MatchResumed {
+                id: id.into(),
+                player: player.into(),
+            },
+        }
+    }
 }
@@ -6,18 +6,26 @@ use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use calimero_sdk::serde::{Deserialize, Serialize};
 use calimero_sdk::types::Error as AppError;
 use calimero_storage::collections::{LwwRegister, UnorderedMap, UserStorage};
+use calimero_storage::env as storage_env;
 use sha2::{Digest, Sha256};
 
 pub mod audit;
 pub mod board;
+pub mod density;
 pub mod events;
+pub mod notation;
 pub mod players;
+pub mod rules;
 pub mod ships;
+#[cfg(test)]
+mod sim;
 pub mod validation;
 
-use board::{Cell, BOARD_SIZE};
-use events::Event;
-use players::{PlayerBoard, PrivateBoards};
+use board::{Cell, CellEncoding, Coordinate};
+use events::{Event, EventRecord};
+use players::{PlayerBoard, PrivateBoards, ShotOutcome, ShotResolver};
+use rules::{GameRules, PlacementRules};
+use ships::{Ship, ShipValidator};
 
 // ---------------------------------------------------------------------------
 // API response types
@@ -36,9 +44,180 @@ pub struct OwnBoardView {
 #[serde(crate = "calimero_sdk::serde")]
 pub struct ShotsView {
     pub size: u8,
+    /// `Cell` codes, row-major. Hits on a ship that has been sunk read as
+    /// `Sunk` (5); clients that don't know it can draw it as a hit.
     pub shots: Vec<u8>,
 }
 
+impl OwnBoardView {
+    /// The board as rows, indexed `grid[y][x]`.
+    pub fn to_grid(&self) -> Vec<Vec<u8>> {
+        to_grid(self.size, &self.board)
+    }
+}
+
+impl ShotsView {
+    /// The shots as rows, indexed `grid[y][x]`.
+    pub fn to_grid(&self) -> Vec<Vec<u8>> {
+        to_grid(self.size, &self.shots)
+    }
+}
+
+/// Run-length packed form of a board view (see `Board::to_packed`). Same
+/// information as `OwnBoardView`/`ShotsView`, for clients that want a
+/// smaller payload.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct PackedBoardView {
+    pub size: u8,
+    pub packed: Vec<u8>,
+}
+
+/// What the shooter knows about one cell of the opponent's grid.
+#[derive(
+    Debug, Clone, Copy, BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum TargetCell {
+    /// Not fired at yet (or the shot is still pending).
+    Unknown,
+    Miss,
+    /// Hit a ship that is still afloat.
+    Hit,
+    /// Part of a ship the caller has fully destroyed.
+    Sunk,
+}
+
+/// Fog-of-war view of the opponent's board from the caller's side. Only
+/// cells the caller has fired at ever carry information, so un-hit ship
+/// cells are never revealed.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct TargetGridView {
+    pub size: u8,
+    pub cells: Vec<TargetCell>,
+}
+
+/// Resolved shots (Hit or Miss) each player has fired so far. Aggregate
+/// counts only, so safe to show either participant.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ShotCountsView {
+    pub player1: u32,
+    pub player2: u32,
+}
+
+/// Ship cells each player still has afloat.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ShipsRemainingView {
+    pub player1: u64,
+    pub player2: u64,
+}
+
+/// Hits each player still needs to win under a `FirstToHits` rule. Both are
+/// `None` when the match is played to the last ship.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct HitsToWinView {
+    pub player1: Option<u32>,
+    pub player2: Option<u32>,
+}
+
+/// A player's run of consecutive hits. Resolving a miss ends the run;
+/// a redundant shot leaves it as it was.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct HitStreak {
+    /// Hits in a row up to and including the latest resolved shot
+    pub current: u32,
+    /// Longest run so far this match
+    pub longest: u32,
+}
+
+impl HitStreak {
+    /// Extends the run on a hit or ends it on a miss.
+    pub fn record(&mut self, hit: bool) {
+        if hit {
+            self.current = self.current.saturating_add(1);
+            self.longest = self.longest.max(self.current);
+        } else {
+            self.current = 0;
+        }
+    }
+}
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct HitStreaksView {
+    pub player1: HitStreak,
+    pub player2: HitStreak,
+}
+
+/// Where a match is in its lifecycle.
+#[derive(
+    Debug, Clone, Copy, BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum MatchPhase {
+    /// At least one player has not placed their fleet yet.
+    Placement,
+    Playing,
+    Finished,
+}
+
+/// Canonical public snapshot of the match in this context. Read endpoints
+/// return this rather than hand-rolling their own shape; fields that don't
+/// apply yet are `None`.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct MatchSummary {
+    pub id: String,
+    pub player1: String,
+    pub player2: String,
+    pub turn: Option<String>,
+    pub phase: MatchPhase,
+    pub winner: Option<String>,
+    pub created_ms: u64,
+    pub ended_ms: Option<u64>,
+    /// The match's random seed, revealed once it is finished so a replay
+    /// draws exactly what the match did. `None` while it is still running.
+    pub seed: Option<u64>,
+    /// Whether the match is paused; see `pause_match`.
+    pub paused: bool,
+}
+
+/// One entry of `get_turn_history`: the turn passed to `player` at `at_ms`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct TurnChange {
+    /// Base58 key of the player who got the turn.
+    pub player: String,
+    pub at_ms: u64,
+}
+
 /// Pending-shot record — small value living in an `LwwRegister`.
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
@@ -48,6 +227,122 @@ pub struct PendingShot {
     pub y: u8,
     pub shooter: PublicKey,
     pub target: PublicKey,
+    /// The `propose_shot` nonce the shot was fired with, so a resend of
+    /// the same call is recognised while the shot is pending.
+    pub nonce: Option<u64>,
+}
+
+/// The shot awaiting acknowledgement, as either player sees it.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct PendingShotView {
+    pub x: u8,
+    pub y: u8,
+    /// Base58 key of the player who fired.
+    pub shooter: String,
+    /// Base58 key of the player who must acknowledge it.
+    pub target: String,
+    /// When the shot was proposed; `force_resolve_pending` counts from here.
+    pub since_ms: Option<u64>,
+}
+
+/// Bundle returned by `get_game_state`.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct GameStateView {
+    pub summary: MatchSummary,
+    /// `None` until the caller has placed their fleet on this node.
+    pub own_board: Option<OwnBoardView>,
+    pub target_grid: TargetGridView,
+    pub ships_remaining: ShipsRemainingView,
+    pub pending: Vec<PendingShotView>,
+}
+
+/// How one ship of a proposed fleet fares on its own; see
+/// `validate_fleet_detailed`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ShipValidation {
+    /// Position of the ship in the submitted list.
+    pub index: u32,
+    pub valid: bool,
+    /// Rules the ship breaks: `"malformed"`, `"bounds"`, `"duplicate"`,
+    /// `"length"`, `"straight"` or `"contiguous"`.
+    pub violations: Vec<String>,
+}
+
+/// Result of `validate_fleet_detailed`: each ship on its own, then what's
+/// wrong between them.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct FleetValidation {
+    pub ships: Vec<ShipValidation>,
+    /// Overlapping or touching pairs among the individually valid ships,
+    /// and a wrong fleet composition. Empty when the fleet can be placed.
+    pub conflicts: Vec<String>,
+}
+
+/// The most recently resolved shot — what a reconnecting client needs to
+/// answer "what just happened?" without replaying the match.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ShotRecord {
+    /// Base58 key of the player who fired.
+    pub shooter: String,
+    pub x: u8,
+    pub y: u8,
+    /// `"hit"` or `"miss"`, as returned by `acknowledge_shot`.
+    pub result: String,
+    /// Every cell of the ship this shot sank; empty when `reveal_on_sink`
+    /// is off.
+    pub sunk: Vec<Coordinate>,
+}
+
+/// One shot board cell settled by a resolved shot; see `get_shots_since`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct CellDelta {
+    /// Sequence number of the shot, counting from 1.
+    pub seq: u64,
+    /// Base58 key of the player who fired; the cell is on the other
+    /// player's board.
+    pub shooter: String,
+    pub x: u8,
+    pub y: u8,
+    /// `"hit"` or `"miss"`.
+    pub result: String,
+}
+
+/// Result of `get_shots_since`: the new cells and the cursor to pass next
+/// time.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ShotsSince {
+    /// Sequence number of the latest resolved shot, 0 before the first.
+    pub seq: u64,
+    pub deltas: Vec<CellDelta>,
+}
+
+/// One shot of a match's move list, as written by `export_notation` and
+/// read back by `import_notation`; see the `notation` module for the text
+/// format.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct NotationMove {
+    /// 1 for `player1`, 2 for `player2`, as in `get_my_side`.
+    pub side: u8,
+    pub x: u8,
+    pub y: u8,
+    /// `"hit"` or `"miss"`.
+    pub result: String,
 }
 
 /// Export payload for cross-device durability. Defined locally (not re-used from
@@ -61,11 +356,91 @@ pub struct ExportedSeed {
     pub salt: [u8; 16],
 }
 
+/// Public state of a match, for moving it to another node (e.g. failover).
+/// A shot that was still pending is left out: its cell is exported as
+/// unfired and the shooter keeps the turn, so they simply fire it again.
+/// Private boards and commitments are per-player and not included; each
+/// player restores theirs with `import_board_seed`.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct MatchSnapshot {
+    /// Layout version, `MATCH_SNAPSHOT_VERSION` when exported by this build.
+    pub version: u8,
+    pub match_id: String,
+    pub player1: String,
+    pub player2: String,
+    pub turn: Option<String>,
+    pub winner: Option<String>,
+    /// Finished without a winner (limited-ammo draw).
+    pub drawn: bool,
+    pub placed_p1: bool,
+    pub placed_p2: bool,
+    /// Row-major cell bytes, one per cell of the match's board.
+    pub shots_p1: Vec<u8>,
+    pub shots_p2: Vec<u8>,
+    /// Sunk-ship lengths per cell, same layout as the shot maps. All zero
+    /// when `reveal_on_sink` is off, as the shooter doesn't know them.
+    pub sunk_p1: Vec<u8>,
+    pub sunk_p2: Vec<u8>,
+    /// Informational; `import_match` recounts hits from the shot maps.
+    pub hits_p1: u32,
+    pub hits_p2: u32,
+    pub shots_this_turn: u32,
+    pub game_rules: GameRules,
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+#[cfg(test)]
+thread_local! {
+    static TEST_EXECUTOR: std::cell::Cell<Option<[u8; 32]>> = const { std::cell::Cell::new(None) };
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_COMMITMENTS: std::cell::RefCell<Vec<([u8; 32], [u8; 32])>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Records `commitment` as `pk`'s on this thread. `UserStorage` only writes
+/// as the runtime executor, so tests publish commitments through here.
+#[cfg(test)]
+pub(crate) fn set_test_commitment(pk: &PublicKey, commitment: [u8; 32]) {
+    TEST_COMMITMENTS.with(|c| c.borrow_mut().push((pk.0, commitment)));
+}
+
+/// A legal classic fleet, one ship per even row from the left edge, for
+/// tests that need any placement at all.
+#[cfg(test)]
+pub(crate) const CLASSIC_TEST_FLEET: [&str; 5] = [
+    "0,0;1,0;2,0;3,0;4,0",
+    "0,2;1,2;2,2;3,2",
+    "0,4;1,4;2,4",
+    "0,6;1,6;2,6",
+    "0,8;1,8",
+];
+
+/// Makes `from_executor_id` return `pk` on this thread (`None` restores the
+/// runtime lookup), so tests can drive the public API as either player.
+#[cfg(test)]
+pub(crate) fn set_test_executor(pk: Option<&PublicKey>) {
+    TEST_EXECUTOR.with(|cell| cell.set(pk.map(|pk| pk.0)));
+}
+
+/// This game context's id, base58 as the lobby stores it, for reports the
+/// lobby only accepts from the match's own context.
+fn own_context_id() -> String {
+    bs58::encode(calimero_sdk::env::context_id()).into_string()
+}
+
 fn from_executor_id() -> Result<PublicKey, GameError> {
+    #[cfg(test)]
+    if let Some(id) = TEST_EXECUTOR.with(|cell| cell.get()) {
+        return Ok(PublicKey(id));
+    }
     let v = calimero_sdk::env::executor_id();
     if v.len() != 32 {
         return Err(GameError::Invalid("executor id length".into()));
@@ -75,12 +450,88 @@ fn from_executor_id() -> Result<PublicKey, GameError> {
     Ok(PublicKey(arr))
 }
 
+#[cfg(test)]
+thread_local! {
+    static FAIL_PRIVATE_WRITES: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Makes every `write_private_board` on this thread fail, so tests can
+/// check that a failed write leaves no partial state behind.
+#[cfg(test)]
+pub(crate) fn set_fail_private_writes(fail: bool) {
+    FAIL_PRIVATE_WRITES.with(|cell| cell.set(fail));
+}
+
+/// Stores `pb` as this node's private board for `match_id`, or removes the
+/// entry when `pb` is `None`.
+fn write_private_board(match_id: &str, pb: Option<PlayerBoard>) -> Result<(), GameError> {
+    #[cfg(test)]
+    if FAIL_PRIVATE_WRITES.with(|cell| cell.get()) {
+        return Err(GameError::Invalid("private board write failed".into()));
+    }
+    let mut priv_boards = PrivateBoards::private_load_or_default()
+        .map_err(|e| GameError::Invalid(format!("private boards: {e}")))?;
+    let mut priv_mut = priv_boards.as_mut();
+    let key = PrivateBoards::key(match_id);
+    let written = match pb {
+        Some(pb) => priv_mut.boards.insert(key, pb),
+        None => priv_mut.boards.remove(&key),
+    };
+    written
+        .map(drop)
+        .map_err(|e| GameError::Invalid(format!("private boards: {e}")))
+}
+
+/// This node's placement draft for `match_id`, empty when there is none.
+fn read_draft(match_id: &str) -> Result<Vec<String>, GameError> {
+    let priv_boards = PrivateBoards::private_load_or_default()
+        .map_err(|e| GameError::Invalid(format!("private boards: {e}")))?;
+    let draft = priv_boards
+        .drafts
+        .get(&PrivateBoards::key(match_id))
+        .map_err(|e| GameError::Invalid(format!("private boards: {e}")))?;
+    Ok(draft.unwrap_or_default())
+}
+
+/// Stores `ships` as this node's placement draft for `match_id`, removing
+/// the entry once it is empty.
+fn write_draft(match_id: &str, ships: Vec<String>) -> Result<(), GameError> {
+    let mut priv_boards = PrivateBoards::private_load_or_default()
+        .map_err(|e| GameError::Invalid(format!("private boards: {e}")))?;
+    let mut priv_mut = priv_boards.as_mut();
+    let key = PrivateBoards::key(match_id);
+    let written = if ships.is_empty() {
+        priv_mut.drafts.remove(&key).map(drop)
+    } else {
+        priv_mut.drafts.insert(key, ships).map(drop)
+    };
+    written.map_err(|e| GameError::Invalid(format!("private boards: {e}")))
+}
+
 /// Bridge between our `battleships_types::PublicKey` and the SDK's own `PublicKey`
 /// (needed for `UserStorage::get_for_user` and similar SDK-typed APIs).
 fn sdk_pk(pk: &PublicKey) -> calimero_sdk::PublicKey {
     calimero_sdk::PublicKey::from(pk.0)
 }
 
+/// How many events `recent_events` keeps before the oldest is dropped.
+const RECENT_EVENTS_CAP: u64 = 64;
+
+/// Version of the public contract: method signatures, the view DTOs
+/// (`OwnBoardView`, `ShotsView`, ...) and `GameError`. Bump it with any
+/// change an already-generated client would notice.
+pub const ABI_VERSION: &str = "1.40.0";
+
+/// Stand-in player2 of a practice match. Not a real key: nobody can sign
+/// as it, so the ghost never takes a turn.
+pub const GHOST_PLAYER: PublicKey = PublicKey([0u8; 32]);
+
+/// The manifest `build.rs` emits from these sources before compiling them.
+const ABI_MANIFEST: &str = include_str!("../res/abi.json");
+
+/// Current `MatchSnapshot` layout; `import_match` rejects any other.
+pub const MATCH_SNAPSHOT_VERSION: u8 = 1;
+
 // ---------------------------------------------------------------------------
 // Game state
 // ---------------------------------------------------------------------------
@@ -97,87 +548,205 @@ pub struct GameState {
     pub winner: LwwRegister<Option<PublicKey>>,
     pub placed_p1: LwwRegister<bool>,
     pub placed_p2: LwwRegister<bool>,
-    pub pending: LwwRegister<Option<PendingShot>>,
+    /// Shots awaiting the target's acknowledgement, oldest first. At most
+    /// one unless the rules allow several shots per turn, in which case
+    /// the shooter may queue up to `shots_per_turn` and the target resolves
+    /// them as a batch.
+    pub pending: LwwRegister<Vec<PendingShot>>,
+    /// Last `propose_shot` nonce accepted from each player, so a resend of
+    /// the shot that ended the match is still recognised once it has left
+    /// `pending`; see `is_retry`.
+    pub last_nonce_p1: LwwRegister<Option<u64>>,
+    pub last_nonce_p2: LwwRegister<Option<u64>>,
     /// key = `[y * 10 + x]`, value = Cell as u8 wrapped in LwwRegister (u8 itself is not Mergeable).
     /// A shot cell may transition Pending -> Hit/Miss, so LWW is correct: the ack always
     /// has a later HLC timestamp than the proposal.
     pub shots_p1: UnorderedMap<[u8; 1], LwwRegister<u8>>,
     pub shots_p2: UnorderedMap<[u8; 1], LwwRegister<u8>>,
+    /// Cells of ships each player has sunk (same key scheme as the shot maps,
+    /// value = sunk ship length). Written by the defender's `acknowledge_shot`,
+    /// the only place the ship's extent is known; every cell is already a
+    /// public Hit, so publishing them reveals nothing new.
+    pub sunk_p1: UnorderedMap<[u8; 1], LwwRegister<u8>>,
+    pub sunk_p2: UnorderedMap<[u8; 1], LwwRegister<u8>>,
     /// SHA256 commitment published by each player at placement time.
     /// `LwwRegister` wrapper provides the `Mergeable` impl that `UserStorage` requires;
     /// write-once semantics are enforced at the call site (`AlreadyCommitted`).
     pub commitments: UserStorage<LwwRegister<[u8; 32]>>,
+    /// Key each player's commitment was written under, once they have
+    /// moved to a new key with `migrate_player`. `UserStorage` is owned by
+    /// the writer, so the commitment can't follow them.
+    pub committed_as_p1: LwwRegister<Option<PublicKey>>,
+    pub committed_as_p2: LwwRegister<Option<PublicKey>>,
+    pub last_shot: LwwRegister<Option<ShotRecord>>,
+    /// Every resolved shot, oldest first; backs `export_notation`. Its
+    /// length is the match's shot sequence number (`get_shots_since`).
+    pub shot_log: LwwRegister<Vec<ShotRecord>>,
+    pub rules: LwwRegister<PlacementRules>,
+    pub game_rules: LwwRegister<GameRules>,
+    /// Cumulative hits each player has landed, for hit-threshold wins.
+    pub hits_p1: LwwRegister<u32>,
+    pub hits_p2: LwwRegister<u32>,
+    /// Each player's run of consecutive hits.
+    pub streak_p1: LwwRegister<HitStreak>,
+    pub streak_p2: LwwRegister<HitStreak>,
+    /// When the oldest pending shot was proposed; starts the clock
+    /// `force_resolve_pending` waits out.
+    pub pending_since_ms: LwwRegister<Option<u64>>,
+    /// When the match was paused, while it is; see `pause_match`.
+    pub paused_since_ms: LwwRegister<Option<u64>>,
+    /// Set when a limited-ammo match ends with both players out of shots.
+    /// A drawn match is finished but has no `winner`.
+    pub drawn: LwwRegister<bool>,
+    /// Shots the player on turn has fired so far this turn.
+    pub shots_this_turn: LwwRegister<u32>,
+    /// Every time the turn passed and when, oldest first. Bounded by the
+    /// number of shots, so a register is fine.
+    pub turn_history: LwwRegister<Vec<TurnChange>>,
+    /// A single-player practice match against `GHOST_PLAYER`; see
+    /// `create_practice_match`. Never reported to the lobby.
+    pub practice: LwwRegister<bool>,
+    /// Seed every randomized operation of the match derives from (together
+    /// with `seed_draws`), so its random choices can be replayed and
+    /// audited. Board salts are secrets and never come from it.
+    pub seed: LwwRegister<Option<u64>>,
+    /// Values drawn from `seed` so far; see `next_seed`.
+    pub seed_draws: LwwRegister<u64>,
+    /// Ring buffer of the last `RECENT_EVENTS_CAP` events emitted by
+    /// state-changing calls, keyed by big-endian sequence number. Read-only
+    /// calls such as `reveal_board` can't write state, so they aren't kept.
+    pub recent_events: UnorderedMap<[u8; 8], LwwRegister<EventRecord>>,
+    /// Sequence number the next recorded event will get.
+    pub event_seq: LwwRegister<u64>,
+    pub created_ms: LwwRegister<u64>,
+    /// Set when the match finishes, together with `winner` or `drawn`.
+    pub ended_ms: LwwRegister<Option<u64>>,
 }
 
 #[app::logic]
 impl GameState {
     #[app::init]
+    // Init parameters are the JSON fields the lobby sends; they can't be grouped.
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         player1: String,
         player2: String,
         lobby_context_id: Option<String>,
         match_id: String,
+        game_rules: Option<GameRules>,
+        seed: Option<u64>,
+        first_mover: Option<String>,
+        placement_rules: Option<PlacementRules>,
     ) -> GameState {
         let pk1 = PublicKey::from_base58(&player1).ok();
         let pk2 = PublicKey::from_base58(&player2).ok();
+        // Player1 opens unless the lobby recorded another first mover. One
+        // that isn't a player leaves the match unset, like a bad key does.
+        let turn = match first_mover {
+            None => pk1.clone(),
+            Some(key) => PublicKey::from_base58(&key)
+                .ok()
+                .filter(|pk| Some(pk) == pk1.as_ref() || Some(pk) == pk2.as_ref()),
+        };
+        // Rules no match could be played under leave it unset too.
+        let rules_ok = game_rules.as_ref().is_none_or(|r| r.validate().is_ok())
+            && placement_rules.as_ref().is_none_or(|r| {
+                r.validate_for(&game_rules.clone().unwrap_or_default())
+                    .is_ok()
+            });
         // Game context echoes the lobby-issued match_id verbatim so the
         // on_match_finished xcall lands on the lobby's matches map directly,
         // no context-id reverse scan needed.
-        let stored_match_id = if pk1.is_some() && pk2.is_some() && !match_id.is_empty() {
-            Some(match_id)
-        } else {
-            None
-        };
+        let stored_match_id =
+            if pk1.is_some() && pk2.is_some() && turn.is_some() && rules_ok && !match_id.is_empty()
+            {
+                Some(match_id)
+            } else {
+                None
+            };
         GameState {
             lobby_context_id: LwwRegister::new(lobby_context_id),
             match_id: LwwRegister::new(stored_match_id),
             player1: LwwRegister::new(pk1.clone()),
             player2: LwwRegister::new(pk2),
-            turn: LwwRegister::new(pk1),
+            turn: LwwRegister::new(turn),
             winner: LwwRegister::new(None),
             placed_p1: LwwRegister::new(false),
             placed_p2: LwwRegister::new(false),
-            pending: LwwRegister::new(None),
+            pending: LwwRegister::new(Vec::new()),
+            last_nonce_p1: LwwRegister::new(None),
+            last_nonce_p2: LwwRegister::new(None),
             shots_p1: UnorderedMap::new_with_field_name("game:shots_p1"),
             shots_p2: UnorderedMap::new_with_field_name("game:shots_p2"),
+            sunk_p1: UnorderedMap::new_with_field_name("game:sunk_p1"),
+            sunk_p2: UnorderedMap::new_with_field_name("game:sunk_p2"),
             commitments: UserStorage::new_with_field_name("game:commitments"),
+            committed_as_p1: LwwRegister::new(None),
+            committed_as_p2: LwwRegister::new(None),
+            last_shot: LwwRegister::new(None),
+            shot_log: LwwRegister::new(Vec::new()),
+            rules: LwwRegister::new(placement_rules.unwrap_or_default()),
+            game_rules: LwwRegister::new(game_rules.unwrap_or_default()),
+            hits_p1: LwwRegister::new(0),
+            hits_p2: LwwRegister::new(0),
+            streak_p1: LwwRegister::new(HitStreak::default()),
+            streak_p2: LwwRegister::new(HitStreak::default()),
+            pending_since_ms: LwwRegister::new(None),
+            paused_since_ms: LwwRegister::new(None),
+            drawn: LwwRegister::new(false),
+            shots_this_turn: LwwRegister::new(0),
+            turn_history: LwwRegister::new(Vec::new()),
+            practice: LwwRegister::new(false),
+            seed: LwwRegister::new(seed),
+            seed_draws: LwwRegister::new(0),
+            recent_events: UnorderedMap::new_with_field_name("game:recent_events"),
+            event_seq: LwwRegister::new(0),
+            created_ms: LwwRegister::new(storage_env::time_now()),
+            ended_ms: LwwRegister::new(None),
         }
     }
 
     // ---- Game API ----
 
+    /// Places and commits the whole fleet in one call; a fleet can be
+    /// checked beforehand with `validate_fleet_detailed`, or built up one
+    /// ship at a time with `place_ship` and committed with
+    /// `confirm_placement`. Any such draft is discarded once the fleet is
+    /// committed. The lobby is told, so it counts an opponent once they
+    /// take part.
     pub fn place_ships(&mut self, match_id: &str, ships: Vec<String>) -> app::Result<()> {
-        let active_id = self
-            .match_id
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("no active match".into())))?;
-        if match_id != active_id {
-            app::bail!(GameError::NotFound(match_id.to_string()));
-        }
-        if self.winner.get().is_some() {
-            app::bail!(GameError::Finished);
-        }
+        self.commit_fleet(match_id, ships, false)
+    }
 
+    /// Lays out and commits the caller's fleet. A `drafted` fleet already
+    /// announced its ships one by one from `place_ship`, so only a fleet
+    /// placed in one call emits a `ShipPlaced` for each of its ships.
+    fn commit_fleet(
+        &mut self,
+        match_id: &str,
+        ships: Vec<String>,
+        drafted: bool,
+    ) -> app::Result<()> {
         let caller = from_executor_id()?;
-        if !self.is_player(&caller) {
-            app::bail!(GameError::Forbidden("not a player".into()));
-        }
+        self.authorize(&caller, match_id)?;
 
-        // Write-once: reject a second commitment from the same player.
-        let existing = self
-            .commitments
-            .get()
-            .map_err(|e| AppError::msg(format!("commitments.get: {e}")))?;
-        if existing.is_some() {
+        // Write-once: reject a second commitment from the same player,
+        // including one made under a key they have since migrated from.
+        if self.commitment_of(&caller)?.is_some() {
             app::bail!(GameError::AlreadyCommitted);
         }
 
-        // Populate the private board (existing validation flow).
-        let mut priv_boards = PrivateBoards::private_load_or_default()?;
-        let mut priv_mut = priv_boards.as_mut();
-        let key = PrivateBoards::key(match_id);
-        let mut pb = priv_mut.boards.get(&key)?.unwrap_or_default();
+        // Lay out the fleet on a copy of the private board (existing
+        // validation flow); nothing is written until it has validated.
+        let previous = PrivateBoards::private_load_or_default()?
+            .boards
+            .get(&PrivateBoards::key(match_id))?;
+        let size = self.rules.get().board_size;
+        let wrap = self.game_rules.get().wrap_edges;
+        let mut pb = previous
+            .clone()
+            .unwrap_or_else(|| PlayerBoard::with_topology(size, wrap));
+        let lengths = ship_lengths(&ships, size);
         pb.place_ships(ships)?;
         // Snapshot the pristine board NOW — `own` will be mutated as shots
         // resolve, but the commitment hash must always match placement state.
@@ -187,72 +756,173 @@ impl GameState {
         let mut salt = [0u8; 16];
         calimero_sdk::env::random_bytes(&mut salt);
         pb.set_salt(salt);
-        let board_bytes = calimero_sdk::borsh::to_vec(&pb.pristine().to_vec())
-            .map_err(|e| AppError::msg(format!("serialize board: {e}")))?;
-        let commitment = compute_commitment(&board_bytes, &salt);
-
-        // Publish commitment to UserStorage (writer-authorized).
-        self.commitments
-            .insert(LwwRegister::new(commitment))
-            .map_err(|e| AppError::msg(format!("commitments.insert: {e}")))?;
+        let commitment = board_commitment(&pb)?;
 
-        // Persist private board.
-        priv_mut.boards.insert(key, pb)?;
-
-        // Flip placed flag on shared state.
-        if caller == self.player1_or_panic()? {
-            self.placed_p1.set(true);
-        } else {
-            self.placed_p2.set(true);
-        }
+        self.persist_placement(&caller, match_id, previous, pb, commitment)?;
+        write_draft(match_id, Vec::new())?;
 
         let commitment_hex = hex_encode(&commitment);
         let caller_b58 = caller.to_base58();
-        app::emit!(Event::BoardCommitted {
+        self.emit_recorded(Event::BoardCommitted {
             id: match_id,
             player: &caller_b58,
             commitment: &commitment_hex,
-        });
-        app::emit!(Event::ShipsPlaced { id: match_id });
+        })?;
+        // Per-ship progress first; `ShipsPlaced` stays the final word.
+        if !drafted {
+            for (i, &length) in lengths.iter().enumerate() {
+                self.emit_recorded(Event::ShipPlaced {
+                    id: match_id,
+                    length,
+                    remaining: (lengths.len() - i - 1) as u32,
+                })?;
+            }
+        }
+        self.emit_recorded(Event::ShipsPlaced { id: match_id })?;
+        if self.lobby_context_id.get().is_some() {
+            self.notify_lobby(
+                "on_ships_placed",
+                calimero_sdk::serde_json::json!({
+                    "match_id": match_id,
+                    "context_id": own_context_id(),
+                    "player": caller_b58,
+                }),
+            );
+        }
         Ok(())
     }
 
-    pub fn propose_shot(&mut self, match_id: &str, x: u8, y: u8) -> app::Result<()> {
-        let active_id = self
-            .match_id
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("no active match".into())))?;
-        if match_id != active_id {
-            app::bail!(GameError::NotFound(match_id.to_string()));
+    /// Adds `ship` (coordinates as `place_ships` takes them) to the
+    /// caller's placement draft. The draft is private and kept in placement
+    /// order, so `undo_last_ship` can take ships back off it; nothing is
+    /// committed, and the player isn't placed, until `confirm_placement`.
+    /// Each ship must be valid, clear of the ones before it and still
+    /// wanted by the rules' fleet.
+    pub fn place_ship(&mut self, match_id: &str, ship: String) -> app::Result<()> {
+        let caller = from_executor_id()?;
+        let mut draft = self.draft_of(&caller, match_id)?;
+        draft.push(ship);
+        self.check_draft(&draft)?;
+        let length = *ship_lengths(&draft, self.board_size())
+            .last()
+            .ok_or_else(|| GameError::Invalid("no ship".into()))?;
+        let remaining = self.ships_still_needed(&draft);
+        write_draft(match_id, draft)?;
+        self.emit_recorded(Event::ShipPlaced {
+            id: match_id,
+            length,
+            remaining,
+        })?;
+        Ok(())
+    }
+
+    /// Takes the most recently placed ship off the caller's draft. Refused
+    /// when the draft is empty, and once placement is confirmed.
+    pub fn undo_last_ship(&mut self, match_id: &str) -> app::Result<()> {
+        let caller = from_executor_id()?;
+        let mut draft = self.draft_of(&caller, match_id)?;
+        let Some(ship) = draft.pop() else {
+            app::bail!(GameError::Invalid("no ships placed".into()));
+        };
+        let length = ship_lengths(&[ship], self.board_size())
+            .first()
+            .copied()
+            .unwrap_or_default();
+        let remaining = self.ships_still_needed(&draft);
+        write_draft(match_id, draft)?;
+        self.emit_recorded(Event::ShipRemoved {
+            id: match_id,
+            length,
+            remaining,
+        })?;
+        Ok(())
+    }
+
+    /// The caller's placement draft, in placement order.
+    pub fn get_draft_ships(&self, match_id: &str) -> app::Result<Vec<String>> {
+        let caller = from_executor_id()?;
+        Ok(self.draft_of(&caller, match_id)?)
+    }
+
+    /// Commits the caller's draft as their fleet, exactly as `place_ships`
+    /// would; an incomplete fleet is refused and the draft kept.
+    pub fn confirm_placement(&mut self, match_id: &str) -> app::Result<()> {
+        let caller = from_executor_id()?;
+        let draft = self.draft_of(&caller, match_id)?;
+        self.commit_fleet(match_id, draft, true)
+    }
+
+    /// Ships the rules' fleet needs beyond those in `draft`.
+    fn ships_still_needed(&self, draft: &[String]) -> u32 {
+        self.rules.get().fleet.len().saturating_sub(draft.len()) as u32
+    }
+
+    /// `caller`'s draft for `match_id`, which only a player who hasn't
+    /// committed a fleet yet may have.
+    fn draft_of(&self, caller: &PublicKey, match_id: &str) -> Result<Vec<String>, GameError> {
+        self.authorize(caller, match_id)?;
+        if self.commitment_of(caller)?.is_some() {
+            return Err(GameError::AlreadyCommitted);
         }
-        if x >= BOARD_SIZE || y >= BOARD_SIZE {
-            app::bail!(GameError::Invalid("out of bounds".into()));
+        read_draft(match_id)
+    }
+
+    /// Checks a draft laid out in order: each ship is validated against
+    /// those before it, and no length may appear more often than in the
+    /// rules' fleet.
+    fn check_draft(&self, draft: &[String]) -> Result<(), GameError> {
+        let rules = self.rules.get();
+        PlayerBoard::check_partial_fleet(
+            rules.board_size,
+            self.game_rules.get().wrap_edges,
+            draft,
+        )?;
+        let lengths = ship_lengths(draft, rules.board_size);
+        for &len in &lengths {
+            let count = |ls: &[u8]| ls.iter().filter(|&&l| l == len).count();
+            if count(&lengths) > count(&rules.fleet) {
+                return Err(GameError::Invalid(format!(
+                    "fleet has no more ships of length {len}"
+                )));
+            }
         }
-        if self.winner.get().is_some() {
-            app::bail!(GameError::Finished);
+        Ok(())
+    }
+
+    /// `nonce` is an optional idempotency key: clients on unreliable
+    /// transports pass a fresh value per shot and may resend the same call
+    /// after a timeout without double-firing. Reusing a nonce for another
+    /// cell, or once its shot has resolved, fires a new shot.
+    pub fn propose_shot(
+        &mut self,
+        match_id: &str,
+        x: u8,
+        y: u8,
+        nonce: Option<u64>,
+    ) -> app::Result<()> {
+        let caller = from_executor_id()?;
+        self.check_match_id(match_id)?;
+        // Checked before `authorize`, so resending the shot that ended the
+        // match is still a no-op rather than `Finished`.
+        if self.is_retry(&caller, x, y, nonce) {
+            return Ok(());
         }
-        if !(*self.placed_p1.get()) || !(*self.placed_p2.get()) {
+        self.authorize(&caller, match_id)?;
+        if *self.practice.get() {
             app::bail!(GameError::Invalid(
-                "both players must place ships first".into()
+                "practice matches are played with practice_fire".into()
             ));
         }
-        if self.pending.get().is_some() {
-            app::bail!(GameError::Invalid("a shot is already pending".into()));
+        let target = self.validate_shot(&caller, x, y)?;
+        if self
+            .game_rules
+            .get()
+            .out_of_ammo(self.shots_fired_by(&caller)?)
+        {
+            app::bail!(GameError::Invalid("out of ammunition".into()));
         }
-
-        let caller = from_executor_id()?;
         let p1 = self.player1_or_panic()?;
-        let p2 = self.player2_or_panic()?;
-        if caller != p1 && caller != p2 {
-            app::bail!(GameError::Forbidden("not a player".into()));
-        }
-        if self.turn.get().as_ref() != Some(&caller) {
-            app::bail!(GameError::Forbidden("not your turn".into()));
-        }
-
-        let target = if caller == p1 { p2.clone() } else { p1.clone() };
-        let key = [y * BOARD_SIZE + x];
+        let key = cell_key(self.board_size(), x, y);
         let shooter_map = if caller == p1 {
             &mut self.shots_p1
         } else {
@@ -270,70 +940,175 @@ impl GameState {
             .map_err(|e| AppError::msg(format!("shots.get: {e}")))?
         {
             let cell = Cell::from_u8(*existing.get());
-            if cell == Cell::Hit || cell == Cell::Miss {
+            if cell.is_resolved_shot() {
                 app::bail!(GameError::Invalid(format!(
                     "cell ({x},{y}) was already shot ({cell:?})"
                 )));
             }
+            if cell.is_pending() {
+                app::bail!(GameError::Invalid(format!(
+                    "cell ({x},{y}) already has a shot pending"
+                )));
+            }
         }
         shooter_map
             .insert(key, LwwRegister::new(Cell::Pending.to_u8()))
             .map_err(|e| AppError::msg(format!("shots.insert: {e}")))?;
-        self.pending.set(Some(PendingShot {
+        if nonce.is_some() {
+            if caller == p1 {
+                self.last_nonce_p1.set(nonce);
+            } else {
+                self.last_nonce_p2.set(nonce);
+            }
+        }
+        let mut queue = self.pending.get().clone();
+        if queue.is_empty() {
+            self.pending_since_ms.set(Some(storage_env::time_now()));
+        }
+        queue.push(PendingShot {
             x,
             y,
             shooter: caller,
             target,
-        }));
+            nonce,
+        });
+        self.pending.set(queue);
+        let fired = self.shots_this_turn.get().saturating_add(1);
+        self.shots_this_turn.set(fired);
 
-        app::emit!((
-            Event::ShotProposed { id: match_id, x, y },
-            "acknowledge_shot_handler"
-        ));
+        let event = Event::ShotProposed { id: match_id, x, y };
+        self.record_event(&event)?;
+        app::emit!((event, "acknowledge_shot_handler"));
         Ok(())
     }
 
-    pub fn acknowledge_shot(&mut self, match_id: &str) -> app::Result<String> {
-        let active_id = self
-            .match_id
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("no active match".into())))?;
-        if match_id != active_id {
-            app::bail!(GameError::NotFound(match_id.to_string()));
-        }
-        if self.winner.get().is_some() {
-            app::bail!(GameError::Finished);
-        }
-
+    /// Pauses the match for a scheduled break: until `resume_match`, shots
+    /// can't be proposed, acknowledged or force-resolved, and the pending
+    /// shot timeout stops running. Either player may pause.
+    pub fn pause_match(&mut self, match_id: &str) -> app::Result<()> {
         let caller = from_executor_id()?;
-        let pending = self
-            .pending
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("no pending shot".into())))?;
-        if pending.target != caller {
+        self.authorize(&caller, match_id)?;
+        self.pause_for(&caller, storage_env::time_now())?;
+        self.report_turn();
+        self.emit_recorded(Event::MatchPaused {
+            id: match_id,
+            player: &caller.to_base58(),
+        })?;
+        Ok(())
+    }
+
+    /// Ends a pause started by either player with `pause_match`.
+    pub fn resume_match(&mut self, match_id: &str) -> app::Result<()> {
+        let caller = from_executor_id()?;
+        self.authorize(&caller, match_id)?;
+        self.resume_for(&caller, storage_env::time_now())?;
+        self.report_turn();
+        self.emit_recorded(Event::MatchResumed {
+            id: match_id,
+            player: &caller.to_base58(),
+        })?;
+        Ok(())
+    }
+
+    /// Withdraws the caller's most recent pending shot before the target
+    /// has acknowledged it, handing that shot back to the caller.
+    pub fn cancel_shot(&mut self, match_id: &str) -> app::Result<()> {
+        let caller = from_executor_id()?;
+        self.authorize(&caller, match_id)?;
+        let cancelled = self.cancel_pending_for(&caller)?;
+        self.report_turn();
+        self.emit_recorded(Event::ShotCancelled {
+            id: match_id,
+            x: cancelled.x,
+            y: cancelled.y,
+        })?;
+        Ok(())
+    }
+
+    pub fn acknowledge_shot(&mut self, match_id: &str) -> app::Result<String> {
+        let caller = from_executor_id()?;
+        self.authorize(&caller, match_id)?;
+        self.check_not_paused()?;
+        let batch = self.pending.get().clone();
+        let Some(first) = batch.first() else {
+            app::bail!(GameError::Invalid("no pending shot".into()));
+        };
+        if first.target != caller {
             app::bail!(GameError::Forbidden("not the target".into()));
         }
+        self.resolve_batch(match_id, batch)
+    }
+
+    /// Resolves a pending shot that has gone unacknowledged for the rules'
+    /// `shot_timeout_ms`, exactly as `acknowledge_shot` would, so a
+    /// crashed or stalling defender can't freeze the match. Either player
+    /// may call it once the shot times out. The shot is still resolved
+    /// against the target's private board, so the call fails with
+    /// `NotFound` naming the target on a node that doesn't hold it, such
+    /// as the shooter's, whose board for this match is their own.
+    pub fn force_resolve_pending(&mut self, match_id: &str) -> app::Result<String> {
+        let caller = from_executor_id()?;
+        self.authorize(&caller, match_id)?;
+        let batch = self.timed_out_pending(storage_env::time_now())?;
+        self.resolve_batch(match_id, batch)
+    }
+
+    /// Shared body of `acknowledge_shot` and `force_resolve_pending`:
+    /// resolves `batch` oldest first and returns the results joined by
+    /// commas (`"hit"` for a single shot, `"hit,miss"` for two). A shot
+    /// that wins the match ends the batch; the rest are dropped.
+    fn resolve_batch(&mut self, match_id: &str, batch: Vec<PendingShot>) -> app::Result<String> {
+        let mut results = Vec::with_capacity(batch.len());
+        let mut extra_turn = false;
+        for shot in batch {
+            results.push(self.resolve_pending(match_id, shot, &mut extra_turn)?);
+            if self.is_finished() {
+                break;
+            }
+        }
+        self.drop_pending()?;
+        Ok(results.join(","))
+    }
 
-        // Resolve against the caller's private board.
+    /// Resolves one shot of the pending batch. `extra_turn` collects
+    /// whether any shot of the batch so far earned another turn.
+    fn resolve_pending(
+        &mut self,
+        match_id: &str,
+        pending: PendingShot,
+        extra_turn: &mut bool,
+    ) -> app::Result<String> {
+        // Resolve against the target's private board.
         let mut priv_boards = PrivateBoards::private_load_or_default()?;
         let mut priv_mut = priv_boards.as_mut();
         let key = PrivateBoards::key(match_id);
-        let mut pb = priv_mut
-            .boards
-            .get(&key)?
-            .ok_or_else(|| AppError::from(GameError::Invalid("target board unavailable".into())))?;
-        let cur = pb.get_board().get(BOARD_SIZE, pending.x, pending.y);
-        let is_hit = cur == Cell::Ship;
-        if is_hit {
-            pb.get_board_mut()
-                .set(BOARD_SIZE, pending.x, pending.y, Cell::Hit);
-            pb.decrement_ships();
-        } else {
-            pb.get_board_mut()
-                .set(BOARD_SIZE, pending.x, pending.y, Cell::Miss);
+        let Some(mut pb) = priv_mut.boards.get(&key)? else {
+            return Err(self.missing_target_board(&pending).into());
+        };
+        // The key names only the match, so on the shooter's node it holds
+        // the shooter's own fleet. Only the board the target committed to
+        // may be fired at.
+        if self.commitment_of(&pending.target)? != Some(board_commitment(&pb)?) {
+            return Err(self.missing_target_board(&pending).into());
         }
+        self.check_board_sizes(&pb)?;
+        // A cell that already holds Hit/Miss resolves as Redundant: the
+        // earlier result is echoed back and the ship counter is untouched.
+        let outcome = ShotResolver::resolve_shot(&mut pb, pending.x, pending.y);
+        let redundant = matches!(outcome, ShotOutcome::Redundant(_));
+        let resolved = outcome.recorded_cell();
+        let is_hit = resolved == Cell::Hit;
+        let sunk_cells = if is_hit {
+            pb.sunk_ship_at(pending.x, pending.y).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        // Re-firing at a settled cell never earns anything.
+        *extra_turn |= !redundant
+            && self
+                .game_rules
+                .get()
+                .earns_extra_turn(is_hit, !sunk_cells.is_empty());
         let ships_remaining = pb.get_ship_count();
         let pristine_bytes = pb.pristine().to_vec();
         let salt = *pb.salt();
@@ -343,8 +1118,8 @@ impl GameState {
 
         // Overwrite the shooter's map entry with the resolved cell.
         let p1 = self.player1_or_panic()?;
-        let resolved = if is_hit { Cell::Hit } else { Cell::Miss };
-        let shot_key = [pending.y * BOARD_SIZE + pending.x];
+        let size = self.board_size();
+        let shot_key = cell_key(size, pending.x, pending.y);
         let shooter_map = if pending.shooter == p1 {
             &mut self.shots_p1
         } else {
@@ -353,21 +1128,68 @@ impl GameState {
         shooter_map
             .insert(shot_key, LwwRegister::new(resolved.to_u8()))
             .map_err(|e| AppError::msg(format!("shots.insert: {e}")))?;
-        self.pending.set(None);
+        // The sunk maps are public state, so a ship's extent is only
+        // written down when the rules reveal it.
+        let revealed = self.revealed_ship(&sunk_cells);
+        let sunk_map = if pending.shooter == p1 {
+            &mut self.sunk_p1
+        } else {
+            &mut self.sunk_p2
+        };
+        for c in &revealed {
+            sunk_map
+                .insert(
+                    cell_key(size, c.x, c.y),
+                    LwwRegister::new(sunk_cells.len() as u8),
+                )
+                .map_err(|e| AppError::msg(format!("sunk.insert: {e}")))?;
+        }
+        let mut queue = self.pending.get().clone();
+        queue.retain(|p| (p.x, p.y) != (pending.x, pending.y));
+        let batch_done = queue.is_empty();
+        self.pending.set(queue);
+        if batch_done {
+            self.pending_since_ms.set(None);
+        }
 
-        let caller_b58 = caller.to_base58();
+        let target_b58 = pending.target.to_base58();
         let result_str = if is_hit { "hit" } else { "miss" };
+        self.record_shot(ShotRecord {
+            shooter: pending.shooter.to_base58(),
+            x: pending.x,
+            y: pending.y,
+            result: result_str.to_string(),
+            sunk: revealed,
+        });
 
-        if ships_remaining == 0 {
+        let shooter_hits = if pending.shooter == p1 {
+            &mut self.hits_p1
+        } else {
+            &mut self.hits_p2
+        };
+        if is_hit && !redundant {
+            let next = shooter_hits.get().saturating_add(1);
+            shooter_hits.set(next);
+        }
+        let shooter_hits = *shooter_hits.get();
+        if !redundant {
+            let streak = if pending.shooter == p1 {
+                &mut self.streak_p1
+            } else {
+                &mut self.streak_p2
+            };
+            let mut next = *streak.get();
+            next.record(is_hit);
+            streak.set(next);
+        }
+        // Ammunition counts queued shots too, so it's only settled once the
+        // whole batch has resolved.
+        let drawn_out = batch_done && self.is_drawn_out(&pending)?;
+        if let Some(winner) = self.decisive_winner(&pending, shooter_hits, ships_remaining) {
             // Winning shot — run audit.
-            let commitment = self
-                .commitments
-                .get_for_user(&sdk_pk(&caller))
-                .map_err(|e| AppError::msg(format!("commitments.get_for_user: {e}")))?
-                .ok_or_else(|| {
-                    AppError::from(GameError::Invalid("no commitment for caller".into()))
-                })?;
-            let commitment_hash = *commitment.get();
+            let commitment_hash = self.commitment_of(&pending.target)?.ok_or_else(|| {
+                AppError::from(GameError::Invalid("no commitment for caller".into()))
+            })?;
             let board_bytes = calimero_sdk::borsh::to_vec(&pristine_bytes)
                 .map_err(|e| AppError::msg(format!("serialize board: {e}")))?;
             let against_me = if pending.shooter == p1 {
@@ -376,100 +1198,106 @@ impl GameState {
                 &self.shots_p2
             };
             let commitment_ok = audit::verify_commitment(&board_bytes, &salt, &commitment_hash);
-            let replay_ok = audit::replay_shots(&pristine_bytes, against_me).is_ok();
+            let replay_ok =
+                audit::replay_shots(&pristine_bytes, self.board_size(), against_me).is_ok();
             let audit_ok = commitment_ok && replay_ok;
 
-            // Winner is always the shooter of this sinking hit.
-            self.winner.set(Some(pending.shooter.clone()));
+            self.winner.set(Some(winner));
+            self.ended_ms.set(Some(storage_env::time_now()));
 
             if audit_ok {
-                app::emit!(Event::AuditPassed {
+                self.emit_recorded(Event::AuditPassed {
                     id: match_id,
-                    player: &caller_b58,
-                });
+                    player: &target_b58,
+                })?;
             } else {
                 let reason = if !commitment_ok {
                     "commitment_mismatch"
                 } else {
                     "shot_inconsistent"
                 };
-                app::emit!(Event::AuditFailed {
+                self.emit_recorded(Event::AuditFailed {
                     id: match_id,
-                    player: &caller_b58,
+                    player: &target_b58,
                     reason,
-                });
+                })?;
             }
 
-            app::emit!(Event::ShotFired {
+            self.emit_recorded(Event::ShotFired {
                 id: match_id,
                 x: pending.x,
                 y: pending.y,
                 result: result_str,
-            });
-            app::emit!(Event::Winner { id: match_id });
-            app::emit!(Event::MatchEnded { id: match_id });
-
-            // xcall lobby with match-finished.
-            if let Some(lobby_ctx) = self.lobby_context_id.get().as_ref() {
-                if let Ok(lobby_bytes) = bs58::decode(lobby_ctx).into_vec() {
-                    if let Ok(ctx_arr) = <[u8; 32]>::try_from(lobby_bytes.as_slice()) {
-                        let winner_b58 = pending.shooter.to_base58();
-                        let loser_b58 = caller.to_base58();
-                        // The lobby-issued match_id was passed into init() and
-                        // stored verbatim, so we echo it back here for an O(1)
-                        // map lookup on the lobby side.
-                        let params = calimero_sdk::serde_json::json!({
-                            "match_id": match_id,
-                            "winner": winner_b58,
-                            "loser": loser_b58,
-                        });
-                        if let Ok(payload) = calimero_sdk::serde_json::to_vec(&params) {
-                            calimero_sdk::env::xcall(&ctx_arr, "on_match_finished", &payload);
-                        }
-                    }
-                }
+            })?;
+            self.emit_recorded(Event::Winner { id: match_id })?;
+            self.emit_recorded(Event::MatchEnded { id: match_id })?;
+
+            // The lobby-issued match_id was passed into init() and stored
+            // verbatim, so we echo it back here for an O(1) map lookup on
+            // the lobby side.
+            self.notify_lobby(
+                "on_match_finished",
+                calimero_sdk::serde_json::json!({
+                    "match_id": match_id,
+                    "winner": pending.shooter.to_base58(),
+                    "loser": target_b58,
+                }),
+            );
+        } else if drawn_out {
+            self.ended_ms.set(Some(storage_env::time_now()));
+            self.drawn.set(true);
+            self.emit_recorded(Event::ShotFired {
+                id: match_id,
+                x: pending.x,
+                y: pending.y,
+                result: result_str,
+            })?;
+            self.emit_recorded(Event::MatchEnded { id: match_id })?;
+            if self.lobby_context_id.get().is_some() {
+                self.notify_lobby(
+                    "on_match_drawn",
+                    calimero_sdk::serde_json::json!({
+                        "match_id": match_id,
+                        "context_id": own_context_id(),
+                    }),
+                );
             }
         } else {
-            // Swap turn.
-            let p2 = self.player2_or_panic()?;
-            let next = if self.turn.get().as_ref() == Some(&p1) {
-                p2
-            } else {
-                p1
-            };
-            self.turn.set(Some(next));
-            app::emit!(Event::ShotFired {
+            if batch_done {
+                let exhausted = self
+                    .game_rules
+                    .get()
+                    .turn_exhausted(*self.shots_this_turn.get());
+                if *extra_turn && exhausted && self.can_fire(&pending.shooter)? {
+                    // Same shooter, fresh allowance.
+                    self.shots_this_turn.set(0);
+                } else {
+                    self.advance_turn()?;
+                }
+            }
+            self.emit_recorded(Event::ShotFired {
                 id: match_id,
                 x: pending.x,
                 y: pending.y,
                 result: result_str,
-            });
+            })?;
         }
 
         Ok(result_str.to_string())
     }
 
     pub fn reveal_board(&self, match_id: &str) -> app::Result<()> {
-        let active_id = self
-            .match_id
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("no active match".into())))?;
-        if match_id != active_id {
-            app::bail!(GameError::NotFound(match_id.to_string()));
-        }
+        self.check_match_id(match_id)?;
         let caller = from_executor_id()?;
-        let commitment = self
-            .commitments
-            .get_for_user(&sdk_pk(&caller))
-            .map_err(|e| AppError::msg(format!("commitments.get_for_user: {e}")))?
+        let commitment_hash = self
+            .commitment_of(&caller)?
             .ok_or_else(|| AppError::from(GameError::Invalid("no commitment for caller".into())))?;
-        let commitment_hash = *commitment.get();
         let priv_boards = PrivateBoards::private_load_or_default()?;
         let pb = priv_boards
             .boards
             .get(&PrivateBoards::key(match_id))?
             .ok_or_else(|| AppError::from(GameError::BoardNotFound))?;
+        self.check_board_sizes(&pb)?;
         let own_cells = pb.pristine().to_vec();
         let board_bytes = calimero_sdk::borsh::to_vec(&own_cells)
             .map_err(|e| AppError::msg(format!("serialize board: {e}")))?;
@@ -488,7 +1316,7 @@ impl GameState {
         } else {
             &self.shots_p1
         };
-        if let Err(failure) = audit::replay_shots(&own_cells, against_me) {
+        if let Err(failure) = audit::replay_shots(&own_cells, self.board_size(), against_me) {
             let reason = failure.to_string();
             app::emit!(Event::AuditFailed {
                 id: match_id,
@@ -508,12 +1336,38 @@ impl GameState {
         Ok(())
     }
 
+    /// Checks a fleet the caller's opponent revealed (for a dispute, or at
+    /// the end of a match) against the caller's recorded shots: every Hit
+    /// must land on a revealed ship and every Miss on water. Returns false
+    /// if a shot disagrees, which means the ships were moved after the
+    /// fact; the first such shot is named in an `AuditFailed` event.
+    pub fn verify_hits_consistent(&self, match_id: &str, revealed: Vec<Ship>) -> app::Result<bool> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        let Some(failure) = self.hits_inconsistency_for(&caller, &revealed)? else {
+            return Ok(true);
+        };
+        let p1 = self.player1_or_panic()?;
+        let opponent = if caller == p1 {
+            self.player2_or_panic()?
+        } else {
+            p1
+        };
+        app::emit!(Event::AuditFailed {
+            id: match_id,
+            player: &opponent.to_base58(),
+            reason: &failure.to_string(),
+        });
+        Ok(false)
+    }
+
     pub fn export_board_seed(&self, match_id: &str) -> app::Result<ExportedSeed> {
         let priv_boards = PrivateBoards::private_load_or_default()?;
         let pb = priv_boards
             .boards
             .get(&PrivateBoards::key(match_id))?
             .ok_or_else(|| AppError::from(GameError::BoardNotFound))?;
+        self.check_board_sizes(&pb)?;
         // Export the pristine-board snapshot so the commitment recomputation
         // on re-import always matches regardless of mid-game mutations.
         let pristine = pb.pristine().to_vec();
@@ -532,17 +1386,15 @@ impl GameState {
         salt: [u8; 16],
     ) -> app::Result<()> {
         let caller = from_executor_id()?;
-        let expected = self
-            .commitments
-            .get_for_user(&sdk_pk(&caller))
-            .map_err(|e| AppError::msg(format!("commitments.get_for_user: {e}")))?
+        let expected_hash = self
+            .commitment_of(&caller)?
             .ok_or_else(|| AppError::from(GameError::Invalid("no commitment for caller".into())))?;
-        let expected_hash = *expected.get();
         if !audit::verify_commitment(&board_bytes, &salt, &expected_hash) {
             app::bail!(GameError::CommitmentMismatch);
         }
         let board: board::Board = calimero_sdk::borsh::from_slice(&board_bytes)
             .map_err(|e| AppError::msg(format!("deserialize board: {e}")))?;
+        board.check_len(self.board_size())?;
         let ship_count = board.0.iter().filter(|&&c| is_ship_cell(c)).count() as u64;
         let mut priv_boards = PrivateBoards::private_load_or_default()?;
         let mut priv_mut = priv_boards.as_mut();
@@ -553,201 +1405,3898 @@ impl GameState {
         Ok(())
     }
 
-    pub fn get_own_board(&self, match_id: &str) -> app::Result<OwnBoardView> {
-        let active_id = self
-            .match_id
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("no active match".into())))?;
-        if match_id != active_id {
-            app::bail!(GameError::NotFound(match_id.to_string()));
+    /// The match's shots so far as human-readable text, e.g.
+    /// `"1. P1 B7 hit, P2 A1 miss"`; see the `notation` module for the
+    /// grammar. Covers shots since the match started or was last imported.
+    pub fn export_notation(&self, match_id: &str) -> app::Result<String> {
+        self.check_match_id(match_id)?;
+        Ok(notation::format(&self.notation_moves()))
+    }
+
+    /// Parses text written by `export_notation` back into its moves, for
+    /// clients replaying a shared match. Cells must fit this context's
+    /// board. Doesn't touch the match state.
+    pub fn import_notation(&self, notation: String) -> app::Result<Vec<NotationMove>> {
+        Ok(notation::parse(&notation, self.board_size())?)
+    }
+
+    /// Snapshot of the active match's public state; see `MatchSnapshot`.
+    pub fn export_match(&self, match_id: &str) -> app::Result<MatchSnapshot> {
+        self.check_match_id(match_id)?;
+        Ok(self.snapshot()?)
+    }
+
+    /// Rolls the active match back to `snapshot`, taken from this same
+    /// match (e.g. on a node recovering from a failover). Only its players
+    /// may, and `force` must be set as the match in play is replaced.
+    /// Rejects snapshots or a held private board that don't fit the
+    /// match's board size; see `restore` for what is checked.
+    pub fn import_match(&mut self, snapshot: MatchSnapshot, force: bool) -> app::Result<()> {
+        let caller = from_executor_id()?;
+        let priv_boards = PrivateBoards::private_load_or_default()?;
+        if let Some(pb) = priv_boards
+            .boards
+            .get(&PrivateBoards::key(&snapshot.match_id))?
+        {
+            pb.check_integrity(self.rules.get().board_size)
+                .map_err(|_| board_size_mismatch())?;
         }
+        self.restore(&caller, snapshot, force)?;
+        self.report_turn();
+        Ok(())
+    }
+
+    /// Checks a fleet the way `place_ships` would, but reports every
+    /// problem at once instead of stopping at the first, so a placement UI
+    /// can mark each ship. Read-only.
+    pub fn validate_fleet_detailed(
+        &self,
+        match_id: &str,
+        ships: Vec<String>,
+    ) -> app::Result<FleetValidation> {
+        self.check_match_id(match_id)?;
+        Ok(self.fleet_validation(&ships))
+    }
+
+    pub fn get_own_board(&self, match_id: &str) -> app::Result<OwnBoardView> {
+        self.check_match_id(match_id)?;
         let caller = from_executor_id()?;
         let priv_boards = PrivateBoards::private_load_or_default()?;
         let pb = priv_boards
             .boards
             .get(&PrivateBoards::key(match_id))?
             .ok_or_else(|| AppError::from(GameError::NotFound(match_id.to_string())))?;
-        let mut board = pb.get_board().0.clone();
-        if let Some(p) = self.pending.get().as_ref() {
-            if p.target == caller {
-                let idx = (p.y as usize) * (BOARD_SIZE as usize) + (p.x as usize);
-                if idx < board.len() {
-                    board[idx] = Cell::Pending.to_u8();
-                }
-            }
-        }
-        Ok(OwnBoardView {
-            size: BOARD_SIZE,
-            board,
-        })
+        Ok(self.own_board_view(&caller, &pb)?)
+    }
+
+    /// Value of a single cell of the caller's own board, for UIs that only
+    /// need one cell rather than the whole of `get_own_board`. Only ever
+    /// reads the private board held by the caller's node.
+    pub fn own_cell(&self, match_id: &str, x: u8, y: u8) -> app::Result<u8> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        let priv_boards = PrivateBoards::private_load_or_default()?;
+        let pb = priv_boards
+            .boards
+            .get(&PrivateBoards::key(match_id))?
+            .ok_or_else(|| AppError::from(GameError::BoardNotFound))?;
+        Ok(self.own_cell_for(&caller, &pb, x, y)?)
+    }
+
+    /// Cells of the caller's ships that haven't been hit yet, row-major, so
+    /// a defender's HUD can tell damaged segments from intact ones without
+    /// fetching the whole board. Only ever reads the private board held by
+    /// the caller's node.
+    pub fn get_my_intact_cells(&self, match_id: &str) -> app::Result<Vec<Coordinate>> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        let priv_boards = PrivateBoards::private_load_or_default()?;
+        let pb = priv_boards
+            .boards
+            .get(&PrivateBoards::key(match_id))?
+            .ok_or_else(|| AppError::from(GameError::BoardNotFound))?;
+        Ok(self.intact_cells_for(&caller, &pb)?)
+    }
+
+    /// The caller's own ships that have been sunk, read from their private
+    /// board, so the defender's UI doesn't have to piece it together from
+    /// `ShotFired` events.
+    pub fn get_my_sunk_ships(&self, match_id: &str) -> app::Result<Vec<Ship>> {
+        self.check_match_id(match_id)?;
+        let priv_boards = PrivateBoards::private_load_or_default()?;
+        let pb = priv_boards
+            .boards
+            .get(&PrivateBoards::key(match_id))?
+            .ok_or_else(|| AppError::from(GameError::BoardNotFound))?;
+        self.check_board_sizes(&pb)?;
+        Ok(pb.sunk_ships())
     }
 
     pub fn get_shots(&self, match_id: &str) -> app::Result<ShotsView> {
-        let active_id = self
-            .match_id
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("no active match".into())))?;
-        if match_id != active_id {
-            app::bail!(GameError::NotFound(match_id.to_string()));
-        }
+        self.check_match_id(match_id)?;
         let caller = from_executor_id()?;
         let p1 = self.player1_or_panic()?;
         let p2 = self.player2_or_panic()?;
         if caller != p1 && caller != p2 {
             app::bail!(GameError::Forbidden("not a player".into()));
         }
-        let map = if caller == p1 {
-            &self.shots_p1
-        } else {
-            &self.shots_p2
-        };
-        let mut shots = vec![0u8; (BOARD_SIZE as usize) * (BOARD_SIZE as usize)];
-        let entries = map
-            .entries()
-            .map_err(|e| AppError::msg(format!("shots.entries: {e}")))?;
-        for (key, reg) in entries {
-            let idx = key[0] as usize;
-            if idx < shots.len() {
-                shots[idx] = *reg.get();
-            }
-        }
         Ok(ShotsView {
-            size: BOARD_SIZE,
-            shots,
+            size: self.board_size(),
+            shots: self.shot_board_for(&caller)?,
         })
     }
 
-    pub fn get_active_match_id(&self) -> app::Result<Option<String>> {
-        Ok(self.match_id.get().clone())
+    /// `get_own_board` with cells in `encoding`'s codes.
+    pub fn get_own_board_as(
+        &self,
+        match_id: &str,
+        encoding: CellEncoding,
+    ) -> app::Result<OwnBoardView> {
+        let mut view = self.get_own_board(match_id)?;
+        view.board = encoding.recode(&view.board);
+        Ok(view)
     }
 
-    pub fn get_current_turn(&self) -> app::Result<Option<String>> {
-        Ok(self.turn.get().as_ref().map(|pk| pk.to_base58()))
+    /// `Board::settled_fingerprint` of the caller's `get_shots` board: poll
+    /// this and refetch the board only when it changes.
+    pub fn get_shots_fingerprint(&self, match_id: &str) -> app::Result<u64> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        self.side_of(&caller)?;
+        Ok(board::Board(self.shot_board_for(&caller)?).settled_fingerprint())
     }
 
-    pub fn get_current_user(&self) -> app::Result<String> {
-        Ok(from_executor_id()?.to_base58())
+    /// `get_shots` with cells in `encoding`'s codes.
+    pub fn get_shots_as(&self, match_id: &str, encoding: CellEncoding) -> app::Result<ShotsView> {
+        let mut view = self.get_shots(match_id)?;
+        view.shots = encoding.recode(&view.shots);
+        Ok(view)
     }
 
-    #[allow(unused_variables)]
-    pub fn acknowledge_shot_handler(&mut self, id: &str, x: u8, y: u8) -> app::Result<()> {
-        self.acknowledge_shot(id)?;
-        Ok(())
+    pub fn get_own_board_packed(&self, match_id: &str) -> app::Result<PackedBoardView> {
+        let view = self.get_own_board(match_id)?;
+        Ok(PackedBoardView {
+            size: view.size,
+            packed: board::Board(view.board).to_packed(),
+        })
     }
-}
 
-impl GameState {
-    fn is_player(&self, pk: &PublicKey) -> bool {
-        self.player1.get().as_ref() == Some(pk) || self.player2.get().as_ref() == Some(pk)
+    pub fn get_shots_packed(&self, match_id: &str) -> app::Result<PackedBoardView> {
+        let view = self.get_shots(match_id)?;
+        Ok(PackedBoardView {
+            size: view.size,
+            packed: board::Board(view.shots).to_packed(),
+        })
     }
 
-    fn player1_or_panic(&self) -> app::Result<PublicKey> {
-        self.player1
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("player1 unset".into())))
+    /// `get_own_board` reshaped into rows, indexed `board[y][x]`.
+    pub fn get_own_board_grid(&self, match_id: &str) -> app::Result<Vec<Vec<u8>>> {
+        Ok(self.get_own_board(match_id)?.to_grid())
     }
 
-    fn player2_or_panic(&self) -> app::Result<PublicKey> {
-        self.player2
-            .get()
-            .clone()
-            .ok_or_else(|| AppError::from(GameError::Invalid("player2 unset".into())))
+    /// `get_shots` reshaped into rows, indexed `shots[y][x]`.
+    pub fn get_shots_grid(&self, match_id: &str) -> app::Result<Vec<Vec<u8>>> {
+        Ok(self.get_shots(match_id)?.to_grid())
     }
-}
 
-/// Compute `SHA256(board_bytes || salt)` — exposed for tests and cross-module use.
-pub fn compute_commitment(board_bytes: &[u8], salt: &[u8; 16]) -> [u8; 32] {
-    let mut h = Sha256::new();
-    h.update(board_bytes);
-    h.update(salt);
-    h.finalize().into()
-}
+    /// Cells the caller has not fired at yet, in row-major order.
+    pub fn get_open_targets(&self, match_id: &str) -> app::Result<Vec<Coordinate>> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        Ok(self.open_targets_for(&caller)?)
+    }
 
-fn hex_encode(bytes: &[u8; 32]) -> String {
-    let mut s = String::with_capacity(64);
-    for b in bytes {
-        s.push_str(&format!("{:02x}", b));
+    pub fn get_target_grid(&self, match_id: &str) -> app::Result<TargetGridView> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        Ok(self.target_grid_for(&caller)?)
     }
-    s
-}
 
-/// Helper used by the audit routine (Task 9) and commitment bootstrapping (Task 7).
-pub fn is_ship_cell(value: u8) -> bool {
-    Cell::from_u8(value) == Cell::Ship
-}
+    /// For each cell of the opponent's board, row-major, how likely it is
+    /// to hold a ship given the caller's hits, misses and sunk ships; see
+    /// the `density` module. Cells already fired at score 0.
+    pub fn get_hit_density(&self, match_id: &str) -> app::Result<Vec<f32>> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        Ok(self.hit_density_for(&caller)?)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn get_shot_counts(&self, match_id: &str) -> app::Result<ShotCountsView> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        Ok(self.shot_counts_for(&caller)?)
+    }
 
-    #[test]
-    fn is_ship_cell_identifies_ship_sentinel() {
-        assert!(is_ship_cell(Cell::Ship.to_u8()));
-        assert!(!is_ship_cell(Cell::Empty.to_u8()));
-        assert!(!is_ship_cell(Cell::Hit.to_u8()));
-        assert!(!is_ship_cell(Cell::Miss.to_u8()));
-        assert!(!is_ship_cell(Cell::Pending.to_u8()));
+    /// Whether the base58 `key` is one of this match's two players. Lets a
+    /// client holding several identities pick the one to act as.
+    pub fn is_participant(&self, match_id: &str, key: String) -> app::Result<bool> {
+        self.check_match_id(match_id)?;
+        Ok(self.is_participant_key(&key)?)
     }
 
-    #[test]
-    fn game_state_skeleton_fields_are_empty() {
-        // Empty player keys + empty match_id → init does not populate
-        // identity fields; everything stays at the default.
-        let state = GameState::init("".into(), "".into(), None, "".into());
-        assert!(state.lobby_context_id.get().is_none());
-        assert!(state.match_id.get().is_none());
-        assert!(state.player1.get().is_none());
-        assert!(state.winner.get().is_none());
-        assert!(!(*state.placed_p1.get()));
-        assert!(!(*state.placed_p2.get()));
-        assert!(state.pending.get().is_none());
+    /// Base58 key of the winner; `None` while the match is still running.
+    pub fn get_winner(&self, match_id: &str) -> app::Result<Option<String>> {
+        Ok(self.winner_of(match_id)?)
     }
 
-    #[test]
-    fn compute_commitment_matches_manual_sha256() {
-        let board_bytes = calimero_sdk::borsh::to_vec(&vec![1u8, 0, 0, 1u8]).unwrap();
-        let salt = [9u8; 16];
-        let mut h = Sha256::new();
-        h.update(&board_bytes);
-        h.update(salt);
-        let expected: [u8; 32] = h.finalize().into();
-        assert_eq!(compute_commitment(&board_bytes, &salt), expected);
+    /// Health check for live deployments: runs the match's invariant checks
+    /// and lists every violation found, or nothing when the state is sound.
+    /// Read-only, and reports corruption rather than failing on it. Private
+    /// boards are only checked on a node that holds one.
+    pub fn audit_match(&self, match_id: &str) -> app::Result<Vec<String>> {
+        self.check_match_id(match_id)?;
+        Ok(self.invariant_violations())
     }
 
-    #[test]
-    fn hex_encode_produces_64_char_lowercase() {
-        let mut bytes = [0u8; 32];
-        bytes[0] = 0xAB;
-        bytes[31] = 0xCD;
-        let s = hex_encode(&bytes);
-        assert_eq!(s.len(), 64);
-        assert!(s.starts_with("ab"));
-        assert!(s.ends_with("cd"));
-        assert!(s
-            .chars()
-            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+    /// Cells settled by shots after sequence number `seq`, so a streaming
+    /// client can keep its shot boards current without refetching them.
+    /// Start from 0 and pass back the returned `seq`. A cursor beyond the
+    /// current `seq` means the match was reset or restored since: start
+    /// over from 0.
+    pub fn get_shots_since(&self, match_id: &str, seq: u64) -> app::Result<ShotsSince> {
+        self.check_match_id(match_id)?;
+        Ok(self.shots_since(seq))
     }
 
-    #[test]
-    fn init_stores_lobby_match_id_verbatim() {
-        let pk1 = PublicKey([1u8; 32]).to_base58();
-        let pk2 = PublicKey([2u8; 32]).to_base58();
-        let lobby_match_id = format!("{pk1}-1700000000000-deadbeef");
-        let state = GameState::init(
-            pk1.clone(),
-            pk2,
-            Some("lobby".into()),
-            lobby_match_id.clone(),
-        );
-        assert_eq!(state.turn.get().as_ref().unwrap().to_base58(), pk1);
-        assert_eq!(
-            state.match_id.get().as_deref(),
-            Some(lobby_match_id.as_str())
+    pub fn get_last_shot(&self, match_id: &str) -> app::Result<Option<ShotRecord>> {
+        self.check_match_id(match_id)?;
+        Ok(self.last_shot.get().clone())
+    }
+
+    /// The shot awaiting acknowledgement, or `None`. Readable by both
+    /// players: the shooter to confirm it registered, the target to see
+    /// what is incoming.
+    pub fn get_pending_shot(&self, match_id: &str) -> app::Result<Option<PendingShotView>> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        Ok(self.pending_shot_for(&caller)?)
+    }
+
+    /// Every shot awaiting acknowledgement, oldest first. Holds more than
+    /// one only when the rules allow several shots per turn.
+    pub fn get_pending_shots(&self, match_id: &str) -> app::Result<Vec<PendingShotView>> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        Ok(self.pending_shots_for(&caller)?)
+    }
+
+    /// Everything a client needs to render the match for the caller in one
+    /// call: the summary (phase, turn, winner), their own board, their
+    /// fog-of-war view of the opponent, ship cells left on each side and
+    /// the pending shot. Players only; each part hides what its standalone
+    /// endpoint hides.
+    pub fn get_game_state(&self, match_id: &str) -> app::Result<GameStateView> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        let own = PrivateBoards::private_load_or_default()?
+            .boards
+            .get(&PrivateBoards::key(match_id))?;
+        Ok(self.game_state_for(&caller, own.as_ref())?)
+    }
+
+    /// Board size and fleet this match is played with.
+    pub fn get_rules(&self, match_id: &str) -> app::Result<PlacementRules> {
+        self.check_match_id(match_id)?;
+        Ok(self.rules.get().clone())
+    }
+
+    /// Progress toward a hit-threshold win, derived from each player's
+    /// cumulative hits and the match's `WinCondition`.
+    pub fn get_hits_to_win(&self, match_id: &str) -> app::Result<HitsToWinView> {
+        self.check_match_id(match_id)?;
+        Ok(self.hits_to_win())
+    }
+
+    /// Ship cells each player has left afloat, for a scoreboard anyone in
+    /// the context may read. Derived from the fleet size and the public hit
+    /// counters, so neither private board is consulted and no cell is
+    /// revealed.
+    pub fn get_total_ships_remaining(&self, match_id: &str) -> app::Result<ShipsRemainingView> {
+        self.check_match_id(match_id)?;
+        Ok(self.ships_remaining())
+    }
+
+    /// Current and longest run of consecutive hits for each player.
+    pub fn get_hit_streaks(&self, match_id: &str) -> app::Result<HitStreaksView> {
+        self.check_match_id(match_id)?;
+        Ok(HitStreaksView {
+            player1: *self.streak_p1.get(),
+            player2: *self.streak_p2.get(),
+        })
+    }
+
+    /// Up to `limit` of the most recent events, oldest first.
+    pub fn get_recent_events(&self, match_id: &str, limit: u32) -> app::Result<Vec<EventRecord>> {
+        self.check_match_id(match_id)?;
+        Ok(self.recent_events_inner(limit)?)
+    }
+
+    pub fn get_match_summary(&self) -> app::Result<MatchSummary> {
+        Ok(self.summary()?)
+    }
+
+    pub fn get_active_match_id(&self) -> app::Result<Option<String>> {
+        Ok(self.match_id.get().clone())
+    }
+
+    pub fn get_current_turn(&self) -> app::Result<Option<String>> {
+        Ok(self.turn.get().as_ref().map(|pk| pk.to_base58()))
+    }
+
+    /// 1 if the caller is `player1`, 2 if `player2`. Side 1's shots are
+    /// `shots_p1`, fired at player2's board, and vice versa.
+    pub fn get_my_side(&self, match_id: &str) -> app::Result<u8> {
+        self.check_match_id(match_id)?;
+        let caller = from_executor_id()?;
+        Ok(self.side_of(&caller)?)
+    }
+
+    /// Each time the turn passed and when, oldest first, for auditing how
+    /// long each player takes to move. Starts empty after `import_match`.
+    pub fn get_turn_history(&self, match_id: &str) -> app::Result<Vec<TurnChange>> {
+        self.check_match_id(match_id)?;
+        Ok(self.turn_history.get().clone())
+    }
+
+    pub fn get_current_user(&self) -> app::Result<String> {
+        Ok(from_executor_id()?.to_base58())
+    }
+
+    /// Maintenance operation for dev and test deployments: returns the
+    /// context to its pristine, match-less state so integration runs don't
+    /// need a redeploy. Only builds with the `dev` feature accept it.
+    /// Refused while an unfinished match is active; once a match has ended
+    /// only its players may reset. Also drops this node's private boards
+    /// for that match and the caller's own commitment (commitments are
+    /// writer-owned, so each player clears theirs by calling `reset` too).
+    /// Emits no events.
+    pub fn reset(&mut self) -> app::Result<()> {
+        if !cfg!(feature = "dev") {
+            app::bail!(GameError::Forbidden("dev builds only".into()));
+        }
+        let caller = from_executor_id()?;
+        self.reset_for(&caller)?;
+        self.commitments
+            .remove()
+            .map_err(|e| AppError::msg(format!("commitments.remove: {e}")))?;
+        Ok(())
+    }
+
+    /// Starts a single-player practice match in this context: the caller
+    /// against `GHOST_PLAYER`, whose fleet is placed from `seed`. The caller
+    /// places no ships and fires with `practice_fire`, which resolves each
+    /// shot on the spot. Practice results are never sent to the lobby, so
+    /// they don't count toward player stats. Replaces a finished match
+    /// under the same rules as `reset`. Returns the practice match id.
+    pub fn create_practice_match(&mut self, seed: u64) -> app::Result<String> {
+        let caller = from_executor_id()?;
+        Ok(self.start_practice(&caller, seed)?)
+    }
+
+    /// Fires at the ghost's board in a practice match and returns `"hit"`
+    /// or `"miss"`. The turn never passes.
+    pub fn practice_fire(&mut self, match_id: &str, x: u8, y: u8) -> app::Result<String> {
+        let caller = from_executor_id()?;
+        Ok(self.practice_fire_for(&caller, match_id, x, y)?)
+    }
+
+    /// Moves the caller's seat in the match to `new_key` (base58), for a
+    /// player who is rotating keys. Turn, pending shot and last shot follow
+    /// the seat, and the lobby's listing is told of the move; the private
+    /// board is keyed by match id on this node and stays put. A commitment
+    /// already made stays under the old key, which the audit keeps reading
+    /// it from; a player who hasn't placed yet commits under the new one.
+    /// Call it from the old key before giving it up.
+    pub fn migrate_player(&mut self, match_id: &str, new_key: String) -> app::Result<()> {
+        let caller = from_executor_id()?;
+        let new_pk = self.migrate_player_for(&caller, match_id, &new_key)?;
+        let (from, to) = (caller.to_base58(), new_pk.to_base58());
+        if self.lobby_context_id.get().is_some() {
+            self.notify_lobby(
+                "on_player_migrated",
+                calimero_sdk::serde_json::json!({
+                    "match_id": match_id,
+                    "context_id": own_context_id(),
+                    "from": from,
+                    "to": to,
+                }),
+            );
+        }
+        self.emit_recorded(Event::PlayerMigrated {
+            id: match_id,
+            from: &from,
+            to: &to,
+        })?;
+        Ok(())
+    }
+
+    /// `{"abi_version": ABI_VERSION, "manifest": <method and type
+    /// manifest>}`. Clients compare it against the bindings they were
+    /// generated from to fail fast on a mismatched deployment.
+    pub fn abi_descriptor(&self) -> app::Result<String> {
+        Ok(abi_descriptor_json()?)
+    }
+
+    #[allow(unused_variables)]
+    pub fn acknowledge_shot_handler(&mut self, id: &str, x: u8, y: u8) -> app::Result<()> {
+        self.acknowledge_shot(id)?;
+        Ok(())
+    }
+}
+
+impl GameState {
+    /// Testable inner for `propose_shot`: every precondition that depends on
+    /// the caller, returning the target on success. Membership is checked
+    /// before placement, turn and pending so a non-player always sees
+    /// "not a player" rather than whichever game-state error happens to
+    /// fire first.
+    pub(crate) fn validate_shot(
+        &self,
+        caller: &PublicKey,
+        x: u8,
+        y: u8,
+    ) -> Result<PublicKey, GameError> {
+        let (p1, p2) = self.authorize_player(caller)?;
+        self.check_not_paused()?;
+        if !board::Board::in_bounds(self.board_size(), x, y) {
+            return Err(GameError::Invalid("out of bounds".into()));
+        }
+        let (mine, theirs) = if *caller == p1 {
+            (*self.placed_p1.get(), *self.placed_p2.get())
+        } else {
+            (*self.placed_p2.get(), *self.placed_p1.get())
+        };
+        if !mine {
+            return Err(GameError::Invalid("place your ships first".into()));
+        }
+        if !theirs {
+            return Err(GameError::Invalid(
+                "opponent has not placed ships yet".into(),
+            ));
+        }
+        // Turn before pending: the defender of an incoming shot is told it
+        // isn't their turn; only the shooter can see "already pending".
+        if self.turn.get().as_ref() != Some(caller) {
+            return Err(GameError::Forbidden("not your turn".into()));
+        }
+        // Salvo rules let the shooter queue shots until the turn's
+        // allowance is used; with one shot per turn this is a plain
+        // "one pending shot at a time".
+        if !self.pending.get().is_empty()
+            && self
+                .game_rules
+                .get()
+                .turn_exhausted(*self.shots_this_turn.get())
+        {
+            return Err(GameError::Invalid("a shot is already pending".into()));
+        }
+        let target = if *caller == p1 { p2 } else { p1 };
+        // Only a corrupt match (player1 == player2 slipping past the lobby's
+        // self-play check) gets here; fail instead of firing at our own board.
+        if target == *caller {
+            return Err(GameError::Invalid("corrupt match: self-target".into()));
+        }
+        Ok(target)
+    }
+
+    /// Tie-break rule for the end of a match: the player who fired the
+    /// decisive shot wins. Resolving a shot only ever touches the target's
+    /// fleet, so even under rules where both fleets could reach zero in the
+    /// same turn (salvos, extra turns), the shooter's own fleet state never
+    /// hands the win to the target — the first resolved sinking shot decides,
+    /// and everything after it is rejected as `Finished`. What counts as
+    /// decisive comes from the match's `WinCondition`.
+    pub(crate) fn decisive_winner(
+        &self,
+        pending: &PendingShot,
+        shooter_hits: u32,
+        target_ships_left: u64,
+    ) -> Option<PublicKey> {
+        self.game_rules
+            .get()
+            .is_won(shooter_hits, target_ships_left)
+            .then(|| pending.shooter.clone())
+    }
+
+    /// Appends `event` to the recent-events buffer, evicting the entry that
+    /// falls out of the window.
+    pub(crate) fn record_event(&mut self, event: &Event<'_>) -> Result<(), GameError> {
+        let seq = *self.event_seq.get();
+        self.recent_events
+            .insert(
+                seq.to_be_bytes(),
+                LwwRegister::new(EventRecord::from(event)),
+            )
+            .map_err(|e| GameError::Invalid(format!("recent_events.insert: {e}")))?;
+        if let Some(evicted) = seq.checked_sub(RECENT_EVENTS_CAP) {
+            self.recent_events
+                .remove(&evicted.to_be_bytes())
+                .map_err(|e| GameError::Invalid(format!("recent_events.remove: {e}")))?;
+        }
+        self.event_seq.set(seq + 1);
+        Ok(())
+    }
+
+    fn emit_recorded(&mut self, event: Event<'_>) -> Result<(), GameError> {
+        self.record_event(&event)?;
+        app::emit!(event);
+        Ok(())
+    }
+
+    /// Testable inner for `get_recent_events`.
+    pub(crate) fn recent_events_inner(&self, limit: u32) -> Result<Vec<EventRecord>, GameError> {
+        let end = *self.event_seq.get();
+        let start = end.saturating_sub(u64::from(limit).min(RECENT_EVENTS_CAP));
+        let mut events = Vec::new();
+        for seq in start..end {
+            let record = self
+                .recent_events
+                .get(&seq.to_be_bytes())
+                .map_err(|e| GameError::Invalid(format!("recent_events.get: {e}")))?;
+            if let Some(record) = record {
+                events.push(record.get().clone());
+            }
+        }
+        Ok(events)
+    }
+
+    /// Won, or drawn on ammunition.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.winner.get().is_some() || *self.drawn.get()
+    }
+
+    /// `shooter`'s shot board as `get_shots` shows it: their stored shots,
+    /// with every cell of a ship they have sunk as `Sunk` instead of `Hit`
+    /// unless `reveal_on_sink` is off.
+    pub(crate) fn shot_board_for(&self, shooter: &PublicKey) -> Result<Vec<u8>, GameError> {
+        let (shots, sunk) = if self.player1.get().as_ref() == Some(shooter) {
+            (&self.shots_p1, &self.sunk_p1)
+        } else {
+            (&self.shots_p2, &self.sunk_p2)
+        };
+        let mut cells = read_cell_map(shots, self.board_size())?;
+        for (cell, ship_len) in cells.iter_mut().zip(self.revealed_sunk(sunk)?) {
+            if ship_len > 0 && Cell::from_u8(*cell) == Cell::Hit {
+                *cell = Cell::Sunk.to_u8();
+            }
+        }
+        Ok(cells)
+    }
+
+    /// A sunk-ship overlay as its shooter may see it: as stored under
+    /// `reveal_on_sink`, otherwise blank, so sunk ships read as plain hits.
+    fn revealed_sunk(
+        &self,
+        sunk: &UnorderedMap<[u8; 1], LwwRegister<u8>>,
+    ) -> Result<Vec<u8>, GameError> {
+        if self.game_rules.get().reveal_on_sink {
+            read_cell_map(sunk, self.board_size())
+        } else {
+            Ok(vec![0; self.board_cells()])
+        }
+    }
+
+    /// The cells of a ship just sunk, if `reveal_on_sink` lets the shooter
+    /// know them.
+    fn revealed_ship(&self, sunk_cells: &[Coordinate]) -> Vec<Coordinate> {
+        if self.game_rules.get().reveal_on_sink {
+            sunk_cells.to_vec()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Shots `player` has fired so far, counting one still pending.
+    pub(crate) fn shots_fired_by(&self, player: &PublicKey) -> Result<u32, GameError> {
+        let map = if self.player1.get().as_ref() == Some(player) {
+            &self.shots_p1
+        } else {
+            &self.shots_p2
+        };
+        Ok(read_cell_map(map, self.board_size())?
+            .into_iter()
+            .filter(|&c| {
+                let cell = Cell::from_u8(c);
+                cell.is_resolved_shot() || cell.is_pending()
+            })
+            .count() as u32)
+    }
+
+    /// Whether the match is drawn after `pending` resolved without a win:
+    /// neither player can fire any more, for want of ammunition or of cells
+    /// to fire at, so the match can't deadlock. One player running dry first ends nothing;
+    /// the opponent fires the rest of theirs (see `advance_turn`).
+    pub(crate) fn is_drawn_out(&self, pending: &PendingShot) -> Result<bool, GameError> {
+        Ok(!self.can_fire(&pending.shooter)? && !self.can_fire(&pending.target)?)
+    }
+
+    /// Whether `player` can take another shot: they have ammunition left
+    /// and a cell they haven't fired at.
+    pub(crate) fn can_fire(&self, player: &PublicKey) -> Result<bool, GameError> {
+        let fired = self.shots_fired_by(player)?;
+        Ok(!self.game_rules.get().out_of_ammo(fired) && self.has_legal_move(player)?)
+    }
+
+    /// Whether `shooter` still has a cell they haven't fired at.
+    pub(crate) fn has_legal_move(&self, shooter: &PublicKey) -> Result<bool, GameError> {
+        Ok((self.shots_fired_by(shooter)? as usize) < self.board_cells())
+    }
+
+    pub(crate) fn phase(&self) -> MatchPhase {
+        if self.is_finished() {
+            MatchPhase::Finished
+        } else if *self.placed_p1.get() && *self.placed_p2.get() {
+            MatchPhase::Playing
+        } else {
+            MatchPhase::Placement
+        }
+    }
+
+    /// Next value of the match's deterministic random stream: draw `n`
+    /// is `splitmix64(seed + n)`, so any draw can be recomputed from the
+    /// seed alone.
+    pub(crate) fn next_seed(&mut self) -> Result<u64, GameError> {
+        let seed = self
+            .seed
+            .get()
+            .ok_or_else(|| GameError::Invalid("match has no seed".into()))?;
+        let draws = *self.seed_draws.get();
+        self.seed_draws.set(draws + 1);
+        let mut state = seed.wrapping_add(draws);
+        Ok(players::splitmix64(&mut state))
+    }
+
+    pub(crate) fn summary(&self) -> Result<MatchSummary, GameError> {
+        let id = self
+            .match_id
+            .get()
+            .clone()
+            .ok_or_else(|| GameError::Invalid("no active match".into()))?;
+        let (Some(p1), Some(p2)) = (self.player1.get().as_ref(), self.player2.get().as_ref())
+        else {
+            return Err(GameError::Invalid("players unset".into()));
+        };
+        Ok(MatchSummary {
+            id,
+            player1: p1.to_base58(),
+            player2: p2.to_base58(),
+            turn: self.turn.get().as_ref().map(|pk| pk.to_base58()),
+            phase: self.phase(),
+            winner: self.winner.get().as_ref().map(|pk| pk.to_base58()),
+            created_ms: *self.created_ms.get(),
+            ended_ms: *self.ended_ms.get(),
+            seed: if self.is_finished() {
+                *self.seed.get()
+            } else {
+                None
+            },
+            paused: self.paused_since_ms.get().is_some(),
+        })
+    }
+
+    /// Number of cells on a board of the match's size.
+    fn board_cells(&self) -> usize {
+        let size = usize::from(self.board_size());
+        size * size
+    }
+
+    /// Width and height of the match's (square) board.
+    pub(crate) fn board_size(&self) -> u8 {
+        self.rules.get().board_size
+    }
+
+    /// Checks that both shot maps, both sunk overlays and the loaded
+    /// private board `pb` all fit the match's board size. Run whenever a
+    /// private board is loaded, so a corrupted or mis-imported match fails
+    /// loudly instead of indexing the two players' boards differently.
+    pub(crate) fn check_board_sizes(&self, pb: &PlayerBoard) -> Result<(), GameError> {
+        let cells = self.board_cells();
+        for map in [&self.shots_p1, &self.shots_p2, &self.sunk_p1, &self.sunk_p2] {
+            let mut keys = map
+                .entries()
+                .map_err(|e| GameError::Invalid(format!("shots.entries: {e}")))?
+                .map(|(key, _)| key[0] as usize);
+            if keys.any(|idx| idx >= cells) {
+                return Err(board_size_mismatch());
+            }
+        }
+        pb.check_integrity(self.rules.get().board_size)
+            .map_err(|_| board_size_mismatch())
+    }
+
+    /// First check of every method that takes a `match_id`: a context holds
+    /// one match, and any other id is `NotFound` — whether the client is
+    /// stale or aimed at the wrong context — before anything else is looked
+    /// at.
+    pub(crate) fn check_match_id(&self, match_id: &str) -> Result<(), GameError> {
+        let active_id = self
+            .match_id
+            .get()
+            .as_deref()
+            .ok_or_else(|| GameError::Invalid("no active match".into()))?;
+        if match_id != active_id {
+            return Err(GameError::NotFound(match_id.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Error for a pending shot whose target board this node doesn't hold.
+    /// `validate_shot` only lets a shot through once both fleets are placed,
+    /// so the board exists somewhere; it's just not here (the call came from
+    /// the shooter's node, or the target's private storage was lost).
+    fn missing_target_board(&self, pending: &PendingShot) -> GameError {
+        debug_assert!(
+            *self.placed_p1.get() && *self.placed_p2.get(),
+            "a shot was pending before both fleets were placed"
+        );
+        GameError::NotFound(format!("private board of {}", pending.target.to_base58()))
+    }
+
+    /// Shared preconditions of every state-changing match method, in the
+    /// one order clients can rely on: match id, then `Finished`, then
+    /// whether `caller` plays in this match. Method-specific checks (turn,
+    /// pending shot, ...) come after.
+    pub(crate) fn authorize(&self, caller: &PublicKey, match_id: &str) -> Result<(), GameError> {
+        self.check_match_id(match_id)?;
+        self.authorize_player(caller).map(drop)
+    }
+
+    /// The `Finished` and participant half of `authorize`, for inners that
+    /// don't see the match id. Returns `(player1, player2)`.
+    fn authorize_player(&self, caller: &PublicKey) -> Result<(PublicKey, PublicKey), GameError> {
+        if self.is_finished() {
+            return Err(GameError::Finished);
+        }
+        let (Some(p1), Some(p2)) = (self.player1.get().clone(), self.player2.get().clone()) else {
+            return Err(GameError::Invalid("players unset".into()));
+        };
+        if *caller != p1 && *caller != p2 {
+            return Err(GameError::Forbidden("not a player".into()));
+        }
+        Ok((p1, p2))
+    }
+
+    /// Testable inner for `pause_match`.
+    pub(crate) fn pause_for(&mut self, caller: &PublicKey, now_ms: u64) -> Result<(), GameError> {
+        self.authorize_player(caller)?;
+        if self.paused_since_ms.get().is_some() {
+            return Err(GameError::Invalid("match already paused".into()));
+        }
+        self.paused_since_ms.set(Some(now_ms));
+        Ok(())
+    }
+
+    /// Testable inner for `resume_match`. The pending shot's clock moves
+    /// forward by the length of the pause, so the break doesn't count
+    /// towards the rules' `shot_timeout_ms`.
+    pub(crate) fn resume_for(&mut self, caller: &PublicKey, now_ms: u64) -> Result<(), GameError> {
+        self.authorize_player(caller)?;
+        let Some(paused_at) = *self.paused_since_ms.get() else {
+            return Err(GameError::Invalid("match is not paused".into()));
+        };
+        if let Some(since) = *self.pending_since_ms.get() {
+            let paused_for = now_ms.saturating_sub(paused_at);
+            self.pending_since_ms
+                .set(Some(since.saturating_add(paused_for)));
+        }
+        self.paused_since_ms.set(None);
+        Ok(())
+    }
+
+    fn check_not_paused(&self) -> Result<(), GameError> {
+        if self.paused_since_ms.get().is_some() {
+            return Err(GameError::Forbidden("match paused".into()));
+        }
+        Ok(())
+    }
+
+    /// The pending batch, provided its oldest shot was proposed at least
+    /// the rules' `shot_timeout_ms` before `now_ms`.
+    pub(crate) fn timed_out_pending(&self, now_ms: u64) -> Result<Vec<PendingShot>, GameError> {
+        self.check_not_paused()?;
+        let pending = self.pending.get().clone();
+        if pending.is_empty() {
+            return Err(GameError::Invalid("no pending shot".into()));
+        }
+        // A pending shot without a timestamp predates the clock; treat it as
+        // already timed out rather than stuck forever.
+        let since = self.pending_since_ms.get().unwrap_or(0);
+        if now_ms.saturating_sub(since) < self.game_rules.get().shot_timeout_ms {
+            return Err(GameError::Invalid(
+                "pending shot has not timed out yet".into(),
+            ));
+        }
+        Ok(pending)
+    }
+
+    /// Write half of `place_ships`: the private board, then the public
+    /// commitment, then the caller's placed flag, each only once the step
+    /// before it succeeded. A failed commitment write puts `previous` back,
+    /// so no failure leaves a board without a commitment, a commitment
+    /// without a board, or a flag without either.
+    pub(crate) fn persist_placement(
+        &mut self,
+        caller: &PublicKey,
+        match_id: &str,
+        previous: Option<PlayerBoard>,
+        pb: PlayerBoard,
+        commitment: [u8; 32],
+    ) -> Result<(), GameError> {
+        let (p1, _) = self.authorize_player(caller)?;
+        write_private_board(match_id, Some(pb))?;
+        if let Err(e) = self.commitments.insert(LwwRegister::new(commitment)) {
+            write_private_board(match_id, previous)?;
+            return Err(GameError::Invalid(format!("commitments.insert: {e}")));
+        }
+        if *caller == p1 {
+            self.placed_p1.set(true);
+        } else {
+            self.placed_p2.set(true);
+        }
+        Ok(())
+    }
+
+    /// Testable inner for `migrate_player`. Returns the parsed new key.
+    pub(crate) fn migrate_player_for(
+        &mut self,
+        caller: &PublicKey,
+        match_id: &str,
+        new_key: &str,
+    ) -> Result<PublicKey, GameError> {
+        self.check_match_id(match_id)?;
+        let (p1, p2) = self.authorize_player(caller)?;
+        let new_pk = PublicKey::from_base58(new_key)?;
+        if new_pk == *caller {
+            return Err(GameError::Invalid("new key is the current key".into()));
+        }
+        if new_pk == p1 || new_pk == p2 {
+            return Err(GameError::Invalid(
+                "new key already plays this match".into(),
+            ));
+        }
+        if new_pk == GHOST_PLAYER {
+            return Err(GameError::Invalid("new key is reserved".into()));
+        }
+
+        let committed = self.commitment_of(caller)?.is_some();
+        let (seat, committed_as) = if *caller == p1 {
+            (&mut self.player1, &mut self.committed_as_p1)
+        } else {
+            (&mut self.player2, &mut self.committed_as_p2)
+        };
+        seat.set(Some(new_pk.clone()));
+        // Only a key that has committed is worth pointing back at; before
+        // placing, the commitment will be written under the new key. A
+        // second migration keeps pointing at the key that committed.
+        if committed_as.get().is_none() && committed {
+            committed_as.set(Some(caller.clone()));
+        }
+        let swap = |pk: &mut PublicKey| {
+            if pk == caller {
+                *pk = new_pk.clone();
+            }
+        };
+        if let Some(mut turn) = self.turn.get().clone() {
+            swap(&mut turn);
+            self.turn.set(Some(turn));
+        }
+        let mut pending = self.pending.get().clone();
+        for shot in &mut pending {
+            swap(&mut shot.shooter);
+            swap(&mut shot.target);
+        }
+        self.pending.set(pending);
+        let (old_b58, new_b58) = (caller.to_base58(), new_pk.to_base58());
+        if let Some(mut last) = self.last_shot.get().clone() {
+            if last.shooter == old_b58 {
+                last.shooter = new_b58.clone();
+                self.last_shot.set(Some(last));
+            }
+        }
+        let mut log = self.shot_log.get().clone();
+        for shot in log.iter_mut().filter(|s| s.shooter == old_b58) {
+            shot.shooter = new_b58.clone();
+        }
+        self.shot_log.set(log);
+        Ok(new_pk)
+    }
+
+    /// Testable inner for `reset`: everything but the caller's commitment.
+    pub(crate) fn reset_for(&mut self, caller: &PublicKey) -> Result<(), GameError> {
+        if self.match_id.get().is_some() {
+            if !self.is_finished() {
+                return Err(GameError::Invalid("an unfinished match is active".into()));
+            }
+            if !self.is_player(caller) {
+                return Err(GameError::Forbidden("not a player".into()));
+            }
+        }
+        for map in [
+            &mut self.shots_p1,
+            &mut self.shots_p2,
+            &mut self.sunk_p1,
+            &mut self.sunk_p2,
+        ] {
+            map.clear()
+                .map_err(|e| GameError::Invalid(format!("shots.clear: {e}")))?;
+        }
+        self.recent_events
+            .clear()
+            .map_err(|e| GameError::Invalid(format!("recent_events.clear: {e}")))?;
+        // Only the ended match's boards: the map may hold boards this node
+        // keeps for matches in other contexts.
+        if let Some(match_id) = self.match_id.get().clone() {
+            write_private_board(&PrivateBoards::key(&match_id), None)?;
+            write_private_board(&PrivateBoards::ghost_key(&match_id), None)?;
+        }
+
+        self.match_id.set(None);
+        self.player1.set(None);
+        self.player2.set(None);
+        self.turn.set(None);
+        self.winner.set(None);
+        self.drawn.set(false);
+        self.placed_p1.set(false);
+        self.placed_p2.set(false);
+        self.pending.set(Vec::new());
+        self.pending_since_ms.set(None);
+        self.paused_since_ms.set(None);
+        self.last_nonce_p1.set(None);
+        self.last_nonce_p2.set(None);
+        self.last_shot.set(None);
+        self.shot_log.set(Vec::new());
+        self.rules.set(PlacementRules::default());
+        self.game_rules.set(GameRules::default());
+        self.hits_p1.set(0);
+        self.hits_p2.set(0);
+        self.streak_p1.set(HitStreak::default());
+        self.streak_p2.set(HitStreak::default());
+        self.shots_this_turn.set(0);
+        self.turn_history.set(Vec::new());
+        self.practice.set(false);
+        self.committed_as_p1.set(None);
+        self.committed_as_p2.set(None);
+        self.seed.set(None);
+        self.seed_draws.set(0);
+        self.event_seq.set(0);
+        self.ended_ms.set(None);
+        self.created_ms.set(storage_env::time_now());
+        Ok(())
+    }
+
+    /// Testable inner for `create_practice_match`.
+    pub(crate) fn start_practice(
+        &mut self,
+        caller: &PublicKey,
+        seed: u64,
+    ) -> Result<String, GameError> {
+        if *caller == GHOST_PLAYER {
+            return Err(GameError::Invalid("the ghost can't practice".into()));
+        }
+        self.reset_for(caller)?;
+        self.seed.set(Some(seed));
+        let rules = self.rules.get().clone();
+        let ghost = PlayerBoard::random(rules.board_size, &rules.fleet, self.next_seed()?)?;
+        let match_id = format!("practice-{seed}");
+        write_private_board(&PrivateBoards::ghost_key(&match_id), Some(ghost))?;
+
+        self.match_id.set(Some(match_id.clone()));
+        self.player1.set(Some(caller.clone()));
+        self.player2.set(Some(GHOST_PLAYER));
+        self.turn.set(Some(caller.clone()));
+        self.placed_p1.set(true);
+        self.placed_p2.set(true);
+        self.practice.set(true);
+        Ok(match_id)
+    }
+
+    /// Testable inner for `practice_fire`: the checks of `propose_shot`,
+    /// then the resolution of `acknowledge_shot` against the ghost's board,
+    /// without the audit or any lobby notification.
+    pub(crate) fn practice_fire_for(
+        &mut self,
+        caller: &PublicKey,
+        match_id: &str,
+        x: u8,
+        y: u8,
+    ) -> Result<String, GameError> {
+        self.authorize(caller, match_id)?;
+        if !*self.practice.get() {
+            return Err(GameError::Invalid("not a practice match".into()));
+        }
+        let size = self.board_size();
+        let at = Coordinate::new(x, y, size)?;
+        let key = cell_key(size, at.x, at.y);
+        if let Some(existing) = self
+            .shots_p1
+            .get(&key)
+            .map_err(|e| GameError::Invalid(format!("shots.get: {e}")))?
+        {
+            let cell = Cell::from_u8(*existing.get());
+            if cell.is_resolved_shot() {
+                return Err(GameError::Invalid(format!(
+                    "cell ({x},{y}) was already shot ({cell:?})"
+                )));
+            }
+        }
+
+        let ghost_key = PrivateBoards::ghost_key(match_id);
+        let mut ghost = PrivateBoards::private_load_or_default()
+            .map_err(|e| GameError::Invalid(format!("private boards: {e}")))?
+            .boards
+            .get(&ghost_key)
+            .map_err(|e| GameError::Invalid(format!("private boards: {e}")))?
+            .ok_or(GameError::BoardNotFound)?;
+        let is_hit = ShotResolver::resolve_shot(&mut ghost, at.x, at.y) == ShotOutcome::Hit;
+        let sunk_cells = if is_hit {
+            ghost.sunk_ship_at(at.x, at.y).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let ships_remaining = ghost.get_ship_count();
+        write_private_board(&ghost_key, Some(ghost))?;
+
+        let resolved = if is_hit { Cell::Hit } else { Cell::Miss };
+        self.shots_p1
+            .insert(key, LwwRegister::new(resolved.to_u8()))
+            .map_err(|e| GameError::Invalid(format!("shots.insert: {e}")))?;
+        for c in &self.revealed_ship(&sunk_cells) {
+            self.sunk_p1
+                .insert(
+                    cell_key(size, c.x, c.y),
+                    LwwRegister::new(sunk_cells.len() as u8),
+                )
+                .map_err(|e| GameError::Invalid(format!("sunk.insert: {e}")))?;
+        }
+        let hits = self.hits_p1.get().saturating_add(is_hit as u32);
+        self.hits_p1.set(hits);
+        let mut streak = *self.streak_p1.get();
+        streak.record(is_hit);
+        self.streak_p1.set(streak);
+        let result = if is_hit { "hit" } else { "miss" };
+        self.record_shot(ShotRecord {
+            shooter: caller.to_base58(),
+            x,
+            y,
+            result: result.to_string(),
+            sunk: self.revealed_ship(&sunk_cells),
+        });
+
+        self.emit_recorded(Event::ShotFired {
+            id: match_id,
+            x,
+            y,
+            result,
+        })?;
+        if self.game_rules.get().is_won(hits, ships_remaining) {
+            self.winner.set(Some(caller.clone()));
+            self.ended_ms.set(Some(storage_env::time_now()));
+            self.emit_recorded(Event::Winner { id: match_id })?;
+            self.emit_recorded(Event::MatchEnded { id: match_id })?;
+        }
+        Ok(result.to_string())
+    }
+
+    /// Testable inner for `get_pending_shot`: the oldest pending shot.
+    pub(crate) fn pending_shot_for(
+        &self,
+        caller: &PublicKey,
+    ) -> Result<Option<PendingShotView>, GameError> {
+        Ok(self.pending_shots_for(caller)?.into_iter().next())
+    }
+
+    /// Testable inner for `get_pending_shots`. The whole batch shares the
+    /// clock of its oldest shot.
+    pub(crate) fn pending_shots_for(
+        &self,
+        caller: &PublicKey,
+    ) -> Result<Vec<PendingShotView>, GameError> {
+        if !self.is_player(caller) {
+            return Err(GameError::Forbidden("not a player".into()));
+        }
+        Ok(self
+            .pending
+            .get()
+            .iter()
+            .map(|p| PendingShotView {
+                x: p.x,
+                y: p.y,
+                shooter: p.shooter.to_base58(),
+                target: p.target.to_base58(),
+                since_ms: *self.pending_since_ms.get(),
+            })
+            .collect())
+    }
+
+    /// Testable inner for `verify_hits_consistent`: the first of the
+    /// caller's shots that contradicts `revealed`, if any. The fleet itself
+    /// must be well formed (`Ship::validate`, on the board, no overlaps).
+    pub(crate) fn hits_inconsistency_for(
+        &self,
+        caller: &PublicKey,
+        revealed: &[Ship],
+    ) -> Result<Option<audit::AuditFailure>, GameError> {
+        let my_shots = if self.player1.get().as_ref() == Some(caller) {
+            &self.shots_p1
+        } else if self.player2.get().as_ref() == Some(caller) {
+            &self.shots_p2
+        } else {
+            return Err(GameError::Forbidden("not a player".into()));
+        };
+        let size = self.board_size();
+        let mut board = board::Board::new_zeroed(size);
+        let wrap = self.game_rules.get().wrap_edges;
+        for ship in revealed {
+            ship.validate_on(size, wrap)?;
+            for c in &ship.coordinates {
+                if !board::Board::in_bounds(size, c.x, c.y) {
+                    return Err(GameError::Invalid("coordinate out of bounds".into()));
+                }
+                if !board.set_if_empty(size, c.x, c.y, Cell::Ship) {
+                    return Err(GameError::Overlap { x: c.x, y: c.y });
+                }
+            }
+        }
+        Ok(audit::replay_shots(&board.0, size, my_shots).err())
+    }
+
+    /// `pb` as `caller` sees it in `get_own_board`: shots still pending
+    /// against them show as `Pending`.
+    pub(crate) fn own_board_view(
+        &self,
+        caller: &PublicKey,
+        pb: &PlayerBoard,
+    ) -> Result<OwnBoardView, GameError> {
+        self.check_board_sizes(pb)?;
+        let size = self.board_size();
+        let mut board = pb.get_board().0.clone();
+        for p in self.pending.get().iter().filter(|p| p.target == *caller) {
+            let idx = board::Board::idx(size, p.x, p.y);
+            if idx < board.len() {
+                board[idx] = Cell::Pending.to_u8();
+            }
+        }
+        Ok(OwnBoardView { size, board })
+    }
+
+    /// Testable inner for `get_game_state`. `own` is the caller's private
+    /// board, if this node holds one.
+    pub(crate) fn game_state_for(
+        &self,
+        caller: &PublicKey,
+        own: Option<&PlayerBoard>,
+    ) -> Result<GameStateView, GameError> {
+        // Each part enforces its own privacy rule; membership is checked
+        // once up front so a non-player gets Forbidden, not a partial view.
+        if !self.is_player(caller) {
+            return Err(GameError::Forbidden("not a player".into()));
+        }
+        Ok(GameStateView {
+            summary: self.summary()?,
+            own_board: own.map(|pb| self.own_board_view(caller, pb)).transpose()?,
+            target_grid: self.target_grid_for(caller)?,
+            ships_remaining: self.ships_remaining(),
+            pending: self.pending_shots_for(caller)?,
+        })
+    }
+
+    /// Testable inner for `own_cell`: membership, then bounds, then the
+    /// cell of `pb`, showing a shot still pending against `caller` as
+    /// `Pending` just like `get_own_board`.
+    pub(crate) fn own_cell_for(
+        &self,
+        caller: &PublicKey,
+        pb: &PlayerBoard,
+        x: u8,
+        y: u8,
+    ) -> Result<u8, GameError> {
+        if !self.is_player(caller) {
+            return Err(GameError::Forbidden("not a player".into()));
+        }
+        let at = Coordinate::new(x, y, self.board_size())?;
+        self.check_board_sizes(pb)?;
+        let pending_here = self
+            .pending
+            .get()
+            .iter()
+            .any(|p| p.target == *caller && (p.x, p.y) == (at.x, at.y));
+        if pending_here {
+            return Ok(Cell::Pending.to_u8());
+        }
+        Ok(pb.get_board().get(pb.size(), at.x, at.y).to_u8())
+    }
+
+    /// Testable inner for `get_my_intact_cells`.
+    pub(crate) fn intact_cells_for(
+        &self,
+        caller: &PublicKey,
+        pb: &PlayerBoard,
+    ) -> Result<Vec<Coordinate>, GameError> {
+        if !self.is_player(caller) {
+            return Err(GameError::Forbidden("not a player".into()));
+        }
+        self.check_board_sizes(pb)?;
+        let size = pb.size();
+        let board = pb.get_board();
+        Ok((0..size)
+            .flat_map(|y| (0..size).map(move |x| Coordinate { x, y }))
+            .filter(|c| board.get(size, c.x, c.y).is_ship())
+            .collect())
+    }
+
+    /// Inner for `export_match`.
+    pub(crate) fn snapshot(&self) -> Result<MatchSnapshot, GameError> {
+        let summary = self.summary()?;
+        let pending = self.pending.get().clone();
+        let mut shots_p1 = read_cell_map(&self.shots_p1, self.board_size())?;
+        let mut shots_p2 = read_cell_map(&self.shots_p2, self.board_size())?;
+        for cell in shots_p1.iter_mut().chain(shots_p2.iter_mut()) {
+            if Cell::from_u8(*cell).is_pending() {
+                *cell = Cell::Empty.to_u8();
+            }
+        }
+        Ok(MatchSnapshot {
+            version: MATCH_SNAPSHOT_VERSION,
+            match_id: summary.id,
+            player1: summary.player1,
+            player2: summary.player2,
+            turn: summary.turn,
+            winner: summary.winner,
+            drawn: *self.drawn.get(),
+            placed_p1: *self.placed_p1.get(),
+            placed_p2: *self.placed_p2.get(),
+            shots_p1,
+            shots_p2,
+            sunk_p1: self.revealed_sunk(&self.sunk_p1)?,
+            sunk_p2: self.revealed_sunk(&self.sunk_p2)?,
+            hits_p1: *self.hits_p1.get(),
+            hits_p2: *self.hits_p2.get(),
+            shots_this_turn: self
+                .shots_this_turn
+                .get()
+                .saturating_sub(pending.len() as u32),
+            game_rules: self.game_rules.get().clone(),
+        })
+    }
+
+    /// Testable inner for `import_match`. Everything is validated before
+    /// any field is written, so a rejected snapshot leaves the slot as it
+    /// was. Only a player of the active, unfinished match may restore it,
+    /// and only from a snapshot of that same match, players and rules.
+    /// Snapshot fields a player could forge to their advantage aren't
+    /// taken on trust: a finished snapshot is refused, hit counts are
+    /// recounted from the shot maps, sunk marks must sit on hits, and a
+    /// player counts as placed only if their commitment is on record here.
+    pub(crate) fn restore(
+        &mut self,
+        caller: &PublicKey,
+        snapshot: MatchSnapshot,
+        force: bool,
+    ) -> Result<(), GameError> {
+        let (p1, p2) = self.authorize_player(caller)?;
+        self.check_match_id(&snapshot.match_id)?;
+        if snapshot.version != MATCH_SNAPSHOT_VERSION {
+            return Err(GameError::Invalid(format!(
+                "unsupported snapshot version {}",
+                snapshot.version
+            )));
+        }
+        if snapshot.player1 != p1.to_base58() || snapshot.player2 != p2.to_base58() {
+            return Err(GameError::Invalid(
+                "snapshot players don't match the active match".into(),
+            ));
+        }
+        if snapshot.game_rules != *self.game_rules.get() {
+            return Err(GameError::Invalid(
+                "snapshot rules don't match the active match".into(),
+            ));
+        }
+        if snapshot.winner.is_some() || snapshot.drawn {
+            return Err(GameError::Invalid(
+                "a finished match can't be restored".into(),
+            ));
+        }
+        let turn = match &snapshot.turn {
+            Some(key) => {
+                let pk = PublicKey::from_base58(key)?;
+                if pk != p1 && pk != p2 {
+                    return Err(GameError::Invalid(format!("{key} is not a player")));
+                }
+                Some(pk)
+            }
+            None => None,
+        };
+        let cells = self.board_cells();
+        let boards = [
+            &snapshot.shots_p1,
+            &snapshot.shots_p2,
+            &snapshot.sunk_p1,
+            &snapshot.sunk_p2,
+        ];
+        if boards.iter().any(|b| b.len() != cells) {
+            return Err(board_size_mismatch());
+        }
+        let mut hits = [0u32; 2];
+        for (i, (shots, sunk)) in [
+            (&snapshot.shots_p1, &snapshot.sunk_p1),
+            (&snapshot.shots_p2, &snapshot.sunk_p2),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            for (&shot, &mark) in shots.iter().zip(sunk.iter()) {
+                let cell = Cell::from_u8(shot);
+                if !cell.is_empty() && !cell.is_resolved_shot() {
+                    return Err(GameError::Invalid(
+                        "snapshot shot map holds unresolved cells".into(),
+                    ));
+                }
+                if mark != 0 && cell != Cell::Hit {
+                    return Err(GameError::Invalid(
+                        "snapshot marks a sunk ship where no hit landed".into(),
+                    ));
+                }
+                hits[i] += u32::from(cell == Cell::Hit);
+            }
+        }
+        let placed_p1 = snapshot.placed_p1 && self.commitment_of(&p1)?.is_some();
+        let placed_p2 = snapshot.placed_p2 && self.commitment_of(&p2)?.is_some();
+        if !force {
+            return Err(GameError::Invalid(
+                "an unfinished match is active; pass force to replace it".into(),
+            ));
+        }
+
+        write_cell_map(&mut self.shots_p1, &snapshot.shots_p1)?;
+        write_cell_map(&mut self.shots_p2, &snapshot.shots_p2)?;
+        write_cell_map(&mut self.sunk_p1, &snapshot.sunk_p1)?;
+        write_cell_map(&mut self.sunk_p2, &snapshot.sunk_p2)?;
+        self.turn.set(turn);
+        self.placed_p1.set(placed_p1);
+        self.placed_p2.set(placed_p2);
+        self.pending.set(Vec::new());
+        self.pending_since_ms.set(None);
+        self.paused_since_ms.set(None);
+        self.last_nonce_p1.set(None);
+        self.last_nonce_p2.set(None);
+        self.last_shot.set(None);
+        self.shot_log.set(Vec::new());
+        self.hits_p1.set(hits[0]);
+        self.hits_p2.set(hits[1]);
+        // Snapshots don't carry shot order, so streaks start over.
+        self.streak_p1.set(HitStreak::default());
+        self.streak_p2.set(HitStreak::default());
+        self.shots_this_turn.set(snapshot.shots_this_turn);
+        self.turn_history.set(Vec::new());
+        Ok(())
+    }
+
+    /// Testable inner for `cancel_shot`: clears the newest pending shot and its
+    /// Pending marker in the shooter's map, returning the withdrawn shot.
+    pub(crate) fn cancel_pending_for(
+        &mut self,
+        caller: &PublicKey,
+    ) -> Result<PendingShot, GameError> {
+        self.authorize_player(caller)?;
+        let mut queue = self.pending.get().clone();
+        let pending = queue
+            .pop()
+            .ok_or_else(|| GameError::Invalid("no pending shot".into()))?;
+        if pending.shooter != *caller {
+            return Err(GameError::Forbidden("not the shooter".into()));
+        }
+        let size = self.board_size();
+        let shooter_map = if self.player1.get().as_ref() == Some(caller) {
+            &mut self.shots_p1
+        } else {
+            &mut self.shots_p2
+        };
+        shooter_map
+            .remove(&cell_key(size, pending.x, pending.y))
+            .map_err(|e| GameError::Invalid(format!("shots.remove: {e}")))?;
+        if queue.is_empty() {
+            self.pending_since_ms.set(None);
+        }
+        self.pending.set(queue);
+        let fired = self.shots_this_turn.get().saturating_sub(1);
+        self.shots_this_turn.set(fired);
+        Ok(pending)
+    }
+
+    /// Makes `shot` the last shot and appends it to the move list.
+    fn record_shot(&mut self, shot: ShotRecord) {
+        let mut log = self.shot_log.get().clone();
+        log.push(shot.clone());
+        self.shot_log.set(log);
+        self.last_shot.set(Some(shot));
+    }
+
+    /// Inner for `get_shots_since`.
+    pub(crate) fn shots_since(&self, seq: u64) -> ShotsSince {
+        let log = self.shot_log.get();
+        let deltas = log
+            .iter()
+            .zip(1u64..)
+            .skip_while(|&(_, n)| n <= seq)
+            .map(|(s, n)| CellDelta {
+                seq: n,
+                shooter: s.shooter.clone(),
+                x: s.x,
+                y: s.y,
+                result: s.result.clone(),
+            })
+            .collect();
+        ShotsSince {
+            seq: log.len() as u64,
+            deltas,
+        }
+    }
+
+    /// The move list in notation form: each logged shot with its shooter
+    /// as a side rather than a key.
+    pub(crate) fn notation_moves(&self) -> Vec<NotationMove> {
+        let p1 = self.player1.get().as_ref().map(|pk| pk.to_base58());
+        self.shot_log
+            .get()
+            .iter()
+            .map(|s| NotationMove {
+                side: if Some(&s.shooter) == p1.as_ref() {
+                    1
+                } else {
+                    2
+                },
+                x: s.x,
+                y: s.y,
+                result: s.result.clone(),
+            })
+            .collect()
+    }
+
+    /// Discards whatever is left of the pending batch, clearing each shot's
+    /// Pending marker. Used once a match ends mid-batch.
+    fn drop_pending(&mut self) -> Result<(), GameError> {
+        let p1 = self.player1.get().clone();
+        let size = self.board_size();
+        for shot in self.pending.get().clone() {
+            let shooter_map = if Some(&shot.shooter) == p1.as_ref() {
+                &mut self.shots_p1
+            } else {
+                &mut self.shots_p2
+            };
+            shooter_map
+                .remove(&cell_key(size, shot.x, shot.y))
+                .map_err(|e| GameError::Invalid(format!("shots.remove: {e}")))?;
+        }
+        self.pending.set(Vec::new());
+        self.pending_since_ms.set(None);
+        Ok(())
+    }
+
+    /// Passes the turn to the other player once the shooter has used up
+    /// their `shots_per_turn` allowance; until then the turn stays put.
+    pub(crate) fn advance_turn(&mut self) -> app::Result<()> {
+        let p1 = self.player1_or_panic()?;
+        let p2 = self.player2_or_panic()?;
+        let (current, next) = if self.turn.get().as_ref() == Some(&p1) {
+            (p1, p2)
+        } else {
+            (p2, p1)
+        };
+        let current_can_fire = self.can_fire(&current)?;
+        let exhausted = self
+            .game_rules
+            .get()
+            .turn_exhausted(*self.shots_this_turn.get());
+        if !exhausted && current_can_fire {
+            return Ok(());
+        }
+        // A player with nothing left to fire is skipped, so the opponent
+        // fires the rest of theirs before the match is decided.
+        if current_can_fire && !self.can_fire(&next)? {
+            self.shots_this_turn.set(0);
+            return Ok(());
+        }
+        let next_b58 = next.to_base58();
+        self.turn.set(Some(next));
+        self.shots_this_turn.set(0);
+        let mut history = self.turn_history.get().clone();
+        history.push(TurnChange {
+            player: next_b58,
+            at_ms: storage_env::time_now(),
+        });
+        self.turn_history.set(history);
+        self.report_turn();
+        Ok(())
+    }
+
+    /// Tells the lobby whose turn it is: nobody while paused or once the
+    /// match is over. Each report carries this context's id, which the
+    /// lobby checks, and the time, so it keeps only the latest.
+    fn report_turn(&self) {
+        // Standalone matches have no lobby to report to.
+        let (Some(_), Some(match_id)) = (self.lobby_context_id.get(), self.match_id.get().clone())
+        else {
+            return;
+        };
+        let turn = if self.is_finished() || self.paused_since_ms.get().is_some() {
+            None
+        } else {
+            self.turn.get().as_ref().map(PublicKey::to_base58)
+        };
+        self.notify_lobby(
+            "on_turn_changed",
+            calimero_sdk::serde_json::json!({
+                "match_id": match_id,
+                "context_id": own_context_id(),
+                "turn": turn,
+                "at_ms": storage_env::time_now(),
+            }),
+        );
+    }
+
+    /// Fire-and-forget xcall of `method` on the lobby context this match
+    /// was created from. A no-op for standalone matches or an undecodable
+    /// lobby id: the lobby's view is a convenience, never a precondition.
+    fn notify_lobby(&self, method: &str, params: calimero_sdk::serde_json::Value) {
+        let Some(lobby_ctx) = self.lobby_context_id.get().as_ref() else {
+            return;
+        };
+        let Ok(lobby_bytes) = bs58::decode(lobby_ctx).into_vec() else {
+            return;
+        };
+        let Ok(ctx_arr) = <[u8; 32]>::try_from(lobby_bytes.as_slice()) else {
+            return;
+        };
+        if let Ok(payload) = calimero_sdk::serde_json::to_vec(&params) {
+            calimero_sdk::env::xcall(&ctx_arr, method, &payload);
+        }
+    }
+
+    /// Testable inner for `get_open_targets`. Hit, Miss and Pending cells
+    /// are all taken; everything else is open.
+    pub(crate) fn open_targets_for(
+        &self,
+        caller: &PublicKey,
+    ) -> Result<Vec<Coordinate>, GameError> {
+        let shots = if self.player1.get().as_ref() == Some(caller) {
+            &self.shots_p1
+        } else if self.player2.get().as_ref() == Some(caller) {
+            &self.shots_p2
+        } else {
+            return Err(GameError::Forbidden("not a player".into()));
+        };
+        let size = self.board_size();
+        let cells = read_cell_map(shots, size)?;
+        let mut open = Vec::new();
+        for y in 0..size {
+            for x in 0..size {
+                if Cell::from_u8(cells[board::Board::idx(size, x, y)]).is_empty() {
+                    open.push(Coordinate { x, y });
+                }
+            }
+        }
+        Ok(open)
+    }
+
+    /// Testable inner for `get_target_grid`.
+    pub(crate) fn target_grid_for(&self, caller: &PublicKey) -> Result<TargetGridView, GameError> {
+        let (shots, sunk) = if self.player1.get().as_ref() == Some(caller) {
+            (&self.shots_p1, &self.sunk_p1)
+        } else if self.player2.get().as_ref() == Some(caller) {
+            (&self.shots_p2, &self.sunk_p2)
+        } else {
+            return Err(GameError::Forbidden("not a player".into()));
+        };
+        let shots = read_cell_map(shots, self.board_size())?;
+        let sunk = self.revealed_sunk(sunk)?;
+        let cells = shots
+            .iter()
+            .zip(&sunk)
+            .map(|(&shot, &sunk_len)| match Cell::from_u8(shot) {
+                Cell::Hit if sunk_len > 0 => TargetCell::Sunk,
+                Cell::Hit => TargetCell::Hit,
+                Cell::Miss => TargetCell::Miss,
+                _ => TargetCell::Unknown,
+            })
+            .collect();
+        Ok(TargetGridView {
+            size: self.board_size(),
+            cells,
+        })
+    }
+
+    /// Inner for `validate_fleet_detailed`. Cross-ship checks only look at
+    /// ships that are valid on their own.
+    pub(crate) fn fleet_validation(&self, ships: &[String]) -> FleetValidation {
+        let size = self.rules.get().board_size;
+        let wrap = self.game_rules.get().wrap_edges;
+        let mut sound = Vec::with_capacity(ships.len());
+        let ships = ships
+            .iter()
+            .enumerate()
+            .map(|(i, group)| {
+                let (violations, coords) = ShipValidator::ship_violations(group, size, wrap);
+                sound.push(if violations.is_empty() {
+                    coords
+                } else {
+                    Vec::new()
+                });
+                ShipValidation {
+                    index: i as u32,
+                    valid: violations.is_empty(),
+                    violations: violations.into_iter().map(String::from).collect(),
+                }
+            })
+            .collect();
+        FleetValidation {
+            ships,
+            conflicts: ShipValidator::fleet_conflicts(&sound, size, wrap),
+        }
+    }
+
+    /// Testable inner for `get_hit_density`.
+    pub(crate) fn hit_density_for(&self, caller: &PublicKey) -> Result<Vec<f32>, GameError> {
+        let (shots, sunk) = if self.player1.get().as_ref() == Some(caller) {
+            (&self.shots_p1, &self.sunk_p1)
+        } else if self.player2.get().as_ref() == Some(caller) {
+            (&self.shots_p2, &self.sunk_p2)
+        } else {
+            return Err(GameError::Forbidden("not a player".into()));
+        };
+        let shots = read_cell_map(shots, self.board_size())?;
+        let sunk = self.revealed_sunk(sunk)?;
+        // Each sunk ship marks all of its cells with its length.
+        let fleet = self.rules.get().fleet.clone();
+        let mut lengths = fleet.clone();
+        lengths.sort_unstable();
+        lengths.dedup();
+        let mut afloat = fleet;
+        for len in lengths.into_iter().filter(|&len| len > 0) {
+            let sunk_of_len = sunk.iter().filter(|&&s| s == len).count() / usize::from(len);
+            for _ in 0..sunk_of_len {
+                if let Some(i) = afloat.iter().position(|&l| l == len) {
+                    afloat.remove(i);
+                }
+            }
+        }
+        Ok(density::hit_density(
+            &shots,
+            &sunk,
+            self.board_size(),
+            &afloat,
+            self.game_rules.get().wrap_edges,
+        ))
+    }
+
+    /// Testable inner for `get_shot_counts`.
+    pub(crate) fn shot_counts_for(&self, caller: &PublicKey) -> Result<ShotCountsView, GameError> {
+        if !self.is_player(caller) {
+            return Err(GameError::Forbidden("not a player".into()));
+        }
+        let resolved = |map| -> Result<u32, GameError> {
+            Ok(read_cell_map(map, self.board_size())?
+                .into_iter()
+                .filter(|&c| Cell::from_u8(c).is_resolved_shot())
+                .count() as u32)
+        };
+        Ok(ShotCountsView {
+            player1: resolved(&self.shots_p1)?,
+            player2: resolved(&self.shots_p2)?,
+        })
+    }
+
+    /// Inner for `get_total_ships_remaining`.
+    pub(crate) fn ships_remaining(&self) -> ShipsRemainingView {
+        let fleet = self.rules.get().total_ship_cells() as u64;
+        ShipsRemainingView {
+            player1: fleet.saturating_sub(u64::from(*self.hits_p2.get())),
+            player2: fleet.saturating_sub(u64::from(*self.hits_p1.get())),
+        }
+    }
+
+    /// Inner for `get_hits_to_win`.
+    pub(crate) fn hits_to_win(&self) -> HitsToWinView {
+        let rules = self.game_rules.get();
+        HitsToWinView {
+            player1: rules.hits_needed(*self.hits_p1.get()),
+            player2: rules.hits_needed(*self.hits_p2.get()),
+        }
+    }
+
+    /// True when `propose_shot(x, y, nonce)` from `caller` resends a shot
+    /// that already landed: one still pending from them with that nonce,
+    /// or, once the match is over, the shot that ended it.
+    pub(crate) fn is_retry(&self, caller: &PublicKey, x: u8, y: u8, nonce: Option<u64>) -> bool {
+        if nonce.is_none() {
+            return false;
+        }
+        let pending = self
+            .pending
+            .get()
+            .iter()
+            .any(|p| p.shooter == *caller && p.nonce == nonce && (p.x, p.y) == (x, y));
+        if pending {
+            return true;
+        }
+        let last_nonce = if self.player1.get().as_ref() == Some(caller) {
+            self.last_nonce_p1.get()
+        } else if self.player2.get().as_ref() == Some(caller) {
+            self.last_nonce_p2.get()
+        } else {
+            return false;
+        };
+        self.is_finished()
+            && *last_nonce == nonce
+            && self.last_shot.get().as_ref().is_some_and(|shot| {
+                shot.shooter == caller.to_base58() && (shot.x, shot.y) == (x, y)
+            })
+    }
+
+    /// Testable inner for `get_winner`. An unknown `match_id` is an error,
+    /// never `Ok(None)`, so "in progress" can't be confused with "no such match".
+    pub(crate) fn winner_of(&self, match_id: &str) -> Result<Option<String>, GameError> {
+        self.check_match_id(match_id)?;
+        Ok(self.winner.get().as_ref().map(|pk| pk.to_base58()))
+    }
+
+    /// Inner for `get_my_side`.
+    pub(crate) fn side_of(&self, pk: &PublicKey) -> Result<u8, GameError> {
+        if self.player1.get().as_ref() == Some(pk) {
+            Ok(1)
+        } else if self.player2.get().as_ref() == Some(pk) {
+            Ok(2)
+        } else {
+            Err(GameError::Forbidden("not a player".into()))
+        }
+    }
+
+    /// Testable inner for `is_participant`.
+    pub(crate) fn is_participant_key(&self, key: &str) -> Result<bool, GameError> {
+        let pk = PublicKey::from_base58(key)?;
+        Ok(self.is_player(&pk))
+    }
+
+    /// Debug check that the shot maps agree with `pending`; see
+    /// `pending_violations`.
+    #[cfg(test)]
+    pub(crate) fn assert_invariants(&self) {
+        let violations = self.pending_violations();
+        assert!(violations.is_empty(), "{}", violations.join("; "));
+    }
+
+    /// Inner for `audit_match`.
+    pub(crate) fn invariant_violations(&self) -> Vec<String> {
+        let mut found = self.pending_violations();
+        let cells = self.board_cells();
+        let maps = [
+            ("shots_p1", &self.shots_p1),
+            ("shots_p2", &self.shots_p2),
+            ("sunk_p1", &self.sunk_p1),
+            ("sunk_p2", &self.sunk_p2),
+        ];
+        for (name, map) in maps {
+            match map.entries() {
+                Ok(mut keys) => {
+                    if keys.any(|(key, _)| key[0] as usize >= cells) {
+                        found.push(format!("{name} has cells beyond the {cells}-cell board"));
+                    }
+                }
+                Err(e) => found.push(format!("{name} unreadable: {e}")),
+            }
+        }
+
+        if !self.is_finished() {
+            match self.turn.get() {
+                Some(pk) if self.is_player(pk) => {}
+                Some(pk) => found.push(format!("turn holder {} is not a player", pk.to_base58())),
+                None => found.push("unfinished match has no turn holder".into()),
+            }
+        }
+
+        let board = PrivateBoards::private_load_or_default()
+            .map_err(|e| e.to_string())
+            .and_then(|boards| {
+                boards
+                    .boards
+                    .get(&PrivateBoards::key(
+                        self.match_id.get().as_deref().unwrap_or(""),
+                    ))
+                    .map_err(|e| e.to_string())
+            });
+        match board {
+            Ok(Some(pb)) => {
+                if pb.check_integrity(self.rules.get().board_size).is_err() {
+                    found.push("private board does not match the board size".into());
+                } else if PlayerBoard::check_ship_count(pb.get_board(), pb.get_ship_count())
+                    .is_err()
+                {
+                    found.push(format!(
+                        "private board ship counter {} disagrees with its Ship cells",
+                        pb.get_ship_count()
+                    ));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => found.push(format!("private boards unreadable: {e}")),
+        }
+        found
+    }
+
+    /// Whether the shot maps agree with `pending`: the shooter's map holds
+    /// exactly the pending cells as Pending and the target's map none, or
+    /// neither map holds any while nothing is pending.
+    fn pending_violations(&self) -> Vec<String> {
+        let pending_cells = |map| -> Result<Vec<usize>, GameError> {
+            Ok(read_cell_map(map, self.board_size())?
+                .into_iter()
+                .enumerate()
+                .filter(|&(_, c)| Cell::from_u8(c).is_pending())
+                .map(|(i, _)| i)
+                .collect())
+        };
+        let (p1_pending, p2_pending) =
+            match (pending_cells(&self.shots_p1), pending_cells(&self.shots_p2)) {
+                (Ok(p1), Ok(p2)) => (p1, p2),
+                (Err(e), _) | (_, Err(e)) => return vec![e.to_string()],
+            };
+        let pending = self.pending.get();
+        let Some(first) = pending.first() else {
+            if p1_pending.is_empty() && p2_pending.is_empty() {
+                return Vec::new();
+            }
+            return vec![format!(
+                "Pending cells without a pending shot: p1={p1_pending:?} p2={p2_pending:?}"
+            )];
+        };
+        let mut found = Vec::new();
+        if !pending.iter().all(|p| p.shooter == first.shooter) {
+            found.push("pending batch mixes shooters".to_string());
+        }
+        let mut expected: Vec<usize> = pending
+            .iter()
+            .map(|p| board::Board::idx(self.board_size(), p.x, p.y))
+            .collect();
+        expected.sort_unstable();
+        let (mine, theirs) = if self.player1.get().as_ref() == Some(&first.shooter) {
+            (p1_pending, p2_pending)
+        } else {
+            (p2_pending, p1_pending)
+        };
+        if mine != expected {
+            found.push(format!(
+                "shooter's map must hold exactly the pending cells: {mine:?} != {expected:?}"
+            ));
+        }
+        if !theirs.is_empty() {
+            found.push(format!("target's map holds Pending cells: {theirs:?}"));
+        }
+        found
+    }
+
+    /// The key `player`'s commitment is stored under: the key they held
+    /// when placing, if they have migrated since, else `player` itself.
+    fn commitment_owner(&self, player: &PublicKey) -> PublicKey {
+        let committed_as = if self.player1.get().as_ref() == Some(player) {
+            self.committed_as_p1.get()
+        } else if self.player2.get().as_ref() == Some(player) {
+            self.committed_as_p2.get()
+        } else {
+            &None
+        };
+        committed_as.clone().unwrap_or_else(|| player.clone())
+    }
+
+    /// `player`'s published board commitment, looked up under
+    /// `commitment_owner`.
+    fn commitment_of(&self, player: &PublicKey) -> Result<Option<[u8; 32]>, GameError> {
+        let owner = self.commitment_owner(player);
+        #[cfg(test)]
+        if let Some(c) = TEST_COMMITMENTS.with(|c| {
+            c.borrow()
+                .iter()
+                .rev()
+                .find(|(pk, _)| *pk == owner.0)
+                .map(|(_, c)| *c)
+        }) {
+            return Ok(Some(c));
+        }
+        Ok(self
+            .commitments
+            .get_for_user(&sdk_pk(&owner))
+            .map_err(|e| GameError::Invalid(format!("commitments.get_for_user: {e}")))?
+            .map(|c| *c.get()))
+    }
+
+    fn is_player(&self, pk: &PublicKey) -> bool {
+        self.player1.get().as_ref() == Some(pk) || self.player2.get().as_ref() == Some(pk)
+    }
+
+    fn player1_or_panic(&self) -> app::Result<PublicKey> {
+        self.player1
+            .get()
+            .clone()
+            .ok_or_else(|| AppError::from(GameError::Invalid("player1 unset".into())))
+    }
+
+    fn player2_or_panic(&self) -> app::Result<PublicKey> {
+        self.player2
+            .get()
+            .clone()
+            .ok_or_else(|| AppError::from(GameError::Invalid("player2 unset".into())))
+    }
+}
+
+/// Compute `SHA256(board_bytes || salt)` — exposed for tests and cross-module use.
+pub fn compute_commitment(board_bytes: &[u8], salt: &[u8; 16]) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(board_bytes);
+    h.update(salt);
+    h.finalize().into()
+}
+
+/// The commitment `pb` was placed under: `compute_commitment` over its
+/// pristine board and salt.
+fn board_commitment(pb: &PlayerBoard) -> Result<[u8; 32], GameError> {
+    let board_bytes = calimero_sdk::borsh::to_vec(&pb.pristine().to_vec())
+        .map_err(|e| GameError::Invalid(format!("serialize board: {e}")))?;
+    Ok(compute_commitment(&board_bytes, pb.salt()))
+}
+
+/// Flatten a cell-keyed map (`[y * 10 + x]` -> value) into a row-major
+/// board vector; cells without an entry read as 0.
+fn read_cell_map(
+    map: &UnorderedMap<[u8; 1], LwwRegister<u8>>,
+    size: u8,
+) -> Result<Vec<u8>, GameError> {
+    let mut cells = vec![0u8; usize::from(size) * usize::from(size)];
+    let entries = map
+        .entries()
+        .map_err(|e| GameError::Invalid(format!("shots.entries: {e}")))?;
+    for (key, reg) in entries {
+        let idx = key[0] as usize;
+        if idx < cells.len() {
+            cells[idx] = *reg.get();
+        }
+    }
+    Ok(cells)
+}
+
+/// Splits row-major `cells` into rows of `size`.
+fn to_grid(size: u8, cells: &[u8]) -> Vec<Vec<u8>> {
+    cells
+        .chunks(usize::from(size).max(1))
+        .map(<[u8]>::to_vec)
+        .collect()
+}
+
+/// Length of each ship in a `place_ships` list, in the order given. Groups
+/// `place_ships` skips (blank, or nothing parseable) are skipped here too.
+fn ship_lengths(ships: &[String], size: u8) -> Vec<u8> {
+    ships
+        .iter()
+        .filter_map(|group| ships::ShipValidator::parse_ship_coords(group, size).ok())
+        .filter(|coords| !coords.is_empty())
+        .map(|coords| coords.len() as u8)
+        .collect()
+}
+
+/// Key of cell `(x, y)` in the shot and sunk maps of a `size`-wide board.
+/// One byte is enough, as boards are at most `MAX_BOARD_SIZE` wide.
+fn cell_key(size: u8, x: u8, y: u8) -> [u8; 1] {
+    [y * size + x]
+}
+
+fn board_size_mismatch() -> GameError {
+    GameError::Invalid("board size mismatch".into())
+}
+
+/// Replaces the contents of a cell map with the non-empty cells of a
+/// row-major board; inverse of `read_cell_map`.
+fn write_cell_map(
+    map: &mut UnorderedMap<[u8; 1], LwwRegister<u8>>,
+    cells: &[u8],
+) -> Result<(), GameError> {
+    map.clear()
+        .map_err(|e| GameError::Invalid(format!("shots.clear: {e}")))?;
+    for (idx, &cell) in cells.iter().enumerate() {
+        if cell != Cell::Empty.to_u8() {
+            map.insert([idx as u8], LwwRegister::new(cell))
+                .map_err(|e| GameError::Invalid(format!("shots.insert: {e}")))?;
+        }
+    }
+    Ok(())
+}
+
+fn hex_encode(bytes: &[u8; 32]) -> String {
+    let mut s = String::with_capacity(64);
+    for b in bytes {
+        s.push_str(&format!("{:02x}", b));
+    }
+    s
+}
+
+fn abi_descriptor_json() -> Result<String, GameError> {
+    let manifest: calimero_sdk::serde_json::Value =
+        calimero_sdk::serde_json::from_str(ABI_MANIFEST)
+            .map_err(|e| GameError::Invalid(format!("abi manifest: {e}")))?;
+    let descriptor = calimero_sdk::serde_json::json!({
+        "abi_version": ABI_VERSION,
+        "manifest": manifest,
+    });
+    Ok(descriptor.to_string())
+}
+
+/// Helper used by the audit routine (Task 9) and commitment bootstrapping (Task 7).
+pub fn is_ship_cell(value: u8) -> bool {
+    Cell::from_u8(value).is_ship()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BOARD_SIZE;
+    use crate::rules::{WinCondition, DEFAULT_SHOT_TIMEOUT_MS};
+
+    #[test]
+    fn abi_descriptor_carries_the_version_and_method_list() {
+        let json = abi_descriptor_json().unwrap();
+        let parsed: calimero_sdk::serde_json::Value =
+            calimero_sdk::serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["abi_version"], ABI_VERSION);
+        let methods = parsed["manifest"]["methods"].as_array().unwrap();
+        assert!(methods.iter().any(|m| m["name"] == "propose_shot"));
+        assert!(parsed["manifest"]["types"]["OwnBoardView"].is_object());
+    }
+
+    #[test]
+    fn is_ship_cell_identifies_ship_sentinel() {
+        assert!(is_ship_cell(Cell::Ship.to_u8()));
+        assert!(!is_ship_cell(Cell::Empty.to_u8()));
+        assert!(!is_ship_cell(Cell::Hit.to_u8()));
+        assert!(!is_ship_cell(Cell::Miss.to_u8()));
+        assert!(!is_ship_cell(Cell::Pending.to_u8()));
+    }
+
+    #[test]
+    fn game_state_skeleton_fields_are_empty() {
+        // Empty player keys + empty match_id → init does not populate
+        // identity fields; everything stays at the default.
+        let state = GameState::init(
+            "".into(),
+            "".into(),
+            None,
+            "".into(),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(state.lobby_context_id.get().is_none());
+        assert!(state.match_id.get().is_none());
+        assert!(state.player1.get().is_none());
+        assert!(state.winner.get().is_none());
+        assert!(!(*state.placed_p1.get()));
+        assert!(!(*state.placed_p2.get()));
+        assert!(state.pending.get().is_empty());
+    }
+
+    #[test]
+    fn compute_commitment_matches_manual_sha256() {
+        let board_bytes = calimero_sdk::borsh::to_vec(&vec![1u8, 0, 0, 1u8]).unwrap();
+        let salt = [9u8; 16];
+        let mut h = Sha256::new();
+        h.update(&board_bytes);
+        h.update(salt);
+        let expected: [u8; 32] = h.finalize().into();
+        assert_eq!(compute_commitment(&board_bytes, &salt), expected);
+    }
+
+    #[test]
+    fn hex_encode_produces_64_char_lowercase() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0xAB;
+        bytes[31] = 0xCD;
+        let s = hex_encode(&bytes);
+        assert_eq!(s.len(), 64);
+        assert!(s.starts_with("ab"));
+        assert!(s.ends_with("cd"));
+        assert!(s
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+    }
+
+    #[test]
+    fn init_stores_lobby_match_id_verbatim() {
+        let pk1 = PublicKey([1u8; 32]).to_base58();
+        let pk2 = PublicKey([2u8; 32]).to_base58();
+        let lobby_match_id = format!("{pk1}-1700000000000-deadbeef");
+        let state = GameState::init(
+            pk1.clone(),
+            pk2.clone(),
+            Some("lobby".into()),
+            lobby_match_id.clone(),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(state.turn.get().as_ref().unwrap().to_base58(), pk1);
+        assert_eq!(
+            state.match_id.get().as_deref(),
+            Some(lobby_match_id.as_str())
+        );
+        assert_eq!(state.lobby_context_id.get().as_deref(), Some("lobby"));
+
+        let second = GameState::init(
+            pk1.clone(),
+            pk2.clone(),
+            None,
+            lobby_match_id.clone(),
+            None,
+            None,
+            Some(pk2.clone()),
+            None,
+        );
+        assert_eq!(second.turn.get().as_ref().unwrap().to_base58(), pk2);
+        let outsider = PublicKey([3u8; 32]).to_base58();
+        let bad = GameState::init(
+            pk1.clone(),
+            pk2.clone(),
+            None,
+            lobby_match_id.clone(),
+            None,
+            None,
+            Some(outsider),
+            None,
+        );
+        assert!(bad.match_id.get().is_none());
+        let instant_win = GameRules {
+            win_condition: WinCondition::FirstToHits(0),
+            ..GameRules::default()
+        };
+        let bad = GameState::init(
+            pk1,
+            pk2,
+            None,
+            lobby_match_id,
+            Some(instant_win),
+            None,
+            None,
+            None,
+        );
+        assert!(bad.match_id.get().is_none());
+    }
+
+    #[test]
+    fn placement_rules_from_init_size_every_board_and_view() {
+        let p1 = PublicKey([1u8; 32]);
+        let p2 = PublicKey([2u8; 32]);
+        let small = PlacementRules {
+            board_size: 8,
+            ..PlacementRules::default()
+        };
+        let mut state = GameState::init(
+            p1.to_base58(),
+            p2.to_base58(),
+            None,
+            "m-1".into(),
+            None,
+            None,
+            None,
+            Some(small),
+        );
+        assert_eq!(state.board_size(), 8);
+        // A fleet hugging the bottom-right corner of the 8x8 board.
+        let fleet = [
+            "3,1;4,1;5,1;6,1;7,1",
+            "4,3;5,3;6,3;7,3",
+            "5,5;6,5;7,5",
+            "5,7;6,7;7,7",
+            "0,7;1,7",
+        ];
+        let ships: Vec<String> = fleet.iter().map(|s| s.to_string()).collect();
+        assert!(state
+            .fleet_validation(&ships)
+            .ships
+            .iter()
+            .all(|ship| ship.valid));
+        let mut pb = PlayerBoard::with_size(8);
+        pb.place_ships(ships).unwrap();
+        pb.capture_pristine();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+
+        assert!(matches!(
+            state.validate_shot(&p1, 8, 0),
+            Err(GameError::Invalid(_))
+        ));
+        set_test_executor(Some(&p1));
+        state.propose_shot("m-1", 7, 7, None).unwrap();
+        set_test_executor(Some(&p2));
+        install_defender_board(&p2, pb);
+        assert_eq!(state.acknowledge_shot("m-1").unwrap(), "hit");
+        set_test_executor(None);
+
+        let grid = state.target_grid_for(&p1).unwrap();
+        assert_eq!((grid.size, grid.cells.len()), (8, 64));
+        assert_eq!(grid.cells[63], TargetCell::Hit);
+        assert_eq!(state.open_targets_for(&p1).unwrap().len(), 63);
+        assert_eq!(state.hit_density_for(&p1).unwrap().len(), 64);
+        assert_eq!(state.export_notation("m-1").unwrap(), "1. P1 H8 hit");
+
+        let too_big = PlacementRules {
+            board_size: rules::MAX_BOARD_SIZE + 1,
+            ..PlacementRules::default()
+        };
+        let bad = GameState::init(
+            p1.to_base58(),
+            p2.to_base58(),
+            None,
+            "m-1".into(),
+            None,
+            None,
+            None,
+            Some(too_big),
+        );
+        assert!(bad.match_id.get().is_none());
+    }
+
+    fn two_player_state() -> (GameState, PublicKey, PublicKey) {
+        let p1 = PublicKey([1u8; 32]);
+        let p2 = PublicKey([2u8; 32]);
+        let state = GameState::init(
+            p1.to_base58(),
+            p2.to_base58(),
+            None,
+            "m-1".into(),
+            None,
+            None,
+            None,
+            None,
+        );
+        (state, p1, p2)
+    }
+
+    #[test]
+    fn validate_shot_rejects_non_player_before_turn_check() {
+        let (mut state, _, _) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        let outsider = PublicKey([9u8; 32]);
+        let err = state.validate_shot(&outsider, 0, 0).unwrap_err();
+        assert_eq!(err, GameError::Forbidden("not a player".into()));
+
+        // Still "not a player" while nothing is placed yet.
+        let (state, _, _) = two_player_state();
+        let err = state.validate_shot(&outsider, 0, 0).unwrap_err();
+        assert_eq!(err, GameError::Forbidden("not a player".into()));
+    }
+
+    #[test]
+    fn validate_shot_rejects_player_out_of_turn() {
+        let (mut state, p1, p2) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        let err = state.validate_shot(&p2, 0, 0).unwrap_err();
+        assert_eq!(err, GameError::Forbidden("not your turn".into()));
+        assert_eq!(state.validate_shot(&p1, 0, 0).unwrap(), p2);
+    }
+
+    #[test]
+    fn validate_shot_names_the_side_that_has_not_placed() {
+        let (mut state, p1, p2) = two_player_state();
+        let err = state.validate_shot(&p1, 0, 0).unwrap_err();
+        assert_eq!(err, GameError::Invalid("place your ships first".into()));
+
+        state.placed_p1.set(true);
+        let err = state.validate_shot(&p1, 0, 0).unwrap_err();
+        assert!(
+            matches!(err, GameError::Invalid(ref m) if m == "opponent has not placed ships yet")
+        );
+        let err = state.validate_shot(&p2, 0, 0).unwrap_err();
+        assert_eq!(err, GameError::Invalid("place your ships first".into()));
+
+        state.placed_p1.set(false);
+        state.placed_p2.set(true);
+        let err = state.validate_shot(&p1, 0, 0).unwrap_err();
+        assert_eq!(err, GameError::Invalid("place your ships first".into()));
+        let err = state.validate_shot(&p2, 0, 0).unwrap_err();
+        assert!(
+            matches!(err, GameError::Invalid(ref m) if m == "opponent has not placed ships yet")
+        );
+    }
+
+    #[test]
+    fn validate_shot_refuses_to_target_the_shooter() {
+        let (mut state, p1, _) = two_player_state();
+        state.player2.set(Some(p1.clone()));
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        let err = state.validate_shot(&p1, 0, 0).unwrap_err();
+        assert_eq!(err, GameError::Invalid("corrupt match: self-target".into()));
+    }
+
+    #[test]
+    fn validate_shot_tells_the_defender_it_is_not_their_turn() {
+        let (mut state, p1, p2) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        state.pending.set(vec![PendingShot {
+            x: 0,
+            y: 0,
+            shooter: p1.clone(),
+            target: p2.clone(),
+            nonce: None,
+        }]);
+        state.shots_this_turn.set(1);
+        let err = state.validate_shot(&p2, 1, 1).unwrap_err();
+        assert_eq!(err, GameError::Forbidden("not your turn".into()));
+        let err = state.validate_shot(&p1, 1, 1).unwrap_err();
+        assert_eq!(err, GameError::Invalid("a shot is already pending".into()));
+    }
+
+    #[test]
+    fn salvo_rules_queue_pending_shots_up_to_the_allowance() {
+        let (mut state, p1, p2) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        state.game_rules.set(GameRules {
+            shots_per_turn: 2,
+            ..GameRules::default()
+        });
+        let shot = |x, y| PendingShot {
+            x,
+            y,
+            shooter: p1.clone(),
+            target: p2.clone(),
+            nonce: None,
+        };
+        state.pending.set(vec![shot(0, 0)]);
+        state.shots_this_turn.set(1);
+        assert_eq!(state.validate_shot(&p1, 1, 1).unwrap(), p2);
+
+        state.pending.set(vec![shot(0, 0), shot(1, 1)]);
+        state.shots_this_turn.set(2);
+        let err = state.validate_shot(&p1, 2, 2).unwrap_err();
+        assert_eq!(err, GameError::Invalid("a shot is already pending".into()));
+
+        let listed: Vec<_> = state
+            .pending_shots_for(&p2)
+            .unwrap()
+            .into_iter()
+            .map(|v| (v.x, v.y))
+            .collect();
+        assert_eq!(listed, vec![(0, 0), (1, 1)]);
+        assert_eq!(state.pending_shot_for(&p2).unwrap().map(|v| v.x), Some(0));
+        assert!(matches!(
+            state.pending_shots_for(&PublicKey([9u8; 32])),
+            Err(GameError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn is_retry_matches_the_pending_shot_or_the_one_that_ended_the_match() {
+        let (mut state, p1, p2) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        set_test_executor(Some(&p1));
+        assert!(!state.is_retry(&p1, 0, 0, Some(7)));
+        state.propose_shot("m-1", 0, 0, Some(7)).unwrap();
+        assert!(state.is_retry(&p1, 0, 0, Some(7)));
+        state.propose_shot("m-1", 0, 0, Some(7)).unwrap();
+        assert_eq!(state.pending.get().len(), 1);
+        assert!(!state.is_retry(&p1, 0, 0, Some(8)));
+        assert!(!state.is_retry(&p1, 0, 0, None));
+        // The same nonce for another cell is a new shot, refused here as
+        // one is already pending rather than silently dropped.
+        assert!(!state.is_retry(&p1, 1, 0, Some(7)));
+        assert!(state.propose_shot("m-1", 1, 0, Some(7)).is_err());
+        // Nonces are tracked per shooter.
+        assert!(!state.is_retry(&p2, 0, 0, Some(7)));
+        assert!(!state.is_retry(&PublicKey([9u8; 32]), 0, 0, Some(7)));
+
+        // Once resolved, a reused nonce goes through normal validation.
+        set_test_executor(Some(&p2));
+        install_defender_board(&p2, placed_board(&CLASSIC_TEST_FLEET));
+        state.acknowledge_shot("m-1").unwrap();
+        set_test_executor(Some(&p1));
+        assert!(!state.is_retry(&p1, 0, 0, Some(7)));
+
+        // A resend of the winning shot stays idempotent once the match ends.
+        state.winner.set(Some(p1.clone()));
+        assert!(state.propose_shot("m-1", 0, 0, Some(7)).is_ok());
+        assert!(state.propose_shot("m-1", 0, 0, Some(8)).is_err());
+        assert!(state.propose_shot("m-1", 5, 5, Some(7)).is_err());
+        set_test_executor(None);
+    }
+
+    fn mark(map: &mut UnorderedMap<[u8; 1], LwwRegister<u8>>, idx: u8, v: u8) {
+        map.insert([idx], LwwRegister::new(v)).unwrap();
+    }
+
+    /// Turns `reveal_on_sink` on, which the sunk-ship overlays need.
+    fn reveal_sunk_ships(state: &mut GameState) {
+        state.game_rules.set(GameRules {
+            reveal_on_sink: true,
+            ..GameRules::default()
+        });
+    }
+
+    #[test]
+    fn target_grid_overlays_sunk_ships_and_hides_the_rest() {
+        let (mut state, p1, _) = two_player_state();
+        reveal_sunk_ships(&mut state);
+        // p1 sank a 2-ship at (0,0)-(1,0), hit (5,5), missed (9,9).
+        mark(&mut state.shots_p1, 0, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 1, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 55, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 99, Cell::Miss.to_u8());
+        mark(&mut state.shots_p1, 42, Cell::Pending.to_u8());
+        mark(&mut state.sunk_p1, 0, 2);
+        mark(&mut state.sunk_p1, 1, 2);
+
+        let view = state.target_grid_for(&p1).unwrap();
+        assert_eq!(view.cells.len(), 100);
+        assert_eq!(view.cells[0], TargetCell::Sunk);
+        assert_eq!(view.cells[1], TargetCell::Sunk);
+        assert_eq!(view.cells[55], TargetCell::Hit);
+        assert_eq!(view.cells[99], TargetCell::Miss);
+        assert_eq!(view.cells[42], TargetCell::Unknown);
+        assert_eq!(
+            view.cells
+                .iter()
+                .filter(|c| **c == TargetCell::Unknown)
+                .count(),
+            96
+        );
+
+        let err = state.target_grid_for(&PublicKey([9u8; 32])).unwrap_err();
+        assert!(matches!(err, GameError::Forbidden(_)));
+    }
+
+    #[test]
+    fn shot_counts_only_include_resolved_shots() {
+        let (mut state, p1, p2) = two_player_state();
+        mark(&mut state.shots_p1, 0, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 1, Cell::Miss.to_u8());
+        mark(&mut state.shots_p2, 7, Cell::Miss.to_u8());
+        mark(&mut state.shots_p2, 8, Cell::Pending.to_u8());
+
+        let counts = state.shot_counts_for(&p2).unwrap();
+        assert_eq!((counts.player1, counts.player2), (2, 1));
+        assert!(state.shot_counts_for(&p1).is_ok());
+        let err = state.shot_counts_for(&PublicKey([9u8; 32])).unwrap_err();
+        assert!(matches!(err, GameError::Forbidden(_)));
+    }
+
+    #[test]
+    fn cancel_pending_clears_the_shot_for_the_shooter_only() {
+        let (mut state, p1, p2) = two_player_state();
+        let err = state.cancel_pending_for(&p1).unwrap_err();
+        assert_eq!(err, GameError::Invalid("no pending shot".into()));
+
+        mark(&mut state.shots_p1, 34, Cell::Pending.to_u8());
+        state.pending.set(vec![PendingShot {
+            x: 4,
+            y: 3,
+            shooter: p1.clone(),
+            target: p2.clone(),
+            nonce: None,
+        }]);
+        let err = state.cancel_pending_for(&p2).unwrap_err();
+        assert_eq!(err, GameError::Forbidden("not the shooter".into()));
+
+        state.assert_invariants();
+        let cancelled = state.cancel_pending_for(&p1).unwrap();
+        state.assert_invariants();
+        assert_eq!((cancelled.x, cancelled.y), (4, 3));
+        assert!(state.pending.get().is_empty());
+        assert!(state.shots_p1.get(&[34]).unwrap().is_none());
+        assert_eq!(state.turn.get().as_ref(), Some(&p1));
+    }
+
+    #[test]
+    fn summary_tracks_phase_through_the_match() {
+        let (mut state, p1, p2) = two_player_state();
+        let summary = state.summary().unwrap();
+        assert_eq!(summary.id, "m-1");
+        assert_eq!(summary.player2, p2.to_base58());
+        assert_eq!(summary.turn, Some(p1.to_base58()));
+        assert_eq!(summary.phase, MatchPhase::Placement);
+        assert!(summary.winner.is_none() && summary.ended_ms.is_none());
+
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        assert_eq!(state.summary().unwrap().phase, MatchPhase::Playing);
+
+        state.winner.set(Some(p2.clone()));
+        state.ended_ms.set(Some(42));
+        let summary = state.summary().unwrap();
+        assert_eq!(summary.phase, MatchPhase::Finished);
+        assert_eq!(summary.winner, Some(p2.to_base58()));
+        assert_eq!(summary.ended_ms, Some(42));
+    }
+
+    #[test]
+    fn is_participant_key_decodes_and_matches_either_player() {
+        let (state, p1, p2) = two_player_state();
+        assert!(state.is_participant_key(&p1.to_base58()).unwrap());
+        assert!(state.is_participant_key(&p2.to_base58()).unwrap());
+        let outsider = PublicKey([9u8; 32]).to_base58();
+        assert!(!state.is_participant_key(&outsider).unwrap());
+        assert!(state.is_participant_key("not-base58!").is_err());
+    }
+
+    #[test]
+    fn passing_the_turn_is_logged() {
+        let (mut state, _, p2) = two_player_state();
+        state.advance_turn().unwrap();
+        assert!(state.turn_history.get().is_empty());
+
+        state.shots_this_turn.set(1);
+        state.advance_turn().unwrap();
+        state.shots_this_turn.set(1);
+        state.advance_turn().unwrap();
+        let history = state.get_turn_history("m-1").unwrap();
+        let players: Vec<&str> = history.iter().map(|c| c.player.as_str()).collect();
+        assert_eq!(
+            players,
+            [p2.to_base58().as_str(), &state.summary().unwrap().player1]
+        );
+        assert!(history[0].at_ms <= history[1].at_ms);
+    }
+
+    #[test]
+    fn side_of_numbers_the_players() {
+        let (state, p1, p2) = two_player_state();
+        assert_eq!(state.side_of(&p1).unwrap(), 1);
+        assert_eq!(state.side_of(&p2).unwrap(), 2);
+        assert!(matches!(
+            state.side_of(&PublicKey([9u8; 32])),
+            Err(GameError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn winner_of_separates_in_progress_from_unknown_match() {
+        let (mut state, _, p2) = two_player_state();
+        assert_eq!(state.winner_of("m-1").unwrap(), None);
+        let err = state.winner_of("m-2").unwrap_err();
+        assert!(matches!(err, GameError::NotFound(ref id) if id == "m-2"));
+
+        state.winner.set(Some(p2.clone()));
+        assert_eq!(state.winner_of("m-1").unwrap(), Some(p2.to_base58()));
+    }
+
+    #[test]
+    fn open_targets_skip_fired_and_pending_cells_in_row_major_order() {
+        let (mut state, p1, p2) = two_player_state();
+        mark(&mut state.shots_p1, 0, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 11, Cell::Miss.to_u8());
+        mark(&mut state.shots_p1, 12, Cell::Pending.to_u8());
+
+        let open = state.open_targets_for(&p1).unwrap();
+        assert_eq!(open.len(), 97);
+        assert_eq!(open[0], Coordinate { x: 1, y: 0 });
+        assert!(!open.contains(&Coordinate { x: 1, y: 1 }));
+        assert!(!open.contains(&Coordinate { x: 2, y: 1 }));
+        assert!(open.windows(2).all(|w| (w[0].y, w[0].x) < (w[1].y, w[1].x)));
+        assert_eq!(state.open_targets_for(&p2).unwrap().len(), 100);
+        assert!(state.open_targets_for(&PublicKey([9u8; 32])).is_err());
+    }
+
+    #[test]
+    fn decisive_shot_wins_even_when_both_fleets_are_nearly_sunk() {
+        let (mut state, p1, p2) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        // Both players are one cell from defeat; p1 fires first.
+        let shot = PendingShot {
+            x: 0,
+            y: 0,
+            shooter: p1.clone(),
+            target: p2.clone(),
+            nonce: None,
+        };
+        assert_eq!(state.decisive_winner(&shot, 16, 1), None);
+        assert_eq!(state.decisive_winner(&shot, 17, 0), Some(p1.clone()));
+
+        state.winner.set(state.decisive_winner(&shot, 17, 0));
+        // p2's would-be equalising shot is never resolved.
+        state.turn.set(Some(p2.clone()));
+        assert!(matches!(
+            state.validate_shot(&p2, 0, 0),
+            Err(GameError::Finished)
+        ));
+        assert_eq!(state.winner_of("m-1").unwrap(), Some(p1.to_base58()));
+    }
+
+    #[test]
+    fn recent_events_keep_a_bounded_window_oldest_first() {
+        let (mut state, _, _) = two_player_state();
+        assert!(state.recent_events_inner(10).unwrap().is_empty());
+        for x in 0..70u8 {
+            state
+                .record_event(&Event::ShotProposed { id: "m-1", x, y: 0 })
+                .unwrap();
+        }
+        assert_eq!(state.recent_events.len().unwrap(), 64);
+        let last_three = state.recent_events_inner(3).unwrap();
+        let xs: Vec<u8> = last_three
+            .iter()
+            .map(|e| match e {
+                EventRecord::ShotProposed { x, .. } => *x,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(xs, vec![67, 68, 69]);
+        let all = state.recent_events_inner(u32::MAX).unwrap();
+        assert_eq!(all.len(), 64);
+        assert_eq!(
+            all[0],
+            EventRecord::ShotProposed {
+                id: "m-1".into(),
+                x: 6,
+                y: 0
+            }
+        );
+    }
+
+    /// Private boards are node-local; a test runs every "node" on one thread,
+    /// so the acting defender's board is installed before they acknowledge.
+    fn install_private_board(pb: PlayerBoard) {
+        let mut priv_boards = PrivateBoards::private_load_or_default().unwrap();
+        let mut priv_mut = priv_boards.as_mut();
+        priv_mut
+            .boards
+            .insert(PrivateBoards::key("m-1"), pb)
+            .unwrap();
+    }
+
+    /// `install_private_board` on `defender`'s node, along with the
+    /// commitment they published when placing it.
+    fn install_defender_board(defender: &PublicKey, pb: PlayerBoard) {
+        set_test_commitment(defender, board_commitment(&pb).unwrap());
+        install_private_board(pb);
+    }
+
+    fn placed_board(ships: &[&str]) -> PlayerBoard {
+        let mut pb = PlayerBoard::new();
+        pb.place_ships(ships.iter().map(|s| s.to_string()).collect())
+            .unwrap();
+        pb.capture_pristine();
+        pb
+    }
+
+    #[test]
+    fn two_players_exchange_shots_through_the_public_api() {
+        let (mut state, p1, p2) = two_player_state();
+        let board_p1 = placed_board(&CLASSIC_TEST_FLEET);
+        let board_p2 = placed_board(&CLASSIC_TEST_FLEET);
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+
+        assert!(state.get_last_shot("m-1").unwrap().is_none());
+        set_test_executor(Some(&p1));
+        state.propose_shot("m-1", 0, 0, Some(1)).unwrap();
+        state.assert_invariants();
+        let pending = state.get_pending_shot("m-1").unwrap().unwrap();
+        assert_eq!((pending.x, pending.y), (0, 0));
+        assert_eq!(pending.shooter, p1.to_base58());
+        assert_eq!(pending.target, p2.to_base58());
+        assert!(pending.since_ms.is_some());
+        let outsider = PublicKey([9u8; 32]);
+        assert!(matches!(
+            state.pending_shot_for(&outsider),
+            Err(GameError::Forbidden(_))
+        ));
+        assert!(state.propose_shot("m-1", 1, 0, Some(2)).is_err());
+        state.assert_invariants();
+
+        set_test_executor(Some(&p2));
+        install_defender_board(&p2, board_p2);
+        assert_eq!(state.acknowledge_shot("m-1").unwrap(), "hit");
+        state.assert_invariants();
+        assert!(state.get_pending_shot("m-1").unwrap().is_none());
+        assert_eq!(state.get_current_turn().unwrap(), Some(p2.to_base58()));
+        state.propose_shot("m-1", 9, 9, None).unwrap();
+        state.assert_invariants();
+
+        set_test_executor(Some(&p1));
+        install_defender_board(&p1, board_p1);
+        assert_eq!(state.acknowledge_shot("m-1").unwrap(), "miss");
+        state.assert_invariants();
+        let last = state.get_last_shot("m-1").unwrap().unwrap();
+        assert_eq!(last.shooter, p2.to_base58());
+        assert_eq!((last.x, last.y, last.result.as_str()), (9, 9, "miss"));
+        assert_eq!(state.get_current_turn().unwrap(), Some(p1.to_base58()));
+        let streaks = state.get_hit_streaks("m-1").unwrap();
+        assert_eq!((streaks.player1.current, streaks.player1.longest), (1, 1));
+        assert_eq!(streaks.player2, HitStreak::default());
+
+        let grid = state.get_target_grid("m-1").unwrap();
+        assert_eq!(grid.cells[0], TargetCell::Hit);
+        let shots = state.get_shots_as("m-1", CellEncoding::HitMiss).unwrap();
+        assert_eq!(shots.shots[0], 1);
+        let counts = state.get_shot_counts("m-1").unwrap();
+        assert_eq!((counts.player1, counts.player2), (1, 1));
+        let recent = state.get_recent_events("m-1", 2).unwrap();
+        assert!(matches!(
+            recent[0],
+            EventRecord::ShotProposed { x: 9, y: 9, .. }
+        ));
+        assert!(matches!(
+            recent[1],
+            EventRecord::ShotFired { ref result, .. } if result == "miss"
+        ));
+        set_test_executor(None);
+    }
+
+    #[test]
+    fn hits_accumulate_toward_a_first_to_hits_win() {
+        let (mut state, p1, p2) = two_player_state();
+        state.game_rules.set(GameRules {
+            win_condition: WinCondition::FirstToHits(2),
+            ..GameRules::default()
+        });
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+
+        set_test_executor(Some(&p1));
+        state.propose_shot("m-1", 0, 0, None).unwrap();
+        set_test_executor(Some(&p2));
+        install_defender_board(&p2, placed_board(&CLASSIC_TEST_FLEET));
+        assert_eq!(state.acknowledge_shot("m-1").unwrap(), "hit");
+        set_test_executor(None);
+
+        assert_eq!((*state.hits_p1.get(), *state.hits_p2.get()), (1, 0));
+        let afloat = state.get_total_ships_remaining("m-1").unwrap();
+        assert_eq!((afloat.player1, afloat.player2), (17, 16));
+        let progress = state.get_hits_to_win("m-1").unwrap();
+        assert_eq!((progress.player1, progress.player2), (Some(1), Some(2)));
+        assert_eq!(state.get_winner("m-1").unwrap(), None);
+
+        // The next hit reaches the threshold with most of the fleet afloat.
+        let shot = PendingShot {
+            x: 1,
+            y: 0,
+            shooter: p1.clone(),
+            target: p2,
+            nonce: None,
+        };
+        assert_eq!(state.decisive_winner(&shot, 2, 15), Some(p1));
+        state.game_rules.set(GameRules::default());
+        assert_eq!(state.decisive_winner(&shot, 2, 15), None);
+    }
+
+    #[test]
+    fn turn_passes_only_after_the_shot_allowance_is_used() {
+        let (mut state, p1, p2) = two_player_state();
+        state.game_rules.set(GameRules {
+            shots_per_turn: 2,
+            ..GameRules::default()
+        });
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+
+        set_test_executor(Some(&p1));
+        state.propose_shot("m-1", 9, 9, None).unwrap();
+        state.cancel_shot("m-1").unwrap();
+        assert_eq!(*state.shots_this_turn.get(), 0);
+
+        for (x, turn_after) in [(9u8, &p1), (0u8, &p2)] {
+            set_test_executor(Some(&p1));
+            state.propose_shot("m-1", x, 0, None).unwrap();
+            set_test_executor(Some(&p2));
+            install_defender_board(&p2, placed_board(&CLASSIC_TEST_FLEET));
+            state.acknowledge_shot("m-1").unwrap();
+            state.assert_invariants();
+            assert_eq!(
+                state.get_current_turn().unwrap(),
+                Some(turn_after.to_base58())
+            );
+        }
+        assert_eq!(*state.shots_this_turn.get(), 0);
+        set_test_executor(None);
+    }
+
+    #[test]
+    fn match_snapshot_round_trips_and_guards_the_active_slot() {
+        let (mut state, p1, p2) = two_player_state();
+        reveal_sunk_ships(&mut state);
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        mark(&mut state.shots_p1, 0, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 1, Cell::Pending.to_u8());
+        mark(&mut state.sunk_p1, 0, 2);
+        state.hits_p1.set(1);
+        state.shots_this_turn.set(1);
+        state.pending.set(vec![PendingShot {
+            x: 1,
+            y: 0,
+            shooter: p1.clone(),
+            target: p2.clone(),
+            nonce: None,
+        }]);
+
+        let snap = state.snapshot().unwrap();
+        assert_eq!(snap.version, MATCH_SNAPSHOT_VERSION);
+        assert_eq!((snap.shots_p1[0], snap.shots_p1[1]), (Cell::Hit.to_u8(), 0));
+        assert_eq!(snap.shots_this_turn, 0);
+
+        let (mut other, _, _) = two_player_state();
+        reveal_sunk_ships(&mut other);
+        let err = other.restore(&p1, snap.clone(), false).unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("pass force")));
+        let outsider = PublicKey([9u8; 32]);
+        let err = other.restore(&outsider, snap.clone(), true).unwrap_err();
+        assert!(matches!(err, GameError::Forbidden(_)));
+        let elsewhere = MatchSnapshot {
+            match_id: "m-2".into(),
+            ..snap.clone()
+        };
+        let err = other.restore(&p1, elsewhere, true).unwrap_err();
+        assert_eq!(err, GameError::NotFound("m-2".into()));
+        let stranger = MatchSnapshot {
+            player2: outsider.to_base58(),
+            ..snap.clone()
+        };
+        assert!(other.restore(&p1, stranger, true).is_err());
+        let won = MatchSnapshot {
+            winner: Some(p1.to_base58()),
+            ..snap.clone()
+        };
+        assert!(other.restore(&p1, won, true).is_err());
+        let drawn = MatchSnapshot {
+            drawn: true,
+            ..snap.clone()
+        };
+        assert!(other.restore(&p1, drawn, true).is_err());
+        let quick = MatchSnapshot {
+            game_rules: GameRules {
+                win_condition: WinCondition::FirstToHits(1),
+                ..GameRules::default()
+            },
+            ..snap.clone()
+        };
+        assert!(other.restore(&p1, quick, true).is_err());
+        let mut phantom_sink = snap.clone();
+        phantom_sink.sunk_p1[50] = 2;
+        assert!(other.restore(&p1, phantom_sink, true).is_err());
+        let bad_version = MatchSnapshot {
+            version: MATCH_SNAPSHOT_VERSION + 1,
+            ..snap.clone()
+        };
+        assert!(other.restore(&p1, bad_version, true).is_err());
+        let short_board = MatchSnapshot {
+            shots_p2: vec![0; 99],
+            ..snap.clone()
+        };
+        let err = other.restore(&p1, short_board, true).unwrap_err();
+        assert_eq!(err, GameError::Invalid("board size mismatch".into()));
+        let bad_turn = MatchSnapshot {
+            turn: Some(outsider.to_base58()),
+            ..snap.clone()
+        };
+        assert!(other.restore(&p1, bad_turn, true).is_err());
+
+        // Only p1 has a commitment on record here; forged hits are recounted.
+        set_test_commitment(&p1, [7; 32]);
+        let forged = MatchSnapshot {
+            hits_p1: 17,
+            ..snap
+        };
+        other.restore(&p2, forged, true).unwrap();
+        other.assert_invariants();
+        assert!(*other.placed_p1.get());
+        assert!(!*other.placed_p2.get());
+        assert_eq!(*other.hits_p1.get(), 1);
+        assert_eq!(read_cell_map(&other.sunk_p1, BOARD_SIZE).unwrap()[0], 2);
+        assert_eq!(other.get_current_turn().unwrap(), Some(p1.to_base58()));
+        assert_eq!(other.shot_counts_for(&p1).unwrap().player1, 1);
+    }
+
+    #[test]
+    fn hit_streak_resets_on_a_miss_and_keeps_the_longest() {
+        let mut streak = HitStreak::default();
+        for hit in [true, true, false, true, true, true, false] {
+            streak.record(hit);
+        }
+        assert_eq!(
+            streak,
+            HitStreak {
+                current: 0,
+                longest: 3
+            }
         );
-        assert_eq!(state.lobby_context_id.get().as_deref(), Some("lobby"));
+        streak.record(true);
+        assert_eq!(
+            streak,
+            HitStreak {
+                current: 1,
+                longest: 3
+            }
+        );
+    }
+
+    #[test]
+    fn failed_private_board_write_leaves_no_partial_placement() {
+        let (mut state, p1, _) = two_player_state();
+        let before = PlayerBoard::with_size(BOARD_SIZE);
+        install_private_board(before.clone());
+        let placed = placed_board(&CLASSIC_TEST_FLEET);
+
+        set_fail_private_writes(true);
+        let result = state.persist_placement(&p1, "m-1", Some(before.clone()), placed, [7u8; 32]);
+        set_fail_private_writes(false);
+
+        assert!(matches!(result, Err(GameError::Invalid(ref m)) if m.contains("write failed")));
+        assert!(!*state.placed_p1.get() && !*state.placed_p2.get());
+        assert!(state
+            .commitments
+            .get_for_user(&sdk_pk(&p1))
+            .unwrap()
+            .is_none());
+        let stored = PrivateBoards::private_load_or_default()
+            .unwrap()
+            .boards
+            .get(&PrivateBoards::key("m-1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.get_board().0, before.get_board().0);
+        assert!(!stored.is_placed());
+    }
+
+    #[test]
+    fn reset_only_after_the_match_ended_and_only_by_a_player() {
+        let (mut state, p1, _) = two_player_state();
+        mark(&mut state.shots_p1, 3, Cell::Miss.to_u8());
+        install_private_board(PlayerBoard::new());
+        write_private_board("m-elsewhere", Some(PlayerBoard::new())).unwrap();
+        state.created_ms.set(1);
+        let err = state.reset_for(&p1).unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("unfinished")));
+
+        state.winner.set(Some(p1.clone()));
+        let outsider = PublicKey([9u8; 32]);
+        assert!(matches!(
+            state.reset_for(&outsider),
+            Err(GameError::Forbidden(_))
+        ));
+        state.reset_for(&p1).unwrap();
+        assert!(state.match_id.get().is_none() && state.winner.get().is_none());
+        assert!(*state.created_ms.get() > 1);
+        assert!(read_cell_map(&state.shots_p1, BOARD_SIZE)
+            .unwrap()
+            .iter()
+            .all(|&c| c == 0));
+        let boards = PrivateBoards::private_load_or_default().unwrap();
+        assert!(boards
+            .boards
+            .get(&PrivateBoards::key("m-1"))
+            .unwrap()
+            .is_none());
+        // Another match's board on this node is left alone.
+        assert!(boards
+            .boards
+            .get(&PrivateBoards::key("m-elsewhere"))
+            .unwrap()
+            .is_some());
+        drop(boards);
+        // With no match left, anyone may reset again.
+        state.reset_for(&outsider).unwrap();
+    }
+
+    #[test]
+    fn practice_match_is_won_by_sinking_the_ghost_fleet() {
+        let mut state = GameState::init(
+            String::new(),
+            String::new(),
+            None,
+            String::new(),
+            None,
+            None,
+            None,
+            None,
+        );
+        let p1 = PublicKey([1u8; 32]);
+        let match_id = state.start_practice(&p1, 7).unwrap();
+        assert_eq!(match_id, "practice-7");
+        assert_eq!(state.player2.get().as_ref(), Some(&GHOST_PLAYER));
+
+        let boards = PrivateBoards::private_load_or_default().unwrap();
+        let ghost = boards
+            .boards
+            .get(&PrivateBoards::ghost_key(&match_id))
+            .unwrap()
+            .unwrap();
+        // The player's own slot stays empty, so get_own_board can't leak it.
+        assert!(boards
+            .boards
+            .get(&PrivateBoards::key(&match_id))
+            .unwrap()
+            .is_none());
+        drop(boards);
+        let targets: Vec<(u8, u8)> = (0..BOARD_SIZE)
+            .flat_map(|y| (0..BOARD_SIZE).map(move |x| (x, y)))
+            .filter(|&(x, y)| ghost.get_board().get(BOARD_SIZE, x, y).is_ship())
+            .collect();
+        assert_eq!(targets.len(), 17);
+
+        set_test_executor(Some(&p1));
+        let err = state.propose_shot(&match_id, 0, 0, None).unwrap_err();
+        assert!(format!("{err:?}").contains("practice_fire"));
+        set_test_executor(None);
+
+        let (first_x, first_y) = targets[0];
+        assert_eq!(
+            state
+                .practice_fire_for(&p1, &match_id, first_x, first_y)
+                .unwrap(),
+            "hit"
+        );
+        assert!(state
+            .practice_fire_for(&p1, &match_id, first_x, first_y)
+            .is_err());
+        for &(x, y) in &targets[1..] {
+            assert_eq!(
+                state.practice_fire_for(&p1, &match_id, x, y).unwrap(),
+                "hit"
+            );
+        }
+        assert_eq!(state.winner.get().as_ref(), Some(&p1));
+        assert_eq!(state.streak_p1.get().longest, 17);
+        assert!(matches!(
+            state.practice_fire_for(&p1, &match_id, 0, 0),
+            Err(GameError::Finished)
+        ));
+        // A finished practice can be replaced by a fresh one.
+        assert_eq!(state.start_practice(&p1, 8).unwrap(), "practice-8");
+        assert!(!state.is_finished());
+    }
+
+    #[test]
+    fn revealed_fleet_must_agree_with_recorded_hits_and_misses() {
+        let (mut state, p1, p2) = two_player_state();
+        let ship = |cells: &[(u8, u8)]| {
+            Ship::new(
+                cells
+                    .iter()
+                    .map(|&(x, y)| Coordinate::new(x, y, BOARD_SIZE).unwrap())
+                    .collect(),
+                BOARD_SIZE,
+            )
+            .unwrap()
+        };
+        // p1 hit (0,0) and missed (5,5) on p2's board.
+        mark(&mut state.shots_p1, 0, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 55, Cell::Miss.to_u8());
+
+        let honest = [ship(&[(0, 0), (1, 0)])];
+        assert_eq!(state.hits_inconsistency_for(&p1, &honest).unwrap(), None);
+
+        let moved = [ship(&[(5, 5), (5, 6)])];
+        let failure = state.hits_inconsistency_for(&p1, &moved).unwrap().unwrap();
+        assert!(matches!(
+            failure,
+            audit::AuditFailure::ShotInconsistent { x, y, .. } if (x, y) == (0, 0) || (x, y) == (5, 5)
+        ));
+
+        // p2 fired nothing, so any fleet is consistent from their side.
+        assert_eq!(state.hits_inconsistency_for(&p2, &moved).unwrap(), None);
+        let overlapping = [ship(&[(0, 0), (1, 0)]), ship(&[(1, 0), (1, 1)])];
+        assert!(matches!(
+            state.hits_inconsistency_for(&p1, &overlapping),
+            Err(GameError::Overlap { x: 1, y: 0 })
+        ));
+        assert!(matches!(
+            state.hits_inconsistency_for(&PublicKey([9u8; 32]), &honest),
+            Err(GameError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn force_resolve_on_the_shooters_node_leaves_their_board_alone() {
+        let (mut state, p1, p2) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        let mut theirs = placed_board(&CLASSIC_TEST_FLEET);
+        theirs.set_salt([2u8; 16]);
+        set_test_commitment(&p2, board_commitment(&theirs).unwrap());
+        // The shooter's node holds only the shooter's fleet under this match.
+        let mine = placed_board(&CLASSIC_TEST_FLEET);
+        install_defender_board(&p1, mine.clone());
+
+        set_test_executor(Some(&p1));
+        state.propose_shot("m-1", 0, 0, None).unwrap();
+        state.pending_since_ms.set(Some(0));
+        let err = state.force_resolve_pending("m-1").unwrap_err();
+        set_test_executor(None);
+        assert!(err.to_string().contains(&p2.to_base58()), "{err}");
+        let stored = PrivateBoards::private_load_or_default()
+            .unwrap()
+            .boards
+            .get(&PrivateBoards::key("m-1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.get_board().0, mine.get_board().0);
+        assert_eq!(*state.hits_p1.get(), 0);
+        assert_eq!(state.pending.get().len(), 1);
+    }
+
+    #[test]
+    fn missing_target_board_names_the_target() {
+        let (mut state, p1, p2) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        let pending = PendingShot {
+            x: 0,
+            y: 0,
+            shooter: p1,
+            target: p2.clone(),
+            nonce: None,
+        };
+        assert!(matches!(
+            state.missing_target_board(&pending),
+            GameError::NotFound(ref m) if m.contains(&p2.to_base58())
+        ));
+    }
+
+    #[test]
+    fn seeded_draws_replay_and_the_seed_shows_once_finished() {
+        let (mut state, p1, _) = two_player_state();
+        assert!(state.next_seed().is_err());
+
+        state.seed.set(Some(42));
+        let draws: Vec<u64> = (0..3).map(|_| state.next_seed().unwrap()).collect();
+        assert_eq!(*state.seed_draws.get(), 3);
+        state.seed_draws.set(0);
+        let replay: Vec<u64> = (0..3).map(|_| state.next_seed().unwrap()).collect();
+        assert_eq!(draws, replay);
+        assert_ne!(draws[0], draws[1]);
+
+        assert_eq!(state.summary().unwrap().seed, None);
+        state.winner.set(Some(p1));
+        assert_eq!(state.summary().unwrap().seed, Some(42));
+    }
+
+    #[test]
+    fn game_state_bundles_the_callers_view() {
+        let (mut state, p1, p2) = two_player_state();
+        let pb = placed_board(&CLASSIC_TEST_FLEET);
+        mark(&mut state.shots_p2, 0, Cell::Hit.to_u8());
+        state.hits_p2.set(1);
+        state.pending.set(vec![PendingShot {
+            x: 1,
+            y: 0,
+            shooter: p2.clone(),
+            target: p1.clone(),
+            nonce: None,
+        }]);
+
+        let view = state.game_state_for(&p1, Some(&pb)).unwrap();
+        assert_eq!(view.summary.id, "m-1");
+        let own = view.own_board.unwrap();
+        assert_eq!(own.board[1], Cell::Pending.to_u8());
+        assert!(view
+            .target_grid
+            .cells
+            .iter()
+            .all(|&c| c == TargetCell::Unknown));
+        assert_eq!(view.ships_remaining.player1, 16);
+        assert_eq!(view.pending[0].shooter, p2.to_base58());
+
+        // The opponent sees their own fog of war and no board they lack.
+        let theirs = state.game_state_for(&p2, None).unwrap();
+        assert!(theirs.own_board.is_none());
+        assert_eq!(theirs.target_grid.cells[0], TargetCell::Hit);
+
+        let outsider = PublicKey([9u8; 32]);
+        assert!(matches!(
+            state.game_state_for(&outsider, Some(&pb)),
+            Err(GameError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn grid_views_index_rows_by_y() {
+        let mut board = vec![0u8; 100];
+        board[board::Board::idx(10, 3, 7)] = Cell::Ship.to_u8();
+        let grid = OwnBoardView { size: 10, board }.to_grid();
+        assert_eq!(grid.len(), 10);
+        assert!(grid.iter().all(|row| row.len() == 10));
+        assert_eq!(grid[7][3], Cell::Ship.to_u8());
+        assert_eq!(grid[3][7], Cell::Empty.to_u8());
+
+        let shots = ShotsView {
+            size: 2,
+            shots: vec![1, 2, 3, 4],
+        };
+        assert_eq!(shots.to_grid(), vec![vec![1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn ship_lengths_follow_the_placement_list() {
+        let ships = ["0,0;1,0;2,0", "  ", "5,5;5,6", "4,4"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(ship_lengths(&ships, BOARD_SIZE), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn ships_placed_one_at_a_time_can_be_undone_until_confirmed() {
+        let (mut state, p1, _) = two_player_state();
+        set_test_executor(Some(&p1));
+        let err = format!("{:?}", state.undo_last_ship("m-1").unwrap_err());
+        assert!(err.contains("no ships placed"), "{err}");
+
+        state
+            .place_ship("m-1", CLASSIC_TEST_FLEET[0].into())
+            .unwrap();
+        state
+            .place_ship("m-1", CLASSIC_TEST_FLEET[1].into())
+            .unwrap();
+        // Overlapping or surplus ships never reach the draft.
+        assert!(state.place_ship("m-1", "0,0;0,1".into()).is_err());
+        let err = state.place_ship("m-1", "5,9;6,9;7,9;8,9;9,9".into());
+        let err = format!("{:?}", err.unwrap_err());
+        assert!(err.contains("no more ships of length 5"), "{err}");
+
+        state.undo_last_ship("m-1").unwrap();
+        // Each change to the draft is announced as it happens.
+        let events: Vec<_> = state
+            .recent_events_inner(u32::MAX)
+            .unwrap()
+            .into_iter()
+            .map(|e| match e {
+                EventRecord::ShipPlaced {
+                    length, remaining, ..
+                } => ("placed", length, remaining),
+                EventRecord::ShipRemoved {
+                    length, remaining, ..
+                } => ("removed", length, remaining),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            events,
+            vec![("placed", 5, 4), ("placed", 4, 3), ("removed", 4, 4)]
+        );
+        let first = vec![CLASSIC_TEST_FLEET[0].to_string()];
+        assert_eq!(state.get_draft_ships("m-1").unwrap(), first);
+        // An incomplete fleet isn't committed, and the draft survives.
+        assert!(state.confirm_placement("m-1").is_err());
+        assert!(!*state.placed_p1.get());
+        assert_eq!(state.get_draft_ships("m-1").unwrap(), first);
+
+        set_test_commitment(&p1, [7u8; 32]);
+        let err = format!("{:?}", state.undo_last_ship("m-1").unwrap_err());
+        assert!(err.contains("AlreadyCommitted"), "{err}");
+        assert!(state
+            .place_ship("m-1", CLASSIC_TEST_FLEET[1].into())
+            .is_err());
+        set_test_executor(None);
+    }
+
+    #[test]
+    fn migrate_player_moves_the_seat_and_keeps_the_commitment_key() {
+        let (mut state, p1, p2) = two_player_state();
+        set_test_commitment(&p1, [7u8; 32]);
+        let p1_new = PublicKey([7u8; 32]);
+        state.pending.set(vec![PendingShot {
+            x: 1,
+            y: 1,
+            shooter: p1.clone(),
+            target: p2.clone(),
+            nonce: None,
+        }]);
+
+        let reject = |state: &mut GameState, caller: &PublicKey, key: &str| {
+            state.migrate_player_for(caller, "m-1", key).unwrap_err()
+        };
+        let outsider = PublicKey([9u8; 32]);
+        assert!(matches!(
+            reject(&mut state, &outsider, &p1_new.to_base58()),
+            GameError::Forbidden(_)
+        ));
+        assert!(matches!(
+            reject(&mut state, &p1, &p2.to_base58()),
+            GameError::Invalid(ref m) if m == "new key already plays this match"
+        ));
+        assert!(matches!(
+            reject(&mut state, &p1, "not-base58!"),
+            GameError::Invalid(_)
+        ));
+
+        let moved = state
+            .migrate_player_for(&p1, "m-1", &p1_new.to_base58())
+            .unwrap();
+        assert_eq!(moved, p1_new);
+        assert_eq!(state.player1.get().as_ref(), Some(&p1_new));
+        assert_eq!(state.turn.get().as_ref(), Some(&p1_new));
+        let pending = state.pending.get()[0].clone();
+        assert_eq!(
+            (pending.shooter, pending.target),
+            (p1_new.clone(), p2.clone())
+        );
+        assert_eq!(state.commitment_owner(&p1_new), p1);
+        assert_eq!(state.commitment_owner(&p2), p2);
+        // The old key no longer plays.
+        assert!(matches!(
+            reject(&mut state, &p1, &PublicKey([8u8; 32]).to_base58()),
+            GameError::Forbidden(_)
+        ));
+
+        // Migrating again still points the audit at the committing key.
+        let p1_newer = PublicKey([8u8; 32]);
+        state
+            .migrate_player_for(&p1_new, "m-1", &p1_newer.to_base58())
+            .unwrap();
+        assert_eq!(state.commitment_owner(&p1_newer), p1);
+    }
+
+    #[test]
+    fn migrating_before_placing_commits_under_the_new_key() {
+        let (mut state, p1, _) = two_player_state();
+        let p1_new = PublicKey([7u8; 32]);
+        state
+            .migrate_player_for(&p1, "m-1", &p1_new.to_base58())
+            .unwrap();
+        assert_eq!(state.committed_as_p1.get(), &None);
+        assert_eq!(state.commitment_owner(&p1_new), p1_new);
+
+        // `place_ships` under the new key, then the audit's lookup.
+        set_test_commitment(&p1_new, [5u8; 32]);
+        assert_eq!(state.commitment_of(&p1_new).unwrap(), Some([5u8; 32]));
+    }
+
+    #[test]
+    fn own_cell_reads_the_callers_board_only_for_players() {
+        let (mut state, p1, p2) = two_player_state();
+        let pb = placed_board(&CLASSIC_TEST_FLEET);
+        assert_eq!(
+            state.own_cell_for(&p1, &pb, 4, 0).unwrap(),
+            Cell::Ship.to_u8()
+        );
+        assert_eq!(
+            state.own_cell_for(&p1, &pb, 5, 0).unwrap(),
+            Cell::Empty.to_u8()
+        );
+        let err = state.own_cell_for(&p1, &pb, 10, 0).unwrap_err();
+        assert_eq!(err, GameError::Invalid("coordinate out of bounds".into()));
+        let outsider = PublicKey([9u8; 32]);
+        let err = state.own_cell_for(&outsider, &pb, 0, 0).unwrap_err();
+        assert!(matches!(err, GameError::Forbidden(_)));
+
+        state.pending.set(vec![PendingShot {
+            x: 4,
+            y: 0,
+            shooter: p2,
+            target: p1.clone(),
+            nonce: None,
+        }]);
+        assert_eq!(
+            state.own_cell_for(&p1, &pb, 4, 0).unwrap(),
+            Cell::Pending.to_u8()
+        );
+    }
+
+    #[test]
+    fn board_sizes_must_agree_with_the_match() {
+        let (mut state, _, _) = two_player_state();
+        let pb = PlayerBoard::new();
+        state.check_board_sizes(&pb).unwrap();
+
+        let small = PlayerBoard::with_size(8);
+        let err = state.check_board_sizes(&small).unwrap_err();
+        assert_eq!(err, GameError::Invalid("board size mismatch".into()));
+
+        mark(&mut state.shots_p2, 100, Cell::Miss.to_u8());
+        let err = state.check_board_sizes(&pb).unwrap_err();
+        assert_eq!(err, GameError::Invalid("board size mismatch".into()));
+    }
+
+    #[test]
+    fn authorize_checks_id_then_finished_then_player() {
+        let (mut state, p1, _) = two_player_state();
+        let outsider = PublicKey([9u8; 32]);
+        state.winner.set(Some(p1.clone()));
+        assert!(matches!(
+            state.authorize(&outsider, "m-2"),
+            Err(GameError::NotFound(_))
+        ));
+        assert!(matches!(
+            state.authorize(&outsider, "m-1"),
+            Err(GameError::Finished)
+        ));
+        state.winner.set(None);
+        assert!(matches!(
+            state.authorize(&outsider, "m-1"),
+            Err(GameError::Forbidden(ref m)) if m == "not a player"
+        ));
+        state.authorize(&p1, "m-1").unwrap();
+        // Shot-specific checks only run once the shared ones pass.
+        assert!(matches!(
+            state.validate_shot(&outsider, 10, 0),
+            Err(GameError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn pending_shot_can_only_be_forced_after_the_timeout() {
+        let (mut state, p1, p2) = two_player_state();
+        assert!(state.timed_out_pending(u64::MAX).is_err());
+
+        let shot = PendingShot {
+            x: 3,
+            y: 3,
+            shooter: p1,
+            target: p2,
+            nonce: None,
+        };
+        state.pending.set(vec![shot]);
+        state.pending_since_ms.set(Some(1_000));
+        let err = state
+            .timed_out_pending(1_000 + DEFAULT_SHOT_TIMEOUT_MS - 1)
+            .unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("not timed out")));
+        let stale = state
+            .timed_out_pending(1_000 + DEFAULT_SHOT_TIMEOUT_MS)
+            .unwrap();
+        assert_eq!((stale[0].x, stale[0].y), (3, 3));
+    }
+
+    #[test]
+    fn pausing_stops_play_and_the_pending_clock() {
+        let (mut state, p1, p2) = two_player_state();
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        let outsider = PublicKey([9u8; 32]);
+        assert!(state.pause_for(&outsider, 0).is_err());
+        assert!(state.resume_for(&p1, 0).is_err());
+
+        state.pending.set(vec![PendingShot {
+            x: 3,
+            y: 3,
+            shooter: p1.clone(),
+            target: p2.clone(),
+            nonce: None,
+        }]);
+        state.pending_since_ms.set(Some(1_000));
+        state.pause_for(&p2, 2_000).unwrap();
+        assert!(state.summary().unwrap().paused);
+        assert!(state.pause_for(&p1, 2_500).is_err());
+        let paused = GameError::Forbidden("match paused".into());
+        assert_eq!(state.validate_shot(&p1, 0, 0).unwrap_err(), paused);
+        assert_eq!(state.timed_out_pending(u64::MAX).unwrap_err(), paused);
+
+        // A ten-minute break doesn't count towards the timeout.
+        state.resume_for(&p1, 602_000).unwrap();
+        assert!(!state.summary().unwrap().paused);
+        assert_eq!(*state.pending_since_ms.get(), Some(601_000));
+        assert!(state
+            .timed_out_pending(1_000 + DEFAULT_SHOT_TIMEOUT_MS)
+            .is_err());
+        assert!(state
+            .timed_out_pending(601_000 + DEFAULT_SHOT_TIMEOUT_MS)
+            .is_ok());
+    }
+
+    #[test]
+    fn a_player_with_no_cell_left_is_skipped_until_the_board_is_drawn_out() {
+        let p1 = PublicKey([1u8; 32]);
+        let p2 = PublicKey([2u8; 32]);
+        let mut state = GameState::init(
+            p1.to_base58(),
+            p2.to_base58(),
+            None,
+            "m-1".into(),
+            Some(GameRules {
+                shots_per_turn: 2,
+                ..GameRules::default()
+            }),
+            None,
+            None,
+            Some(PlacementRules {
+                board_size: 7,
+                ..PlacementRules::default()
+            }),
+        );
+        state.placed_p1.set(true);
+        state.placed_p2.set(true);
+        // A ship count one too high keeps each fleet afloat once sunk, so
+        // the 49 cells run out before anyone wins.
+        let afloat = || {
+            let mut pb = PlayerBoard::with_size(7);
+            pb.place_ships(
+                [
+                    "0,0;1,0;2,0;3,0;4,0",
+                    "0,2;1,2;2,2;3,2",
+                    "0,4;1,4;2,4",
+                    "0,6;1,6;2,6",
+                    "4,6;5,6",
+                ]
+                .map(String::from)
+                .to_vec(),
+            )
+            .unwrap();
+            let ships = pb.get_ship_count() + 1;
+            PlayerBoard::new_with_salt(pb.get_board().clone(), ships, true, [0; 16])
+        };
+        let mut boards = [afloat(), afloat()];
+        let cells: Vec<(u8, u8)> = (0..7).flat_map(|y| (0..7).map(move |x| (x, y))).collect();
+        let mut fired = [0usize; 2];
+        let mut skipped = false;
+
+        while !state.is_finished() {
+            let (shooter, defender, i) = if state.turn.get().as_ref() == Some(&p1) {
+                (&p1, &p2, 0)
+            } else {
+                (&p2, &p1, 1)
+            };
+            set_test_executor(Some(shooter));
+            let mut batch = 0;
+            while batch < 2 && fired[i] < cells.len() {
+                let (x, y) = cells[fired[i]];
+                state.propose_shot("m-1", x, y, None).unwrap();
+                fired[i] += 1;
+                batch += 1;
+            }
+            assert!(batch > 0, "the turn holder had nothing to fire");
+            set_test_executor(Some(defender));
+            install_defender_board(defender, boards[1 - i].clone());
+            state.acknowledge_shot("m-1").unwrap();
+            boards[1 - i] = PrivateBoards::private_load_or_default()
+                .unwrap()
+                .boards
+                .get(&PrivateBoards::key("m-1"))
+                .unwrap()
+                .unwrap();
+            // p1 fires the board's odd last cell alone, then must hand over.
+            if i == 0 && batch == 1 {
+                assert!(!state.can_fire(&p1).unwrap());
+                assert_eq!(state.turn.get().as_ref(), Some(&p2));
+                skipped = true;
+            }
+        }
+        set_test_executor(None);
+        assert!(skipped);
+        assert!(*state.drawn.get());
+        assert!(state.winner.get().is_none());
+        assert_eq!(fired, [49, 49]);
+    }
+
+    #[test]
+    fn notation_export_follows_the_shot_log_across_migration() {
+        let (mut state, p1, p2) = two_player_state();
+        assert_eq!(state.export_notation("m-1").unwrap(), "");
+        let shot = |pk: &PublicKey, x, y, result: &str| ShotRecord {
+            shooter: pk.to_base58(),
+            x,
+            y,
+            result: result.to_string(),
+            sunk: Vec::new(),
+        };
+        state.record_shot(shot(&p1, 1, 6, "hit"));
+        state.record_shot(shot(&p2, 0, 0, "miss"));
+        state.record_shot(shot(&p1, 2, 6, "miss"));
+        assert_eq!(state.last_shot.get().as_ref().unwrap().x, 2);
+
+        let p1_new = PublicKey([7u8; 32]);
+        state
+            .migrate_player_for(&p1, "m-1", &p1_new.to_base58())
+            .unwrap();
+        let text = state.export_notation("m-1").unwrap();
+        assert_eq!(text, "1. P1 B7 hit, P2 A1 miss\n2. P1 C7 miss");
+        assert_eq!(state.import_notation(text).unwrap(), state.notation_moves());
+    }
+
+    #[test]
+    fn shots_since_returns_only_newer_cells() {
+        let (mut state, p1, p2) = two_player_state();
+        assert_eq!(state.shots_since(0).seq, 0);
+        let shot = |pk: &PublicKey, x, result: &str| ShotRecord {
+            shooter: pk.to_base58(),
+            x,
+            y: 0,
+            result: result.to_string(),
+            sunk: Vec::new(),
+        };
+        state.record_shot(shot(&p1, 1, "hit"));
+        state.record_shot(shot(&p2, 2, "miss"));
+        state.record_shot(shot(&p1, 3, "miss"));
+
+        let all = state.shots_since(0);
+        assert_eq!(all.seq, 3);
+        assert_eq!(all.deltas.len(), 3);
+        let newer = state.shots_since(1);
+        assert_eq!(
+            newer
+                .deltas
+                .iter()
+                .map(|d| (d.seq, d.x))
+                .collect::<Vec<_>>(),
+            vec![(2, 2), (3, 3)]
+        );
+        assert_eq!(newer.deltas[0].shooter, p2.to_base58());
+        assert!(state.shots_since(3).deltas.is_empty());
+        assert!(state.shots_since(9).deltas.is_empty());
+    }
+
+    #[test]
+    fn hit_density_follows_hits_and_skips_fired_cells() {
+        let (mut state, p1, _) = two_player_state();
+        reveal_sunk_ships(&mut state);
+        assert!(matches!(
+            state.hit_density_for(&PublicKey([9u8; 32])),
+            Err(GameError::Forbidden(_))
+        ));
+
+        let fresh = state.hit_density_for(&p1).unwrap();
+        assert_eq!(fresh.len(), 100);
+        assert!((fresh.iter().sum::<f32>() - 1.0).abs() < 1e-4);
+        // The centre fits more placements than a corner.
+        assert!(fresh[55] > fresh[0]);
+
+        mark(&mut state.shots_p1, 44, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 45, Cell::Miss.to_u8());
+        let density = state.hit_density_for(&p1).unwrap();
+        assert_eq!((density[44], density[45]), (0.0, 0.0));
+        let best = density
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+            .unwrap();
+        assert!([34, 43, 54].contains(&best), "best guess {best}");
+
+        // Once every ship is sunk nothing is left to find.
+        for (i, &len) in [5u8, 4, 3, 3, 2].iter().enumerate() {
+            for x in 0..len {
+                mark(&mut state.sunk_p1, (i as u8) * 20 + x, len);
+            }
+        }
+        assert!(state
+            .hit_density_for(&p1)
+            .unwrap()
+            .iter()
+            .all(|&d| d == 0.0));
+    }
+
+    #[test]
+    fn validate_fleet_detailed_reports_every_ship() {
+        let (state, _, _) = two_player_state();
+        let report = state.fleet_validation(&[
+            "0,0;1,0;2,0;3,0;4,0".to_string(),
+            "0,1;1,1;2,1;3,1".to_string(),
+            "9,9;9,10".to_string(),
+            "5,5;6,6".to_string(),
+        ]);
+        let violations: Vec<Vec<String>> =
+            report.ships.iter().map(|s| s.violations.clone()).collect();
+        assert_eq!(
+            violations,
+            vec![
+                vec![],
+                vec![],
+                vec!["bounds".to_string()],
+                vec!["straight".to_string()]
+            ]
+        );
+        assert!(report.ships[0].valid && !report.ships[3].valid);
+        assert_eq!(report.ships[3].index, 3);
+        // Only the two sound ships are compared, and they touch.
+        assert!(report
+            .conflicts
+            .iter()
+            .any(|c| c == "ships 0 and 1 are adjacent"));
+    }
+
+    #[test]
+    fn running_out_of_ammo_lets_the_opponent_finish_then_draws() {
+        let (mut state, p1, p2) = two_player_state();
+        state.game_rules.set(GameRules {
+            max_shots: Some(2),
+            ..GameRules::default()
+        });
+        let shot = PendingShot {
+            x: 9,
+            y: 9,
+            shooter: p1.clone(),
+            target: p2.clone(),
+            nonce: None,
+        };
+        mark(&mut state.shots_p1, 0, Cell::Miss.to_u8());
+        mark(&mut state.shots_p1, 1, Cell::Hit.to_u8());
+        assert_eq!(state.shots_fired_by(&p1).unwrap(), 2);
+        // p1 running dry first decides nothing: p2 still has two shots.
+        assert!(!state.is_drawn_out(&shot).unwrap());
+        state.shots_this_turn.set(1);
+        state.advance_turn().unwrap();
+        assert_eq!(state.turn.get().as_ref(), Some(&p2));
+
+        // p2 keeps the turn while p1 has nothing to fire.
+        mark(&mut state.shots_p2, 0, Cell::Miss.to_u8());
+        state.shots_this_turn.set(1);
+        state.advance_turn().unwrap();
+        assert_eq!(state.turn.get().as_ref(), Some(&p2));
+        assert_eq!(*state.shots_this_turn.get(), 0);
+
+        mark(&mut state.shots_p2, 1, Cell::Pending.to_u8());
+        let reply = PendingShot {
+            shooter: p2.clone(),
+            target: p1.clone(),
+            nonce: None,
+            ..shot
+        };
+        assert!(state.is_drawn_out(&reply).unwrap());
+
+        state.drawn.set(true);
+        assert_eq!(state.phase(), MatchPhase::Finished);
+        assert!(matches!(
+            state.authorize(&p1, "m-1"),
+            Err(GameError::Finished)
+        ));
+    }
+
+    #[test]
+    fn wrong_match_id_is_not_found_everywhere() {
+        let (mut state, p1, _) = two_player_state();
+        set_test_executor(Some(&p1));
+        let not_found = |r: app::Result<()>| {
+            let err = format!("{:?}", r.unwrap_err());
+            assert!(err.contains("NotFound") && err.contains("m-2"), "{err}");
+        };
+        state.winner.set(Some(p1.clone()));
+        // Checked before `Finished`, so a stale id is reported as such.
+        not_found(state.place_ships("m-2", vec![]));
+        not_found(state.propose_shot("m-2", 0, 0, None));
+        not_found(state.acknowledge_shot("m-2").map(drop));
+        not_found(state.cancel_shot("m-2"));
+        not_found(state.get_own_board("m-2").map(drop));
+        not_found(state.get_shots("m-2").map(drop));
+        not_found(state.get_target_grid("m-2").map(drop));
+        not_found(state.get_shot_counts("m-2").map(drop));
+        not_found(state.get_winner("m-2").map(drop));
+        not_found(state.get_last_shot("m-2").map(drop));
+        not_found(state.get_rules("m-2").map(drop));
+        not_found(state.export_match("m-2").map(drop));
+        let err = format!("{:?}", state.propose_shot("m-1", 0, 0, None).unwrap_err());
+        assert!(err.contains("Finished"), "{err}");
+        set_test_executor(None);
+    }
+
+    #[test]
+    fn intact_cells_leave_out_hits() {
+        let (state, p1, _) = two_player_state();
+        let mut pb = placed_board(&CLASSIC_TEST_FLEET);
+        ShotResolver::resolve_shot(&mut pb, 1, 0);
+        ShotResolver::resolve_shot(&mut pb, 9, 9);
+        let intact = state.intact_cells_for(&p1, &pb).unwrap();
+        assert_eq!(intact.len(), 16);
+        assert_eq!(
+            intact[..2],
+            [Coordinate { x: 0, y: 0 }, Coordinate { x: 2, y: 0 }]
+        );
+        assert!(matches!(
+            state.intact_cells_for(&PublicKey([9u8; 32]), &pb),
+            Err(GameError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn shot_board_marks_sunk_ships() {
+        let (mut state, p1, p2) = two_player_state();
+        reveal_sunk_ships(&mut state);
+        for idx in [0, 1] {
+            mark(&mut state.shots_p1, idx, Cell::Hit.to_u8());
+            mark(&mut state.sunk_p1, idx, 2);
+        }
+        mark(&mut state.shots_p1, 20, Cell::Hit.to_u8());
+        mark(&mut state.shots_p1, 21, Cell::Miss.to_u8());
+        let board = state.shot_board_for(&p1).unwrap();
+        let sunk = Cell::Sunk.to_u8();
+        assert_eq!(&board[..2], &[sunk, sunk]);
+        assert_eq!(&board[20..22], &[Cell::Hit.to_u8(), Cell::Miss.to_u8()]);
+        assert!(state.shot_board_for(&p2).unwrap().iter().all(|&c| c == 0));
+        // Stored shots keep plain hits.
+        assert_eq!(
+            read_cell_map(&state.shots_p1, BOARD_SIZE).unwrap()[0],
+            Cell::Hit.to_u8()
+        );
+    }
+
+    #[test]
+    fn sunk_ship_extents_are_only_stored_when_revealed() {
+        for reveal in [false, true] {
+            let (mut state, p1, p2) = two_player_state();
+            state.game_rules.set(GameRules {
+                shots_per_turn: 2,
+                reveal_on_sink: reveal,
+                ..GameRules::default()
+            });
+            state.placed_p1.set(true);
+            state.placed_p2.set(true);
+            set_test_executor(Some(&p1));
+            state.propose_shot("m-1", 0, 8, None).unwrap();
+            state.propose_shot("m-1", 1, 8, None).unwrap();
+            set_test_executor(Some(&p2));
+            install_defender_board(&p2, placed_board(&CLASSIC_TEST_FLEET));
+            state.acknowledge_shot("m-1").unwrap();
+            set_test_executor(None);
+
+            let len = if reveal { 2 } else { 0 };
+            let marks = read_cell_map(&state.sunk_p1, BOARD_SIZE).unwrap();
+            assert_eq!((marks[80], marks[81]), (len, len));
+            let last = state.get_last_shot("m-1").unwrap().unwrap();
+            assert_eq!(last.sunk.len(), usize::from(len));
+        }
+    }
+
+    #[test]
+    fn without_reveal_on_sink_sunk_ships_read_as_hits_everywhere() {
+        let (mut state, p1, _) = two_player_state();
+        state.game_rules.set(GameRules {
+            reveal_on_sink: false,
+            ..GameRules::default()
+        });
+        for idx in [0, 1] {
+            mark(&mut state.shots_p1, idx, Cell::Hit.to_u8());
+            mark(&mut state.sunk_p1, idx, 2);
+        }
+        let hit = Cell::Hit.to_u8();
+        assert_eq!(&state.shot_board_for(&p1).unwrap()[..2], &[hit, hit]);
+        assert_eq!(
+            state.target_grid_for(&p1).unwrap().cells[0],
+            TargetCell::Hit
+        );
+        let snapshot = state.snapshot().unwrap();
+        assert!(snapshot.sunk_p1.iter().all(|&len| len == 0));
+    }
+
+    #[test]
+    fn audit_match_reports_instead_of_failing() {
+        let (mut state, p1, p2) = two_player_state();
+        assert_eq!(state.audit_match("m-1").unwrap(), Vec::<String>::new());
+        assert!(state.audit_match("m-2").is_err());
+
+        state.turn.set(Some(PublicKey([9u8; 32])));
+        mark(&mut state.shots_p2, 7, Cell::Pending.to_u8());
+        let mut pb = placed_board(&CLASSIC_TEST_FLEET);
+        pb.get_board_mut().set(BOARD_SIZE, 9, 9, Cell::Ship);
+        install_private_board(pb);
+        let found = state.audit_match("m-1").unwrap();
+        assert_eq!(found.len(), 3, "{found:?}");
+        assert!(found[0].starts_with("Pending cells without a pending shot"));
+        assert!(found[1].contains("not a player"));
+        assert!(found[2].contains("ship counter 17"));
+
+        state.turn.set(Some(p1.clone()));
+        state.pending.set(vec![PendingShot {
+            x: 7,
+            y: 0,
+            shooter: p2,
+            target: p1,
+            nonce: None,
+        }]);
+        install_private_board(PlayerBoard::new());
+        assert!(state.audit_match("m-1").unwrap().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "target's map holds Pending cells")]
+    fn assert_invariants_catches_a_second_pending_cell() {
+        let (mut state, p1, p2) = two_player_state();
+        mark(&mut state.shots_p1, 5, Cell::Pending.to_u8());
+        mark(&mut state.shots_p2, 6, Cell::Pending.to_u8());
+        state.pending.set(vec![PendingShot {
+            x: 5,
+            y: 0,
+            shooter: p1,
+            target: p2,
+            nonce: None,
+        }]);
+        state.assert_invariants();
     }
 
     /// Exercises the duplicate-shot guard added in `propose_shot`. A direct
@@ -0,0 +1,174 @@
+//! # Notation Module
+//!
+//! A plain-text move list for sharing and archiving matches, in the spirit
+//! of chess PGN. Unlike the binary `export_match` snapshot it is meant to
+//! be read, diffed and pasted by people:
+//!
+//! ```text
+//! 1. P1 B7 hit, P2 A1 miss
+//! 2. P1 C7 hit, P2 J10 miss
+//! ```
+//!
+//! Grammar:
+//!
+//! ```text
+//! notation = [ round *( LF round ) ]
+//! round    = number ". " shot *( ", " shot )
+//! shot     = side SP cell SP result
+//! side     = "P1" / "P2"
+//! cell     = column row     ; column "A".. is x, row "1".. is y + 1
+//! result   = "hit" / "miss"
+//! ```
+//!
+//! Rounds are numbered from 1. A round opens with the side that fired the
+//! match's first shot and runs until that side fires again after the
+//! other side has, so salvo turns simply list several shots in a row.
+//! Columns and rows run as far as the board does: "A".."J" and "1".."10"
+//! on the classic 10x10 board.
+
+use battleships_types::GameError;
+
+use crate::NotationMove;
+
+/// Renders `moves`, oldest first, as notation text.
+pub fn format(moves: &[NotationMove]) -> String {
+    let Some(opener) = moves.first().map(|m| m.side) else {
+        return String::new();
+    };
+    let mut out = String::new();
+    let mut round = 0u32;
+    let mut prev_side = None;
+    for m in moves {
+        if m.side == opener && prev_side != Some(opener) {
+            round += 1;
+            if round > 1 {
+                out.push('\n');
+            }
+            out.push_str(&format!("{round}. "));
+        } else {
+            out.push_str(", ");
+        }
+        let column = char::from(b'A' + m.x);
+        out.push_str(&format!("P{} {column}{} {}", m.side, m.y + 1, m.result));
+        prev_side = Some(m.side);
+    }
+    out
+}
+
+/// Parses notation text for a `size`x`size` board back into its moves,
+/// oldest first. Blank lines are ignored; anything else outside the
+/// grammar, or off the board, is `Invalid`.
+pub fn parse(text: &str, size: u8) -> Result<Vec<NotationMove>, GameError> {
+    let mut moves = Vec::new();
+    let rounds = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    for (i, line) in rounds.enumerate() {
+        let (number, shots) = line
+            .split_once(". ")
+            .ok_or_else(|| invalid(line, "expected `<round>. <shots>`"))?;
+        if number.parse::<usize>().ok() != Some(i + 1) {
+            return Err(invalid(line, &format!("expected round {}", i + 1)));
+        }
+        for shot in shots.split(", ") {
+            moves.push(parse_shot(shot, size)?);
+        }
+    }
+    Ok(moves)
+}
+
+fn parse_shot(shot: &str, size: u8) -> Result<NotationMove, GameError> {
+    let parts: Vec<&str> = shot.split(' ').collect();
+    let [side, cell, result] = parts[..] else {
+        return Err(invalid(shot, "expected `<side> <cell> <result>`"));
+    };
+    let side = match side {
+        "P1" => 1,
+        "P2" => 2,
+        _ => return Err(invalid(shot, "side must be P1 or P2")),
+    };
+    let (x, y) = parse_cell(cell, size).ok_or_else(|| invalid(shot, "cell out of bounds"))?;
+    if !matches!(result, "hit" | "miss") {
+        return Err(invalid(shot, "result must be hit or miss"));
+    }
+    Ok(NotationMove {
+        side,
+        x,
+        y,
+        result: result.to_string(),
+    })
+}
+
+/// `"B7"` -> `(1, 6)`, if that is on a `size`x`size` board.
+fn parse_cell(cell: &str, size: u8) -> Option<(u8, u8)> {
+    let mut chars = cell.chars();
+    let column = chars.next()?;
+    if !column.is_ascii_uppercase() {
+        return None;
+    }
+    let x = column as u8 - b'A';
+    let row: u8 = chars.as_str().parse().ok()?;
+    let y = row.checked_sub(1)?;
+    (x < size && y < size).then_some((x, y))
+}
+
+fn invalid(at: &str, why: &str) -> GameError {
+    GameError::Invalid(format!("notation `{at}`: {why}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BOARD_SIZE;
+
+    fn mv(side: u8, x: u8, y: u8, result: &str) -> NotationMove {
+        NotationMove {
+            side,
+            x,
+            y,
+            result: result.to_string(),
+        }
+    }
+
+    #[test]
+    fn rounds_group_alternating_and_salvo_turns() {
+        let moves = vec![
+            mv(1, 1, 6, "hit"),
+            mv(2, 0, 0, "miss"),
+            mv(1, 2, 6, "hit"),
+            mv(1, 3, 6, "miss"),
+            mv(2, 9, 9, "miss"),
+        ];
+        let text = format(&moves);
+        assert_eq!(
+            text,
+            "1. P1 B7 hit, P2 A1 miss\n2. P1 C7 hit, P1 D7 miss, P2 J10 miss"
+        );
+        assert_eq!(parse(&text, BOARD_SIZE).unwrap(), moves);
+        assert_eq!(format(&[]), "");
+        assert!(parse("\n", BOARD_SIZE).unwrap().is_empty());
+    }
+
+    #[test]
+    fn text_outside_the_grammar_is_rejected() {
+        for bad in [
+            "P1 A1 hit",
+            "2. P1 A1 hit",
+            "1. P3 A1 hit",
+            "1. P1 K1 hit",
+            "1. P1 A0 hit",
+            "1. P1 A11 hit",
+            "1. P1 a1 hit",
+            "1. P1 A1 sunk",
+            "1. P1 A1",
+            "1. P1 A1 hit,P2 B1 miss",
+        ] {
+            assert!(
+                matches!(parse(bad, BOARD_SIZE), Err(GameError::Invalid(_))),
+                "accepted {bad:?}"
+            );
+        }
+        // Cells are checked against the board the match is played on.
+        assert!(parse("1. P1 H8 hit", 8).is_ok());
+        assert!(parse("1. P1 I1 hit", 8).is_err());
+        assert!(parse("1. P1 A9 hit", 8).is_err());
+    }
+}
@@ -8,6 +8,7 @@
 //! - **`PublicKey`** - Represents a player's public key for identification
 //! - **`PlayerBoard`** - Represents a player's private board and ship data
 //! - **`PrivateBoards`** - Repository for storing player board data privately
+//! - **`ShotResolver`** - Service applying an incoming shot to a `PlayerBoard`
 //!
 //! ## Player Management
 //!
@@ -42,8 +43,8 @@
 //! assert!(board.is_placed());
 //! ```
 
-use crate::board::{Board, Cell, BOARD_SIZE};
-use crate::ships::ShipValidator;
+use crate::board::{line_coordinates, Board, Cell, Coordinate, Orientation, BOARD_SIZE};
+use crate::ships::{Fleet, Ship, ShipValidator};
 use crate::validation::validate_fleet_composition;
 use battleships_types::GameError;
 use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
@@ -98,6 +99,9 @@ pub struct PlayerBoard {
     /// the commitment during audit/reveal/import. `own` is mutated as shots
     /// resolve, so we can't hash it. Empty until the first `place_ships`.
     pristine: Vec<u8>,
+    /// Whether the board wraps at its edges (`GameRules::wrap_edges`), so
+    /// ships may cross them and touch across them.
+    wrap_edges: bool,
 }
 
 impl Default for PlayerBoard {
@@ -108,12 +112,25 @@ impl Default for PlayerBoard {
 
 impl PlayerBoard {
     pub fn new() -> PlayerBoard {
+        PlayerBoard::with_size(BOARD_SIZE)
+    }
+
+    /// An empty board for a match played on a `size`x`size` grid. Every
+    /// later placement and shot uses that size.
+    pub fn with_size(size: u8) -> PlayerBoard {
+        PlayerBoard::with_topology(size, false)
+    }
+
+    /// `with_size`, on a board that wraps at its edges when `wrap_edges`
+    /// is set.
+    pub fn with_topology(size: u8, wrap_edges: bool) -> PlayerBoard {
         PlayerBoard {
-            own: Board::new_zeroed(BOARD_SIZE),
+            own: Board::new_zeroed(size),
             ships: 0,
             placed: false,
             salt: [0u8; 16],
             pristine: Vec::new(),
+            wrap_edges,
         }
     }
 
@@ -125,9 +142,119 @@ impl PlayerBoard {
             placed,
             salt,
             pristine,
+            wrap_edges: false,
         }
     }
 
+    /// Places a fleet drawn as a `size`x`size` grid, one row per line, with
+    /// `#` for ship cells and `.` for water; blank lines and whitespace
+    /// around a row are ignored. Ships are read back as the grid's
+    /// connected groups of `#` and go through `place_ships`, so a drawing
+    /// that isn't a legal fleet is rejected the same way a ship list is.
+    ///
+    /// ```text
+    /// #####.....
+    /// ..........
+    /// ####......
+    /// ```
+    pub fn from_ascii(s: &str, size: u8) -> Result<PlayerBoard, GameError> {
+        let malformed = |why: &str| GameError::Invalid(format!("malformed ascii board: {why}"));
+        let rows: Vec<&str> = s
+            .lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .collect();
+        if rows.len() != size as usize {
+            return Err(malformed("wrong number of rows"));
+        }
+        let mut drawn = Board::new_zeroed(size);
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != size as usize {
+                return Err(malformed("wrong row length"));
+            }
+            for (x, ch) in row.chars().enumerate() {
+                match ch {
+                    '#' => drawn.set(size, x as u8, y as u8, Cell::Ship),
+                    '.' => {}
+                    _ => return Err(malformed("unexpected character")),
+                }
+            }
+        }
+
+        let ships = drawn
+            .connected_ship_groups(size, false)
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|c| format!("{},{}", c.x, c.y))
+                    .collect::<Vec<_>>()
+                    .join(";")
+            })
+            .collect();
+        let mut pb = PlayerBoard::with_size(size);
+        pb.place_ships(ships)?;
+        Ok(pb)
+    }
+
+    /// Lays out `fleet` (ship lengths) at pseudo-random positions derived
+    /// from `seed`; the same seed always yields the same board. Each ship is
+    /// retried at up to `RANDOM_PLACEMENT_ATTEMPTS` positions before the
+    /// fleet is declared not to fit. The result goes through `place_ships`.
+    pub fn random(size: u8, fleet: &[u8], seed: u64) -> Result<PlayerBoard, GameError> {
+        let mut rng = seed;
+        let mut scratch = Board::new_zeroed(size);
+        let mut ships = Vec::with_capacity(fleet.len());
+        for &len in fleet {
+            let coords = (0..RANDOM_PLACEMENT_ATTEMPTS)
+                .find_map(|_| {
+                    let roll = splitmix64(&mut rng);
+                    let orientation = if roll & 1 == 0 {
+                        Orientation::Horizontal
+                    } else {
+                        Orientation::Vertical
+                    };
+                    let x = ((roll >> 8) % size as u64) as u8;
+                    let y = ((roll >> 32) % size as u64) as u8;
+                    let coords =
+                        line_coordinates(Coordinate { x, y }, orientation, len, size).ok()?;
+                    ShipValidator::validate_ship_placement(&scratch, size, &coords, false).ok()?;
+                    Some(coords)
+                })
+                .ok_or_else(|| GameError::Invalid("fleet does not fit the board".into()))?;
+            for c in &coords {
+                scratch.set(size, c.x, c.y, Cell::Ship);
+            }
+            ships.push(
+                coords
+                    .iter()
+                    .map(|c| format!("{},{}", c.x, c.y))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+            );
+        }
+        let mut pb = PlayerBoard::with_size(size);
+        pb.place_ships(ships)?;
+        pb.capture_pristine();
+        Ok(pb)
+    }
+
+    /// Validates the board shapes against the match's board `size` after
+    /// loading from private storage. The pristine snapshot is allowed to be
+    /// empty (nothing placed yet).
+    pub fn check_integrity(&self, size: u8) -> Result<(), GameError> {
+        self.own.check_len(size)?;
+        if !self.pristine.is_empty() {
+            Board(self.pristine.clone()).check_len(size)?;
+        }
+        Ok(())
+    }
+
+    /// Side length of the board, as fixed by `with_size`.
+    pub fn size(&self) -> u8 {
+        self.own.side()
+    }
+
     pub fn salt(&self) -> &[u8; 16] {
         &self.salt
     }
@@ -153,57 +280,129 @@ impl PlayerBoard {
         if self.placed {
             return Err(GameError::Invalid("already placed".into()));
         }
+        if ships.iter().all(|group| group.trim().is_empty()) {
+            return Err(GameError::Invalid("no ships".into()));
+        }
+
+        // Nothing on `self` is touched until the whole fleet has validated.
+        let (board, fleet) = Self::layout_fleet(&self.own, &ships, self.wrap_edges)?;
+        // The fleet spec, not the placement loop, decides how many hits sink
+        // it; win detection relies on the two agreeing.
+        let ship_cells = fleet.total_cells() as u64;
+        let counted = Self::check_ship_count(&board, ship_cells);
+        debug_assert!(counted.is_ok(), "ship counter diverged from the board");
+        counted?;
+        self.own = board;
+        self.ships = ship_cells;
+        self.placed = true;
+        Ok(())
+    }
+
+    /// Rejects a placement whose ship counter doesn't match the `Ship`
+    /// cells actually stamped on `board`.
+    pub(crate) fn check_ship_count(board: &Board, ships: u64) -> Result<(), GameError> {
+        let on_board = board
+            .0
+            .iter()
+            .filter(|&&c| Cell::from_u8(c).is_ship())
+            .count() as u64;
+        if on_board != ships {
+            return Err(GameError::Invalid("ship count mismatch".into()));
+        }
+        Ok(())
+    }
 
+    /// Parses and validates `ships` against a scratch copy of `base`,
+    /// returning the stamped board and the fleet it holds. Pure: any error
+    /// leaves the caller's board exactly as it was.
+    fn layout_fleet(
+        base: &Board,
+        ships: &[String],
+        wrap: bool,
+    ) -> Result<(Board, Fleet), GameError> {
         let mut ship_counts = [0; 4]; // [2,3,4,5] lengths
-        let mut total_ships = 0;
         let mut all_ship_coordinates = Vec::new();
+        let mut fleet_ships = Vec::new();
+        let mut scratch = base.clone();
+        let size = base.side();
 
         for group in ships.iter() {
-            let coords = ShipValidator::parse_ship_coords(group)?;
-            if coords.is_empty() {
+            let Some(coords) = Self::stamp_ship(&mut scratch, group, wrap)? else {
                 continue;
-            }
-
-            let ship_len = coords.len();
-            if !(2..=5).contains(&ship_len) {
-                return Err(GameError::Invalid("ship length must be 2-5".into()));
-            }
-
-            let idx = ship_len - 2;
-            if idx >= 4 {
-                return Err(GameError::Invalid("invalid ship length".into()));
-            }
-            ship_counts[idx] += 1;
-            total_ships += 1;
+            };
+            ship_counts[coords.len() - 2] += 1;
 
             // Store coordinates for fleet validation
             all_ship_coordinates.push(coords.clone());
-
-            ShipValidator::validate_ship_placement(&self.own, BOARD_SIZE, &coords)?;
-
-            // Place the ship
-            for coord in coords {
-                self.own.set(BOARD_SIZE, coord.x, coord.y, Cell::Ship);
-                self.ships = self.ships.saturating_add(1);
-            }
+            fleet_ships.push(Ship::new(coords, size)?);
         }
 
-        if total_ships == 0 {
+        if fleet_ships.is_empty() {
             return Err(GameError::Invalid("no ships".into()));
         }
 
         // Use validation strategy pattern for fleet composition
-        Self::validate_fleet_composition(ship_counts, all_ship_coordinates)?;
-        self.placed = true;
+        Self::validate_fleet_composition(ship_counts, all_ship_coordinates, size, wrap)?;
+        let mut fleet = Fleet { ships: fleet_ships };
+        fleet.canonicalize();
+        Ok((scratch, fleet))
+    }
+
+    /// Checks an unfinished fleet, such as a placement draft: every ship
+    /// must be valid and clear of the ones before it, but the fleet's
+    /// composition is left to `place_ships`.
+    pub(crate) fn check_partial_fleet(
+        size: u8,
+        wrap: bool,
+        ships: &[String],
+    ) -> Result<(), GameError> {
+        let mut scratch = Board::new_zeroed(size);
+        for group in ships {
+            if Self::stamp_ship(&mut scratch, group, wrap)?.is_none() {
+                return Err(GameError::Invalid("no ship".into()));
+            }
+        }
         Ok(())
     }
 
+    /// Validates one ship against `scratch` and stamps its cells there,
+    /// returning them; `None` for a group with no cells on the board.
+    fn stamp_ship(
+        scratch: &mut Board,
+        group: &str,
+        wrap: bool,
+    ) -> Result<Option<Vec<Coordinate>>, GameError> {
+        let size = scratch.side();
+        let coords = ShipValidator::parse_ship_coords(group, size)?;
+        if coords.is_empty() {
+            return Ok(None);
+        }
+        if !(2..=5).contains(&coords.len()) {
+            return Err(GameError::Invalid("ship length must be 2-5".into()));
+        }
+
+        ShipValidator::validate_ship_placement(scratch, size, &coords, wrap)?;
+
+        // Place the ship
+        for coord in &coords {
+            if !scratch.set_if_empty(size, coord.x, coord.y, Cell::Ship) {
+                return Err(GameError::Overlap {
+                    x: coord.x,
+                    y: coord.y,
+                });
+            }
+        }
+        Ok(Some(coords))
+    }
+
     fn validate_fleet_composition(
         ship_counts: [usize; 4],
         ship_coordinates: Vec<Vec<crate::board::Coordinate>>,
+        size: u8,
+        wrap: bool,
     ) -> Result<(), GameError> {
         // Use the validation strategy pattern
-        validate_fleet_composition(ship_counts, ship_coordinates)
+        validate_fleet_composition(ship_counts, ship_coordinates, size, wrap)
     }
 
     pub fn get_board(&self) -> &Board {
@@ -222,6 +421,41 @@ impl PlayerBoard {
         self.ships
     }
 
+    /// If the ship covering `(x, y)` has had every cell hit, returns its
+    /// cells; `None` while any part of it is still afloat (or there is no
+    /// ship there at all).
+    pub fn sunk_ship_at(&self, x: u8, y: u8) -> Option<Vec<Coordinate>> {
+        let size = self.size();
+        let group = self.own.ship_group_at(size, x, y, self.wrap_edges);
+        if group.is_empty()
+            || group
+                .iter()
+                .any(|c| self.own.get(size, c.x, c.y) != Cell::Hit)
+        {
+            return None;
+        }
+        Some(group)
+    }
+
+    /// Every ship of the fleet that has been fully hit, read from the
+    /// board itself rather than from remembered shot events.
+    pub fn sunk_ships(&self) -> Vec<Ship> {
+        let size = self.size();
+        self.own
+            .connected_ship_groups(size, self.wrap_edges)
+            .into_iter()
+            .filter(|group| {
+                group
+                    .iter()
+                    .all(|c| self.own.get(size, c.x, c.y) == Cell::Hit)
+            })
+            .map(|coordinates| Ship {
+                length: coordinates.len() as u8,
+                coordinates,
+            })
+            .collect()
+    }
+
     pub fn decrement_ships(&mut self) {
         if self.ships > 0 {
             self.ships = self.ships.saturating_sub(1);
@@ -229,6 +463,55 @@ impl PlayerBoard {
     }
 }
 
+// ============================================================================
+// SHOT RESOLUTION SERVICE
+// ============================================================================
+
+/// How a shot landed on the defender's private board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShotOutcome {
+    /// Struck an intact ship cell; the ship counter was decremented.
+    Hit,
+    /// Landed on open water.
+    Miss,
+    /// The cell was already resolved (holds the earlier `Hit` or `Miss`).
+    /// Nothing is mutated, so a duplicate can never double-count a hit.
+    Redundant(Cell),
+}
+
+impl ShotOutcome {
+    /// The cell value to record in the shooter's shot map.
+    pub fn recorded_cell(self) -> Cell {
+        match self {
+            ShotOutcome::Hit => Cell::Hit,
+            ShotOutcome::Miss => Cell::Miss,
+            ShotOutcome::Redundant(cell) => cell,
+        }
+    }
+}
+
+pub struct ShotResolver;
+
+impl ShotResolver {
+    pub fn resolve_shot(board: &mut PlayerBoard, x: u8, y: u8) -> ShotOutcome {
+        let size = board.size();
+        match board.own.get(size, x, y) {
+            Cell::Ship => {
+                board.own.set(size, x, y, Cell::Hit);
+                board.decrement_ships();
+                ShotOutcome::Hit
+            }
+            cell @ (Cell::Hit | Cell::Miss) => ShotOutcome::Redundant(cell),
+            // Never stored on a board; read as the hit it stands for.
+            Cell::Sunk => ShotOutcome::Redundant(Cell::Hit),
+            Cell::Empty | Cell::Pending => {
+                board.own.set(size, x, y, Cell::Miss);
+                ShotOutcome::Miss
+            }
+        }
+    }
+}
+
 // ============================================================================
 // REPOSITORY PATTERN - Data access abstraction
 // ============================================================================
@@ -238,12 +521,16 @@ impl PlayerBoard {
 #[calimero_sdk::app::private]
 pub struct PrivateBoards {
     pub boards: UnorderedMap<String, PlayerBoard>,
+    /// Ships laid out one at a time with `place_ship`, in placement order,
+    /// until `confirm_placement` commits them; keyed like `boards`.
+    pub drafts: UnorderedMap<String, Vec<String>>,
 }
 
 impl Default for PrivateBoards {
     fn default() -> PrivateBoards {
         PrivateBoards {
             boards: UnorderedMap::new(),
+            drafts: UnorderedMap::new(),
         }
     }
 }
@@ -252,11 +539,31 @@ impl PrivateBoards {
     pub fn key(match_id: &str) -> String {
         match_id.to_string()
     }
+
+    /// Where a practice match keeps the ghost opponent's board, apart from
+    /// the player's own so `get_own_board` never shows it.
+    pub fn ghost_key(match_id: &str) -> String {
+        format!("{match_id}:ghost")
+    }
+}
+
+/// Positions tried per ship by `PlayerBoard::random`.
+const RANDOM_PLACEMENT_ATTEMPTS: u32 = 1000;
+
+/// SplitMix64 step: advances `state` and returns the next value. Small and
+/// deterministic, which is all seeded placement needs.
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::CLASSIC_TEST_FLEET;
 
     #[test]
     fn player_board_default_has_zero_salt() {
@@ -279,6 +586,289 @@ mod tests {
         assert_eq!(pb.salt(), &[42u8; 16]);
     }
 
+    #[test]
+    fn sunk_ship_at_requires_every_cell_hit() {
+        let mut pb = PlayerBoard::new();
+        for x in 2..5 {
+            pb.get_board_mut().set(BOARD_SIZE, x, 3, Cell::Ship);
+        }
+        pb.get_board_mut().set(BOARD_SIZE, 2, 3, Cell::Hit);
+        pb.get_board_mut().set(BOARD_SIZE, 3, 3, Cell::Hit);
+        assert_eq!(pb.sunk_ship_at(3, 3), None);
+        assert_eq!(pb.sunk_ship_at(0, 0), None);
+
+        pb.get_board_mut().set(BOARD_SIZE, 4, 3, Cell::Hit);
+        let sunk = pb.sunk_ship_at(4, 3).unwrap();
+        assert_eq!(
+            sunk,
+            vec![
+                Coordinate { x: 2, y: 3 },
+                Coordinate { x: 3, y: 3 },
+                Coordinate { x: 4, y: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_integrity_rejects_truncated_boards() {
+        assert!(PlayerBoard::new().check_integrity(BOARD_SIZE).is_ok());
+        let short = PlayerBoard::new_with_salt(Board(vec![0; 10]), 0, true, [0u8; 16]);
+        assert!(matches!(
+            short.check_integrity(BOARD_SIZE),
+            Err(GameError::Invalid(_))
+        ));
+        assert!(PlayerBoard::with_size(8)
+            .check_integrity(BOARD_SIZE)
+            .is_err());
+    }
+
+    #[test]
+    fn classic_fleet_fits_the_narrowest_boards_allowed() {
+        use crate::rules::{MIN_BOARD_SIZE, MIN_WRAPPED_BOARD_SIZE};
+        // Rows 0, 2, 4 and 6, the two 3-ships sharing row 4 with a gap.
+        let fleet = [
+            "0,0;1,0;2,0;3,0;4,0",
+            "0,2;1,2;2,2;3,2",
+            "0,4;1,4;2,4",
+            "4,4;5,4;6,4",
+            "0,6;1,6",
+        ]
+        .map(String::from)
+        .to_vec();
+        let mut flat = PlayerBoard::with_size(MIN_BOARD_SIZE);
+        assert!(flat.place_ships(fleet.clone()).is_ok());
+        // Wrapped, row 6 touches row 0 on a 7-wide board but not an 8-wide one.
+        let mut tight = PlayerBoard::with_topology(MIN_BOARD_SIZE, true);
+        assert!(tight.place_ships(fleet.clone()).is_err());
+        let mut wrapped = PlayerBoard::with_topology(MIN_WRAPPED_BOARD_SIZE, true);
+        assert!(wrapped.place_ships(fleet).is_ok());
+    }
+
+    #[test]
+    fn random_placement_is_legal_and_repeatable() {
+        let fleet = crate::rules::CLASSIC_FLEET;
+        let a = PlayerBoard::random(BOARD_SIZE, &fleet, 42).unwrap();
+        let b = PlayerBoard::random(BOARD_SIZE, &fleet, 42).unwrap();
+        let c = PlayerBoard::random(BOARD_SIZE, &fleet, 43).unwrap();
+        assert!(a.is_placed());
+        assert_eq!(a.get_ship_count(), 17);
+        assert_eq!(
+            a.get_board().connected_ship_groups(BOARD_SIZE, false).len(),
+            5
+        );
+        assert_eq!(a.get_board().0, b.get_board().0);
+        assert_ne!(a.get_board().0, c.get_board().0);
+
+        let err = PlayerBoard::random(4, &fleet, 1).unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("does not fit")));
+    }
+
+    #[test]
+    fn from_ascii_places_the_drawn_fleet() {
+        let drawing = "
+            #####.....
+            ..........
+            ####......
+            ..........
+            ###.......
+            ..........
+            ###.......
+            ..........
+            ##........
+            ..........
+        ";
+        let pb = PlayerBoard::from_ascii(drawing, BOARD_SIZE).unwrap();
+        assert!(pb.is_placed());
+        assert_eq!(pb.get_ship_count(), 17);
+        assert_eq!(pb.get_board().get(BOARD_SIZE, 4, 0), Cell::Ship);
+        assert_eq!(pb.get_board().get(BOARD_SIZE, 5, 0), Cell::Empty);
+
+        // Two ships touching diagonally read as separate groups, and the
+        // placement rules still reject them.
+        let touching =
+            drawing
+                .replacen("..........", ".....##...", 1)
+                .replacen("##........", "..........", 1);
+        let err = PlayerBoard::from_ascii(&touching, BOARD_SIZE).unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("adjacent")));
+        let short = drawing.replacen("#####.....", "#####....", 1);
+        let err = PlayerBoard::from_ascii(&short, BOARD_SIZE).unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("row length")));
+        let bad_char = drawing.replacen("#####.....", "#####....x", 1);
+        assert!(PlayerBoard::from_ascii(&bad_char, BOARD_SIZE).is_err());
+    }
+
+    #[test]
+    fn sunk_ships_lists_only_fully_hit_ships() {
+        let fleet = |ships: &[&str]| ships.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut pb = PlayerBoard::new();
+        pb.place_ships(fleet(&CLASSIC_TEST_FLEET)).unwrap();
+        assert_eq!(
+            pb.get_board()
+                .connected_ship_groups(BOARD_SIZE, false)
+                .len(),
+            5
+        );
+        assert!(pb.sunk_ships().is_empty());
+
+        ShotResolver::resolve_shot(&mut pb, 0, 8);
+        ShotResolver::resolve_shot(&mut pb, 1, 8);
+        ShotResolver::resolve_shot(&mut pb, 0, 4);
+        let sunk = pb.sunk_ships();
+        assert_eq!(sunk.len(), 1);
+        assert_eq!(sunk[0].length, 2);
+        assert_eq!(
+            sunk[0].coordinates,
+            vec![Coordinate { x: 0, y: 8 }, Coordinate { x: 1, y: 8 }]
+        );
+    }
+
+    #[test]
+    fn placement_on_a_small_board_uses_its_own_edges() {
+        let fleet = |ships: &[&str]| ships.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut pb = PlayerBoard::with_size(8);
+        pb.place_ships(fleet(&[
+            "3,0;4,0;5,0;6,0;7,0",
+            "0,2;1,2;2,2;3,2",
+            "0,4;1,4;2,4",
+            "0,6;1,6;2,6",
+            "7,6;7,7",
+        ]))
+        .unwrap();
+        pb.check_integrity(8).unwrap();
+        assert_eq!(pb.get_board().get(8, 7, 7), Cell::Ship);
+        assert_eq!(ShotResolver::resolve_shot(&mut pb, 7, 7), ShotOutcome::Hit);
+        assert_eq!(ShotResolver::resolve_shot(&mut pb, 7, 6), ShotOutcome::Hit);
+        assert!(pb.sunk_ship_at(7, 7).is_some());
+
+        // Column 8 exists on the classic board but not on this one.
+        let mut pb = PlayerBoard::with_size(8);
+        let err = pb
+            .place_ships(fleet(&[
+                "3,0;4,0;5,0;6,0;7,0",
+                "0,2;1,2;2,2;3,2",
+                "0,4;1,4;2,4",
+                "0,6;1,6;2,6",
+                "8,6,2,v",
+            ]))
+            .unwrap_err();
+        assert_eq!(err, GameError::Invalid("ship off board".into()));
+    }
+
+    fn classic_fleet() -> Vec<String> {
+        CLASSIC_TEST_FLEET.map(String::from).to_vec()
+    }
+
+    #[test]
+    fn place_ships_counts_fleet_cells() {
+        let mut pb = PlayerBoard::new();
+        pb.place_ships(classic_fleet()).unwrap();
+        assert!(pb.is_placed());
+        assert_eq!(pb.get_ship_count(), 17);
+    }
+
+    #[test]
+    fn ship_count_must_match_the_stamped_cells() {
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        board.set(BOARD_SIZE, 0, 0, Cell::Ship);
+        board.set(BOARD_SIZE, 1, 0, Cell::Ship);
+        assert!(PlayerBoard::check_ship_count(&board, 2).is_ok());
+        let err = PlayerBoard::check_ship_count(&board, 3).unwrap_err();
+        assert_eq!(err, GameError::Invalid("ship count mismatch".into()));
+    }
+
+    #[test]
+    fn place_ships_rejects_empty_and_blank_lists() {
+        for input in [vec![], vec!["".to_string(), "  ".to_string()]] {
+            let mut pb = PlayerBoard::new();
+            let err = pb.place_ships(input).unwrap_err();
+            assert_eq!(err, GameError::Invalid("no ships".into()));
+            assert!(!pb.is_placed());
+            assert_eq!(pb.get_ship_count(), 0);
+            assert!(pb.get_board().0.iter().all(|&c| c == Cell::Empty.to_u8()));
+        }
+    }
+
+    #[test]
+    fn place_ships_leaves_board_untouched_when_a_later_ship_fails() {
+        let mut pb = PlayerBoard::new();
+        let input = vec![
+            "0,0;1,0;2,0;3,0;4,0".to_string(),
+            // Diagonally touches the carrier.
+            "5,1;6,1;7,1;8,1".to_string(),
+        ];
+        assert!(pb.place_ships(input).is_err());
+        assert!(pb.get_board().0.iter().all(|&c| c == Cell::Empty.to_u8()));
+        assert_eq!(pb.get_ship_count(), 0);
+    }
+
+    #[test]
+    fn place_ships_is_atomic_on_composition_failure() {
+        let mut pb = PlayerBoard::new();
+        // Every ship is well-formed and well-spaced; the carrier is missing.
+        let mut input = classic_fleet();
+        input.remove(0);
+        assert!(pb.place_ships(input).is_err());
+        assert!(!pb.is_placed());
+        assert_eq!(pb.get_ship_count(), 0);
+        assert!(pb.get_board().0.iter().all(|&c| c == Cell::Empty.to_u8()));
+
+        // The same board still accepts a valid fleet afterwards.
+        pb.place_ships(classic_fleet()).unwrap();
+        assert_eq!(pb.get_ship_count(), 17);
+    }
+
+    #[test]
+    fn wrapping_boards_let_ships_cross_the_edge() {
+        let mut fleet = classic_fleet();
+        fleet[0] = "7,0;8,0;9,0;0,0;1,0".to_string();
+
+        let mut flat = PlayerBoard::new();
+        assert_eq!(
+            flat.place_ships(fleet.clone()).unwrap_err(),
+            GameError::Invalid("ship must be contiguous".into())
+        );
+
+        let mut torus = PlayerBoard::with_topology(BOARD_SIZE, true);
+        torus.place_ships(fleet.clone()).unwrap();
+        assert_eq!(torus.get_ship_count(), 17);
+        for x in [7, 8, 9, 0] {
+            ShotResolver::resolve_shot(&mut torus, x, 0);
+            assert!(torus.sunk_ship_at(x, 0).is_none());
+        }
+        ShotResolver::resolve_shot(&mut torus, 1, 0);
+        assert_eq!(torus.sunk_ship_at(1, 0).unwrap().len(), 5);
+        assert_eq!(torus.sunk_ships().len(), 1);
+
+        // The bottom row touches the carrier across the top edge.
+        fleet[4] = "5,9;6,9".to_string();
+        let mut torus = PlayerBoard::with_topology(BOARD_SIZE, true);
+        assert_eq!(
+            torus.place_ships(fleet).unwrap_err(),
+            GameError::Invalid("adjacent to existing ship".into())
+        );
+    }
+
+    #[test]
+    fn resolve_shot_classifies_repeat_shots_as_redundant() {
+        let mut pb = PlayerBoard::new();
+        pb.place_ships(classic_fleet()).unwrap();
+
+        assert_eq!(ShotResolver::resolve_shot(&mut pb, 0, 0), ShotOutcome::Hit);
+        assert_eq!(pb.get_ship_count(), 16);
+        assert_eq!(
+            ShotResolver::resolve_shot(&mut pb, 0, 0),
+            ShotOutcome::Redundant(Cell::Hit)
+        );
+        assert_eq!(pb.get_ship_count(), 16);
+
+        assert_eq!(ShotResolver::resolve_shot(&mut pb, 9, 9), ShotOutcome::Miss);
+        let again = ShotResolver::resolve_shot(&mut pb, 9, 9);
+        assert_eq!(again, ShotOutcome::Redundant(Cell::Miss));
+        assert_eq!(again.recorded_cell(), Cell::Miss);
+        assert_eq!(pb.get_board().get(BOARD_SIZE, 9, 9), Cell::Miss);
+    }
+
     #[test]
     fn capture_pristine_snapshots_current_own_board() {
         let mut pb = PlayerBoard::new();
@@ -0,0 +1,22 @@
+//! # Rules Module
+//!
+//! The per-match rule set clients need in order to render and play a game:
+//! board size and fleet (`PlacementRules`), and how the match is played
+//! and won (`GameRules`, `WinCondition`). The types are defined in
+//! `battleships_types::rules`, so the lobby can store them in match
+//! templates, and re-exported here.
+//!
+//! ## Usage Examples
+//!
+//! ```rust
+//! use battleship::rules::PlacementRules;
+//!
+//! let rules = PlacementRules::default();
+//! assert_eq!(rules.board_size, 10);
+//! assert_eq!(rules.total_ship_cells(), 17);
+//! ```
+
+pub use battleships_types::rules::{
+    GameRules, PlacementRules, WinCondition, CLASSIC_BOARD_SIZE, CLASSIC_FLEET,
+    DEFAULT_SHOT_TIMEOUT_MS, MAX_BOARD_SIZE, MIN_BOARD_SIZE, MIN_WRAPPED_BOARD_SIZE,
+};
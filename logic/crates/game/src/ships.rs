@@ -15,6 +15,7 @@
 //! - Length between 2 and 5 cells
 //! - Must be straight (horizontal or vertical)
 //! - Must be contiguous (no gaps)
+//! - Cannot name the same cell twice
 //! - Cannot overlap with other ships
 //! - Cannot be adjacent to other ships
 //!
@@ -30,32 +31,36 @@
 //!
 //! ### Creating a Ship
 //! ```rust
-//! use battleship::board::Coordinate;
+//! use battleship::board::{Coordinate, BOARD_SIZE};
 //! use battleship::ships::Ship;
 //!
 //! let coords = vec![
-//!     Coordinate::new(0, 0).unwrap(),
-//!     Coordinate::new(0, 1).unwrap(),
-//!     Coordinate::new(0, 2).unwrap(),
+//!     Coordinate::new(0, 0, BOARD_SIZE).unwrap(),
+//!     Coordinate::new(0, 1, BOARD_SIZE).unwrap(),
+//!     Coordinate::new(0, 2, BOARD_SIZE).unwrap(),
 //! ];
-//! let ship = Ship::new(coords)?;
+//! let ship = Ship::new(coords, BOARD_SIZE)?;
 //! assert_eq!(ship.length, 3);
 //! ```
 //!
 //! ### Creating a Fleet
 //! ```rust
 //! use battleship::ships::{Ship, Fleet};
-//! use battleship::board::Coordinate;
+//! use battleship::board::{Coordinate, BOARD_SIZE};
 //!
+//! let cells = vec![Coordinate::new(0, 0, BOARD_SIZE)?, Coordinate::new(0, 1, BOARD_SIZE)?];
 //! let ships = vec![
-//!     Ship::new(vec![Coordinate::new(0, 0).unwrap(), Coordinate::new(0, 1).unwrap()])?,
+//!     Ship::new(cells, BOARD_SIZE)?,
 //!     // ... more ships
 //! ];
-//! let fleet = Fleet::new(ships)?;
+//! let fleet = Fleet::new(ships, BOARD_SIZE)?;
 //! ```
 
-use crate::board::{Board, Coordinate};
-use crate::validation::{validate_fleet_composition, validate_ship_placement};
+use crate::board::{is_contiguous_line, line_coordinates, Board, Coordinate, Orientation};
+use crate::validation::{
+    validate_fleet_composition, validate_ship_placement, FleetCompositionValidationStrategy,
+    ValidationInput, ValidationStrategy,
+};
 use battleships_types::GameError;
 use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use calimero_sdk::serde::{Deserialize, Serialize};
@@ -82,15 +87,15 @@ use calimero_sdk::serde::{Deserialize, Serialize};
 ///
 /// # Example
 /// ```rust
-/// use battleship::board::Coordinate;
+/// use battleship::board::{Coordinate, BOARD_SIZE};
 /// use battleship::ships::Ship;
 ///
 /// let coords = vec![
-///     Coordinate::new(0, 0).unwrap(),
-///     Coordinate::new(0, 1).unwrap(),
-///     Coordinate::new(0, 2).unwrap(),
+///     Coordinate::new(0, 0, BOARD_SIZE).unwrap(),
+///     Coordinate::new(0, 1, BOARD_SIZE).unwrap(),
+///     Coordinate::new(0, 2, BOARD_SIZE).unwrap(),
 /// ];
-/// let ship = Ship::new(coords)?;
+/// let ship = Ship::new(coords, BOARD_SIZE)?;
 /// assert_eq!(ship.length, 3);
 /// assert!(ship.is_straight());
 /// assert!(ship.is_contiguous());
@@ -106,7 +111,9 @@ pub struct Ship {
 }
 
 impl Ship {
-    pub fn new(coordinates: Vec<Coordinate>) -> Result<Ship, GameError> {
+    /// A ship over `coordinates`, every one of them on a `size`x`size`
+    /// board.
+    pub fn new(coordinates: Vec<Coordinate>, size: u8) -> Result<Ship, GameError> {
         if coordinates.is_empty() {
             return Err(GameError::Invalid("ship cannot be empty".into()));
         }
@@ -118,12 +125,13 @@ impl Ship {
 
         // Validate all coordinates are valid
         for coord in &coordinates {
-            if !coord.is_valid() {
+            if !coord.is_valid(size) {
                 return Err(GameError::Invalid(
                     "ship contains invalid coordinates".into(),
                 ));
             }
         }
+        reject_duplicate_coordinates(&coordinates)?;
 
         Ok(Ship {
             coordinates,
@@ -131,6 +139,28 @@ impl Ship {
         })
     }
 
+    /// Re-checks what `new` guarantees, for a `Ship` that didn't come from
+    /// it (a deserialized blob can carry any `length`): the length field
+    /// matches the coordinate count, and the cells form a straight,
+    /// contiguous line on a `size`-wide board. On a board that wraps at
+    /// its edges, a ship crossing the edge is still contiguous.
+    pub fn validate_on(&self, size: u8, wrap: bool) -> Result<(), GameError> {
+        if self.length as usize != self.coordinates.len() {
+            return Err(GameError::Invalid(format!(
+                "ship length {} does not match its {} coordinates",
+                self.length,
+                self.coordinates.len()
+            )));
+        }
+        if !self.is_straight() {
+            return Err(GameError::Invalid("ship is not straight".into()));
+        }
+        if !is_contiguous_line(&self.coordinates, size, wrap) {
+            return Err(GameError::Invalid("ship is not contiguous".into()));
+        }
+        Ok(())
+    }
+
     pub fn is_straight(&self) -> bool {
         if self.coordinates.len() <= 1 {
             return true;
@@ -227,13 +257,14 @@ impl Ship {
 /// # Example
 /// ```rust
 /// use battleship::ships::{Ship, Fleet};
-/// use battleship::board::Coordinate;
+/// use battleship::board::{Coordinate, BOARD_SIZE};
 ///
+/// let cells = vec![Coordinate::new(0, 0, BOARD_SIZE)?, Coordinate::new(0, 1, BOARD_SIZE)?];
 /// let ships = vec![
-///     Ship::new(vec![Coordinate::new(0, 0).unwrap(), Coordinate::new(0, 1).unwrap()])?,
+///     Ship::new(cells, BOARD_SIZE)?,
 ///     // ... more ships to complete the fleet
 /// ];
-/// let fleet = Fleet::new(ships)?;
+/// let fleet = Fleet::new(ships, BOARD_SIZE)?;
 /// assert_eq!(fleet.total_ships(), 5);
 /// ```
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
@@ -245,11 +276,13 @@ pub struct Fleet {
 }
 
 impl Fleet {
-    pub fn new(ships: Vec<Ship>) -> Result<Fleet, GameError> {
+    /// A fleet of `ships` on a `size`x`size` board that doesn't wrap.
+    pub fn new(ships: Vec<Ship>, size: u8) -> Result<Fleet, GameError> {
         // Calculate ship counts for validation
         let mut ship_counts = [0; 4]; // [2,3,4,5] lengths
 
         for ship in &ships {
+            ship.validate_on(size, false)?;
             let idx = (ship.length - 2) as usize;
             if idx >= 4 {
                 return Err(GameError::Invalid("invalid ship length".into()));
@@ -262,7 +295,7 @@ impl Fleet {
             ships.iter().map(|ship| ship.coordinates.clone()).collect();
 
         // Use the validation strategy pattern for fleet composition
-        validate_fleet_composition(ship_counts, ship_coordinates)?;
+        validate_fleet_composition(ship_counts, ship_coordinates, size, false)?;
 
         Ok(Fleet { ships })
     }
@@ -274,6 +307,55 @@ impl Fleet {
     pub fn get_ship_count(&self) -> u64 {
         self.ships.len() as u64
     }
+
+    /// Number of board cells the fleet occupies, i.e. the number of hits
+    /// needed to sink all of it.
+    pub fn total_cells(&self) -> usize {
+        self.ships.iter().map(|ship| ship.length as usize).sum()
+    }
+
+    /// Puts the fleet in its canonical order: each ship's coordinates
+    /// sorted, and ships sorted by their smallest coordinate. Two fleets
+    /// holding the same ships then serialize to the same bytes, whatever
+    /// order they were placed in.
+    pub fn canonicalize(&mut self) {
+        for ship in &mut self.ships {
+            ship.coordinates.sort();
+        }
+        self.ships
+            .sort_by_key(|ship| ship.coordinates.first().copied());
+    }
+
+    /// Smallest box holding every ship, as its top-left and bottom-right
+    /// corners, or `None` for an empty fleet. A ship wrapping a torus edge
+    /// stretches the box across the whole board.
+    pub fn bounding_box(&self) -> Option<(Coordinate, Coordinate)> {
+        let mut cells = self.ships.iter().flat_map(|ship| &ship.coordinates);
+        let first = *cells.next()?;
+        Some(cells.fold((first, first), |(min, max), c| {
+            (
+                Coordinate {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                Coordinate {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            )
+        }))
+    }
+}
+
+/// A repeated cell would inflate a ship's length without covering more of the
+/// board, so it is rejected rather than silently deduplicated.
+fn reject_duplicate_coordinates(coords: &[Coordinate]) -> Result<(), GameError> {
+    for (i, coord) in coords.iter().enumerate() {
+        if coords[..i].contains(coord) {
+            return Err(GameError::Invalid("duplicate coordinate in ship".into()));
+        }
+    }
+    Ok(())
 }
 
 // ============================================================================
@@ -288,25 +370,142 @@ impl ShipValidator {
         board: &Board,
         size: u8,
         coords: &[Coordinate],
+        wrap: bool,
     ) -> Result<(), GameError> {
         if coords.is_empty() {
             return Err(GameError::Invalid("empty ship".into()));
         }
 
         // Use the validation strategy pattern
-        validate_ship_placement(board, coords, size)
+        validate_ship_placement(board, coords, size, wrap)
+    }
+
+    /// Every rule the ship `group` breaks on its own, ignoring the rest of
+    /// the fleet: `"malformed"`, `"bounds"`, `"duplicate"`, `"length"`,
+    /// `"straight"` or `"contiguous"`. Empty when the ship is fine by
+    /// itself. Unlike `parse_ship_coords`, cells off the board are kept
+    /// (and reported) rather than dropped. Also returns the cells read, for
+    /// the fleet-level checks of `fleet_conflicts`.
+    pub fn ship_violations(
+        group: &str,
+        size: u8,
+        wrap: bool,
+    ) -> (Vec<&'static str>, Vec<Coordinate>) {
+        let tokens: Vec<&str> = group.split([',', ':']).map(str::trim).collect();
+        let coords = if tokens.len() == 4 && !group.contains([';', '|']) {
+            match Self::expand_shorthand(&tokens, size) {
+                Ok(coords) => coords,
+                Err(GameError::Invalid(m)) if m == "ship off board" => {
+                    return (vec!["bounds"], Vec::new())
+                }
+                Err(_) => return (vec!["malformed"], Vec::new()),
+            }
+        } else {
+            let parsed: Option<Vec<Coordinate>> = group
+                .split([';', '|'])
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(|p| {
+                    let mut it = p.split([',', ':']).map(str::trim);
+                    let x = it.next()?.parse().ok()?;
+                    let y = it.next()?.parse().ok()?;
+                    Some(Coordinate { x, y })
+                })
+                .collect();
+            match parsed {
+                Some(coords) => coords,
+                None => return (vec!["malformed"], Vec::new()),
+            }
+        };
+
+        let mut violations = Vec::new();
+        if coords.iter().any(|c| c.x >= size || c.y >= size) {
+            violations.push("bounds");
+        }
+        if reject_duplicate_coordinates(&coords).is_err() {
+            violations.push("duplicate");
+        }
+        if !(2..=5).contains(&coords.len()) {
+            violations.push("length");
+        }
+        if let [first, rest @ ..] = coords.as_slice() {
+            let same_x = rest.iter().all(|c| c.x == first.x);
+            let same_y = rest.iter().all(|c| c.y == first.y);
+            if !rest.is_empty() && !(same_x ^ same_y) {
+                violations.push("straight");
+            } else if !is_contiguous_line(&coords, size, wrap) {
+                violations.push("contiguous");
+            }
+        }
+        (violations, coords)
+    }
+
+    /// Problems between the ships of a fleet, each sound on its own: pairs
+    /// that overlap or touch (across the edge too when `wrap` is set), and
+    /// a fleet that isn't the classic composition. `ships[i]` is ship `i`;
+    /// an empty entry stands in for a ship left out of the comparison.
+    pub fn fleet_conflicts(ships: &[Vec<Coordinate>], size: u8, wrap: bool) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        for i in 0..ships.len() {
+            for j in i + 1..ships.len() {
+                let pairs = || {
+                    ships[i]
+                        .iter()
+                        .flat_map(move |&a| ships[j].iter().map(move |&b| (a, b)))
+                };
+                if let Some((c, _)) = pairs().find(|(a, b)| a == b) {
+                    conflicts.push(format!("ships {i} and {j} overlap at ({},{})", c.x, c.y));
+                } else if pairs().any(|(a, b)| a.touches(&b, size, wrap)) {
+                    conflicts.push(format!("ships {i} and {j} are adjacent"));
+                }
+            }
+        }
+
+        let mut counts = [0usize; 4];
+        for ship in ships {
+            if let Some(count) = ship.len().checked_sub(2).and_then(|i| counts.get_mut(i)) {
+                *count += 1;
+            }
+        }
+        let input = ValidationInput::new().with_fleet_composition(counts);
+        if let Err(e) = FleetCompositionValidationStrategy.validate(&input) {
+            conflicts.push(e.to_string());
+        }
+        conflicts
     }
 
     /// Parses ship coordinates from a string format
-    pub fn parse_ship_coords(group: &str) -> Result<Vec<Coordinate>, GameError> {
+    ///
+    /// Grammar (delimiters may be mixed freely within one string):
+    ///
+    /// ```text
+    /// ship      := shorthand | coord ( seg coord )* seg?
+    /// shorthand := ws* x ws* sep ws* y ws* sep ws* len ws* sep ws* dir ws*
+    /// coord     := ws* x ws* sep ws* y ws*
+    /// seg       := ';' | '|'
+    /// sep       := ',' | ':'
+    /// dir       := 'h' | 'v' | 'H' | 'V'
+    /// ```
+    ///
+    /// `x`, `y` and `len` are decimal `u8` values. A shorthand names the
+    /// top/left cell, the length, and the direction, and is expanded by
+    /// `expand_shorthand` (so it is rejected if it runs off the board). In
+    /// the explicit form, empty segments and segments that don't parse to a
+    /// coordinate on the `size`x`size` board are skipped, and naming the
+    /// same cell twice is an error.
+    pub fn parse_ship_coords(group: &str, size: u8) -> Result<Vec<Coordinate>, GameError> {
+        let tokens: Vec<&str> = group.split([',', ':']).map(str::trim).collect();
+        if tokens.len() == 4 && !group.contains([';', '|']) {
+            return Self::expand_shorthand(&tokens, size);
+        }
         let coords: Vec<Coordinate> = group
-            .split(';')
+            .split([';', '|'])
             .filter_map(|p| {
                 let p = p.trim();
                 if p.is_empty() {
                     return None;
                 }
-                let mut it = p.split(',');
+                let mut it = p.split([',', ':']).map(str::trim);
                 let sx = it.next().unwrap_or("");
                 let sy = it.next().unwrap_or("");
                 let x: u8 = match sx.parse() {
@@ -317,9 +516,198 @@ impl ShipValidator {
                     Ok(v) => v,
                     Err(_) => return None,
                 };
-                Coordinate::new(x, y).ok()
+                Coordinate::new(x, y, size).ok()
             })
             .collect();
+        reject_duplicate_coordinates(&coords)?;
         Ok(coords)
     }
+
+    /// Expands `[x, y, len, dir]` into the ship's cells via
+    /// `line_coordinates`; a ship that would leave the `size`x`size` board
+    /// is an error, never clamped or wrapped.
+    fn expand_shorthand(tokens: &[&str], size: u8) -> Result<Vec<Coordinate>, GameError> {
+        let malformed = || GameError::Invalid("malformed ship shorthand".into());
+        let x: u8 = tokens[0].parse().map_err(|_| malformed())?;
+        let y: u8 = tokens[1].parse().map_err(|_| malformed())?;
+        let len: u8 = tokens[2].parse().map_err(|_| malformed())?;
+        let orientation = match tokens[3] {
+            "h" | "H" => Orientation::Horizontal,
+            "v" | "V" => Orientation::Vertical,
+            _ => return Err(malformed()),
+        };
+        line_coordinates(Coordinate { x, y }, orientation, len, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BOARD_SIZE;
+
+    fn ship(cells: &[(u8, u8)]) -> Ship {
+        Ship::new(
+            cells
+                .iter()
+                .map(|&(x, y)| Coordinate::new(x, y, BOARD_SIZE).unwrap())
+                .collect(),
+            BOARD_SIZE,
+        )
+        .unwrap()
+    }
+
+    fn coords(cells: &[(u8, u8)]) -> Vec<Coordinate> {
+        cells.iter().map(|&(x, y)| Coordinate { x, y }).collect()
+    }
+
+    #[test]
+    fn validate_rejects_ships_that_bypassed_new() {
+        assert!(ship(&[(0, 0), (1, 0), (2, 0)])
+            .validate_on(BOARD_SIZE, false)
+            .is_ok());
+
+        let mut lying = ship(&[(0, 0), (1, 0)]);
+        lying.length = 5;
+        let err = lying.validate_on(BOARD_SIZE, false).unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("does not match")));
+        // Fleet construction runs the same check instead of trusting `length`.
+        assert!(Fleet::new(vec![lying], BOARD_SIZE).is_err());
+
+        let bent = Ship {
+            coordinates: coords(&[(0, 0), (1, 0), (1, 1)]),
+            length: 3,
+        };
+        assert!(
+            matches!(bent.validate_on(BOARD_SIZE, false), Err(GameError::Invalid(ref m)) if m.contains("straight"))
+        );
+        let gapped = Ship {
+            coordinates: coords(&[(0, 0), (2, 0)]),
+            length: 2,
+        };
+        assert!(
+            matches!(gapped.validate_on(BOARD_SIZE, false), Err(GameError::Invalid(ref m)) if m.contains("contiguous"))
+        );
+    }
+
+    #[test]
+    fn parse_ship_coords_accepts_each_delimiter_variant() {
+        let expected = coords(&[(0, 0), (0, 1), (0, 2)]);
+        for input in [
+            "0,0;0,1;0,2",
+            "0,0|0,1|0,2",
+            "0:0;0:1;0:2",
+            "0:0|0:1|0:2",
+            " 0 , 0 ; 0 , 1 ; 0 , 2 ;",
+            "0,0|0:1;0 : 2",
+        ] {
+            assert_eq!(
+                ShipValidator::parse_ship_coords(input, BOARD_SIZE).unwrap(),
+                expected,
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_ship_coords_skips_unparseable_segments() {
+        let parsed = ShipValidator::parse_ship_coords("1,1;x,2;;3;10,0;2,1", BOARD_SIZE).unwrap();
+        assert_eq!(parsed, coords(&[(1, 1), (2, 1)]));
+    }
+
+    #[test]
+    fn canonical_fleets_serialize_identically() {
+        let mut a = Fleet {
+            ships: vec![ship(&[(0, 0), (0, 1)]), ship(&[(5, 5), (6, 5), (7, 5)])],
+        };
+        let mut b = Fleet {
+            ships: vec![ship(&[(7, 5), (5, 5), (6, 5)]), ship(&[(0, 1), (0, 0)])],
+        };
+        let bytes = |f: &Fleet| calimero_sdk::borsh::to_vec(f).unwrap();
+        assert_ne!(bytes(&a), bytes(&b));
+
+        a.canonicalize();
+        b.canonicalize();
+        assert_eq!(bytes(&a), bytes(&b));
+        assert_eq!(a.ships[0].coordinates, coords(&[(0, 0), (0, 1)]));
+    }
+
+    #[test]
+    fn duplicate_coordinates_are_rejected_not_counted() {
+        for input in ["0,0;0,0;0,1", "0,0|0,1|0:0"] {
+            let err = ShipValidator::parse_ship_coords(input, BOARD_SIZE).unwrap_err();
+            assert!(
+                matches!(err, GameError::Invalid(ref m) if m == "duplicate coordinate in ship"),
+                "{input:?}"
+            );
+        }
+        let err = Ship::new(coords(&[(3, 3), (3, 4), (3, 3)]), BOARD_SIZE).unwrap_err();
+        assert_eq!(
+            err,
+            GameError::Invalid("duplicate coordinate in ship".into())
+        );
+    }
+
+    #[test]
+    fn shorthand_expands_horizontally_and_vertically() {
+        assert_eq!(
+            ShipValidator::parse_ship_coords("2,3,3,h", BOARD_SIZE).unwrap(),
+            coords(&[(2, 3), (3, 3), (4, 3)])
+        );
+        assert_eq!(
+            ShipValidator::parse_ship_coords(" 0 : 5 : 2 : V ", BOARD_SIZE).unwrap(),
+            coords(&[(0, 5), (0, 6)])
+        );
+        assert!(ShipValidator::parse_ship_coords("0,0,3,d", BOARD_SIZE).is_err());
+    }
+
+    #[test]
+    fn shorthand_running_off_the_board_is_rejected() {
+        for input in ["7,0,5,h", "0,7,5,v", "250,0,10,h"] {
+            let err = ShipValidator::parse_ship_coords(input, BOARD_SIZE).unwrap_err();
+            assert!(
+                matches!(err, GameError::Invalid(ref m) if m == "ship off board"),
+                "{input:?}"
+            );
+        }
+        // Ending exactly on the last column is fine.
+        assert_eq!(
+            ShipValidator::parse_ship_coords("5,0,5,h", BOARD_SIZE)
+                .unwrap()
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn classic_fleet_occupies_seventeen_cells() {
+        let fleet = Fleet::new(
+            vec![
+                ship(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]),
+                ship(&[(0, 2), (1, 2), (2, 2), (3, 2)]),
+                ship(&[(0, 4), (1, 4), (2, 4)]),
+                ship(&[(0, 6), (1, 6), (2, 6)]),
+                ship(&[(0, 8), (1, 8)]),
+            ],
+            BOARD_SIZE,
+        )
+        .unwrap();
+        assert_eq!(fleet.total_ships(), 5);
+        assert_eq!(fleet.total_cells(), 17);
+    }
+
+    #[test]
+    fn bounding_box_spans_opposite_corners() {
+        assert_eq!(Fleet { ships: Vec::new() }.bounding_box(), None);
+
+        let mut fleet = Fleet {
+            ships: vec![ship(&[(3, 4), (3, 5)])],
+        };
+        let (min, max) = fleet.bounding_box().unwrap();
+        assert_eq!(vec![min, max], coords(&[(3, 4), (3, 5)]));
+
+        fleet.ships.push(ship(&[(9, 7), (9, 8), (9, 9)]));
+        fleet.ships.push(ship(&[(0, 0), (1, 0)]));
+        let (min, max) = fleet.bounding_box().unwrap();
+        assert_eq!(vec![min, max], coords(&[(0, 0), (9, 9)]));
+    }
 }
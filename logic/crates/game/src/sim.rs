@@ -0,0 +1,333 @@
+//! # Simulation Module
+//!
+//! A runtime-free driver for whole matches, used by tests to reproduce
+//! game-flow bugs end to end.
+//!
+//! `simulate_game` plays two fleets against each other from scripted shot
+//! lists through a real `GameState`: the side on turn fires with
+//! `propose_shot` (a whole salvo when the rules allow one), and the other
+//! side answers with `acknowledge_shot`, so turns, extra turns, ammunition,
+//! draws and the end-of-match audit all run the production code. The
+//! executor and published commitments come from the test seams
+//! (`set_test_executor`, `set_test_commitment`), and lobby notifications
+//! are skipped as the match has no lobby.
+
+use crate::players::{PlayerBoard, PrivateBoards};
+use crate::rules::GameRules;
+use crate::{
+    board_commitment, set_test_commitment, set_test_executor, write_private_board, GameState,
+    PublicKey,
+};
+use battleships_types::GameError;
+use calimero_sdk::app;
+
+/// Match id the simulated `GameState` is created under.
+const SIM_MATCH: &str = "sim";
+
+/// One of the two simulated players. `A` fires first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    A,
+    B,
+}
+
+impl Side {
+    fn other(self) -> Side {
+        match self {
+            Side::A => Side::B,
+            Side::B => Side::A,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn key(self) -> PublicKey {
+        PublicKey([self as u8 + 1; 32])
+    }
+}
+
+/// What one player did over a simulated match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct PlayerStats {
+    /// Shots fired
+    pub shots: u32,
+    /// Shots that struck an intact ship cell
+    pub hits: u32,
+    /// Opponent ships fully sunk
+    pub ships_sunk: u32,
+}
+
+/// Outcome of `simulate_game`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GameResult {
+    /// The side that won, if the match ended with a winner
+    pub winner: Option<Side>,
+    /// Whether both sides ran out of ammunition
+    pub drawn: bool,
+    /// Stats for `A` and `B`, in that order
+    pub stats: [PlayerStats; 2],
+}
+
+impl GameResult {
+    /// Whether the match reached an end before the scripts ran out.
+    pub fn finished(&self) -> bool {
+        self.winner.is_some() || self.drawn
+    }
+}
+
+/// Plays `fleet_a` against `fleet_b` under `rules`, the side on turn firing
+/// the next coordinates of its script, up to its turn's allowance. Stops as
+/// soon as the match ends, or when the side on turn has no scripted shots
+/// left. Placement errors and shots `propose_shot` refuses (out of bounds,
+/// already shot) are returned as is.
+pub(crate) fn simulate_game(
+    rules: &GameRules,
+    fleet_a: &[&str],
+    fleet_b: &[&str],
+    shots_a: &[(u8, u8)],
+    shots_b: &[(u8, u8)],
+) -> app::Result<GameResult> {
+    let mut state = GameState::init(
+        Side::A.key().to_base58(),
+        Side::B.key().to_base58(),
+        None,
+        SIM_MATCH.into(),
+        Some(rules.clone()),
+        None,
+        None,
+        None,
+    );
+    if state.match_id.get().is_none() {
+        app::bail!(GameError::Invalid("rules can't be played".into()));
+    }
+    let size = state.board_size();
+    let place = |side: Side, fleet: &[&str]| -> app::Result<PlayerBoard> {
+        let mut pb = PlayerBoard::with_topology(size, rules.wrap_edges);
+        pb.place_ships(fleet.iter().map(|s| s.to_string()).collect())?;
+        pb.capture_pristine();
+        set_test_commitment(&side.key(), board_commitment(&pb)?);
+        Ok(pb)
+    };
+    // Both private boards live on this one thread, so the defender's is
+    // swapped in for each acknowledgement and read back after it.
+    let mut boards = [place(Side::A, fleet_a)?, place(Side::B, fleet_b)?];
+    state.placed_p1.set(true);
+    state.placed_p2.set(true);
+    let mut scripts = [shots_a.iter().peekable(), shots_b.iter().peekable()];
+
+    let played = (|| -> app::Result<()> {
+        while !state.is_finished() {
+            let turn = if state.turn.get().as_ref() == Some(&Side::A.key()) {
+                Side::A
+            } else {
+                Side::B
+            };
+            set_test_executor(Some(&turn.key()));
+            let script = &mut scripts[turn.index()];
+            if script.peek().is_none() {
+                break;
+            }
+            while let Some(&&(x, y)) = script.peek() {
+                let full = !state.pending.get().is_empty()
+                    && rules.turn_exhausted(*state.shots_this_turn.get());
+                if full {
+                    break;
+                }
+                state.propose_shot(SIM_MATCH, x, y, None)?;
+                script.next();
+            }
+
+            let defender = turn.other();
+            set_test_executor(Some(&defender.key()));
+            write_private_board(SIM_MATCH, Some(boards[defender.index()].clone()))?;
+            state.acknowledge_shot(SIM_MATCH)?;
+            boards[defender.index()] = PrivateBoards::private_load_or_default()?
+                .boards
+                .get(&PrivateBoards::key(SIM_MATCH))?
+                .ok_or_else(|| GameError::Invalid("defender board vanished".into()))?;
+        }
+        Ok(())
+    })();
+    set_test_executor(None);
+    played?;
+
+    let winner = state.winner.get().as_ref().map(|pk| {
+        if *pk == Side::A.key() {
+            Side::A
+        } else {
+            Side::B
+        }
+    });
+    let stats = |side: Side| -> Result<PlayerStats, GameError> {
+        let hits = match side {
+            Side::A => *state.hits_p1.get(),
+            Side::B => *state.hits_p2.get(),
+        };
+        // Counted on the opponent's board, as the public sunk maps are
+        // only kept under `reveal_on_sink`.
+        let ships_sunk = boards[side.other().index()].sunk_ships().len() as u32;
+        Ok(PlayerStats {
+            shots: state.shots_fired_by(&side.key())?,
+            hits,
+            ships_sunk,
+        })
+    };
+    Ok(GameResult {
+        winner,
+        drawn: *state.drawn.get(),
+        stats: [stats(Side::A)?, stats(Side::B)?],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::WinCondition;
+    use crate::CLASSIC_TEST_FLEET;
+
+    /// Every ship cell of `CLASSIC_TEST_FLEET`, in placement order.
+    fn fleet_cells() -> Vec<(u8, u8)> {
+        let rows = [(0u8, 5u8), (2, 4), (4, 3), (6, 3), (8, 2)];
+        rows.iter()
+            .flat_map(|&(y, len)| (0..len).map(move |x| (x, y)))
+            .collect()
+    }
+
+    /// `n` distinct cells that miss `CLASSIC_TEST_FLEET`.
+    fn misses(n: usize) -> Vec<(u8, u8)> {
+        (0..n as u8).map(|i| (9 - i / 10, i % 10)).collect()
+    }
+
+    #[test]
+    fn sinking_the_whole_fleet_wins_the_classic_game() {
+        let result = simulate_game(
+            &GameRules::default(),
+            &CLASSIC_TEST_FLEET,
+            &CLASSIC_TEST_FLEET,
+            &fleet_cells(),
+            &misses(16),
+        )
+        .unwrap();
+        assert_eq!(result.winner, Some(Side::A));
+        assert_eq!(
+            result.stats[0],
+            PlayerStats {
+                shots: 17,
+                hits: 17,
+                ships_sunk: 5
+            }
+        );
+        // B fired between each of A's shots but never after the last.
+        assert_eq!(result.stats[1].shots, 16);
+    }
+
+    #[test]
+    fn salvo_and_hit_threshold_rules_shape_the_game() {
+        let rules = GameRules {
+            win_condition: WinCondition::FirstToHits(3),
+            shots_per_turn: 2,
+            ..GameRules::default()
+        };
+        let result = simulate_game(
+            &rules,
+            &CLASSIC_TEST_FLEET,
+            &CLASSIC_TEST_FLEET,
+            &[(9, 9), (0, 0), (1, 0), (2, 0)],
+            &fleet_cells(),
+        )
+        .unwrap();
+        // A: miss, hit | B: hit, hit | A: hit, hit -> third hit wins.
+        assert_eq!(result.winner, Some(Side::A));
+        assert_eq!((result.stats[0].shots, result.stats[0].hits), (4, 3));
+        assert_eq!((result.stats[1].shots, result.stats[1].hits), (2, 2));
+    }
+
+    #[test]
+    fn extra_turns_keep_the_shooter_firing() {
+        let rules = GameRules {
+            extra_turn_on_hit: true,
+            ..GameRules::default()
+        };
+        // A hits (0,0)..(3,0) in a row, then sinks the carrier at (4,0),
+        // which doesn't earn a turn, so B fires once before A goes on.
+        let mut shots_a = fleet_cells()[..5].to_vec();
+        shots_a.push((9, 9));
+        let result = simulate_game(
+            &rules,
+            &CLASSIC_TEST_FLEET,
+            &CLASSIC_TEST_FLEET,
+            &shots_a,
+            &misses(1),
+        )
+        .unwrap();
+        assert_eq!(result.stats[0].shots, 6);
+        assert_eq!(result.stats[1].shots, 1);
+        assert_eq!(result.stats[0].ships_sunk, 1);
+
+        let on_sink = GameRules {
+            extra_turn_on_sink: true,
+            ..GameRules::default()
+        };
+        // Under salvo rules one sinking shot in the batch keeps the turn.
+        let salvo = GameRules {
+            shots_per_turn: 2,
+            ..on_sink
+        };
+        let result = simulate_game(
+            &salvo,
+            &CLASSIC_TEST_FLEET,
+            &CLASSIC_TEST_FLEET,
+            &[(0, 8), (1, 8), (9, 9), (9, 8)],
+            &[],
+        )
+        .unwrap();
+        assert_eq!(result.stats[0].shots, 4);
+        assert_eq!(result.stats[1].shots, 0);
+    }
+
+    #[test]
+    fn running_out_of_ammo_lets_the_opponent_finish_then_draws() {
+        let rules = GameRules {
+            max_shots: Some(3),
+            ..GameRules::default()
+        };
+        let result = simulate_game(
+            &rules,
+            &CLASSIC_TEST_FLEET,
+            &CLASSIC_TEST_FLEET,
+            &misses(3),
+            &misses(3),
+        )
+        .unwrap();
+        // A fires first and is dry first, but B still fires its third shot.
+        assert_eq!(result.winner, None);
+        assert!(result.drawn);
+        assert_eq!((result.stats[0].shots, result.stats[1].shots), (3, 3));
+
+        let unfinished = simulate_game(
+            &GameRules::default(),
+            &CLASSIC_TEST_FLEET,
+            &CLASSIC_TEST_FLEET,
+            &misses(2),
+            &[],
+        )
+        .unwrap();
+        assert!(!unfinished.finished());
+    }
+
+    #[test]
+    fn repeated_shots_are_refused_like_propose_shot() {
+        let err = simulate_game(
+            &GameRules::default(),
+            &CLASSIC_TEST_FLEET,
+            &CLASSIC_TEST_FLEET,
+            &[(0, 0), (0, 0)],
+            &misses(2),
+        )
+        .unwrap_err();
+        let err = format!("{err:?}");
+        assert!(err.contains("already shot"), "{err}");
+    }
+}
@@ -23,7 +23,7 @@
 //!     Coordinate::new(0, 1).unwrap(),
 //!     Coordinate::new(0, 2).unwrap(),
 //! ];
-//! let result = validate_ship_placement(&board, &coordinates, BOARD_SIZE);
+//! let result = validate_ship_placement(&board, &coordinates, BOARD_SIZE, false);
 //! ```
 //!
 //! ### Creating Custom Validation Contexts
@@ -80,8 +80,8 @@
 //! - **Single Responsibility**: Each strategy has one clear purpose
 //! - **Open/Closed Principle**: Open for extension, closed for modification
 
-use crate::board::{Board, Cell, Coordinate, BOARD_SIZE};
-use battleships_types::GameError;
+use crate::board::{is_contiguous_line, Board, Coordinate, BOARD_SIZE};
+use battleships_types::{GameError, ShipLengthCount};
 
 // ============================================================================
 // VALIDATION STRATEGY PATTERN
@@ -125,8 +125,26 @@ pub trait ValidationStrategy {
     ///
     /// This is used for debugging and logging purposes.
     fn name(&self) -> &'static str;
+
+    /// Rough cost of this strategy, one of the `PRIORITY_*` tiers.
+    ///
+    /// `ValidationContext` runs cheaper strategies first, so obviously
+    /// invalid input fails before any pairwise work. Strategies that don't
+    /// say are assumed to scan the board.
+    fn priority(&self) -> u8 {
+        PRIORITY_BOARD
+    }
 }
 
+/// Constant time or a single pass over the input: bounds, lengths, counts.
+pub const PRIORITY_CHEAP: u8 = 0;
+/// Sorts or de-duplicates the input.
+pub const PRIORITY_SORTING: u8 = 10;
+/// Looks at the board around every coordinate.
+pub const PRIORITY_BOARD: u8 = 20;
+/// Compares every pair of ships; grows quadratically with the fleet.
+pub const PRIORITY_PAIRWISE: u8 = 30;
+
 /// Input data for validation strategies
 ///
 /// This struct provides a flexible way to pass different types of data
@@ -157,6 +175,8 @@ pub struct ValidationInput {
     pub fleet_composition: Option<[usize; 4]>,
     /// Multiple ship coordinate sets for fleet validation
     pub ships: Option<Vec<Vec<Coordinate>>>,
+    /// Whether the board wraps at its edges, for adjacency and contiguity
+    pub wrap: bool,
 }
 
 impl Default for ValidationInput {
@@ -174,6 +194,7 @@ impl ValidationInput {
             ship_length: None,
             fleet_composition: None,
             ships: None,
+            wrap: false,
         }
     }
 
@@ -206,6 +227,11 @@ impl ValidationInput {
         self.ships = Some(ships);
         self
     }
+
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
 }
 
 // ============================================================================
@@ -236,6 +262,10 @@ impl ValidationStrategy for BoundsValidationStrategy {
     fn name(&self) -> &'static str {
         "BoundsValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_CHEAP
+    }
 }
 
 /// Validates that coordinates are unique (no duplicates)
@@ -262,6 +292,10 @@ impl ValidationStrategy for UniquenessValidationStrategy {
     fn name(&self) -> &'static str {
         "UniquenessValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_SORTING
+    }
 }
 
 /// Validates that ship placement doesn't overlap with existing ships
@@ -278,8 +312,11 @@ impl ValidationStrategy for OverlapValidationStrategy {
         let size = input.size.unwrap_or(BOARD_SIZE);
 
         for &coord in coordinates {
-            if matches!(board.get(size, coord.x, coord.y), Cell::Ship) {
-                return Err(GameError::Invalid("overlap with existing ship".into()));
+            if board.get(size, coord.x, coord.y).is_ship() {
+                return Err(GameError::Overlap {
+                    x: coord.x,
+                    y: coord.y,
+                });
             }
         }
         Ok(())
@@ -288,6 +325,10 @@ impl ValidationStrategy for OverlapValidationStrategy {
     fn name(&self) -> &'static str {
         "OverlapValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_BOARD
+    }
 }
 
 /// Validates that ships are not adjacent to each other
@@ -304,7 +345,7 @@ impl ValidationStrategy for AdjacencyValidationStrategy {
         let size = input.size.unwrap_or(BOARD_SIZE);
 
         for &coord in coordinates {
-            if board.is_adjacent_violation(size, coord.x, coord.y) {
+            if board.is_adjacent_violation(size, coord.x, coord.y, input.wrap) {
                 return Err(GameError::Invalid("adjacent to existing ship".into()));
             }
         }
@@ -314,6 +355,10 @@ impl ValidationStrategy for AdjacencyValidationStrategy {
     fn name(&self) -> &'static str {
         "AdjacencyValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_BOARD
+    }
 }
 
 /// Validates that ship is in a straight line
@@ -343,6 +388,10 @@ impl ValidationStrategy for StraightLineValidationStrategy {
     fn name(&self) -> &'static str {
         "StraightLineValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_CHEAP
+    }
 }
 
 /// Validates that ship coordinates are contiguous
@@ -357,6 +406,13 @@ impl ValidationStrategy for ContiguityValidationStrategy {
         if coordinates.len() <= 1 {
             return Ok(());
         }
+        if input.wrap {
+            let size = input.size.unwrap_or(BOARD_SIZE);
+            if !is_contiguous_line(coordinates, size, true) {
+                return Err(GameError::Invalid("ship must be contiguous".into()));
+            }
+            return Ok(());
+        }
 
         let same_x = coordinates.iter().all(|coord| coord.x == coordinates[0].x);
         let mut sorted = coordinates.clone();
@@ -382,6 +438,10 @@ impl ValidationStrategy for ContiguityValidationStrategy {
     fn name(&self) -> &'static str {
         "ContiguityValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_SORTING
+    }
 }
 
 /// Validates ship length is within acceptable range
@@ -407,11 +467,18 @@ impl ValidationStrategy for ShipLengthValidationStrategy {
     fn name(&self) -> &'static str {
         "ShipLengthValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_CHEAP
+    }
 }
 
 /// Validates fleet composition follows standard battleship rules
 pub struct FleetCompositionValidationStrategy;
 
+/// Ships required of each length 2..=5, indexed like `fleet_composition`.
+const CLASSIC_COMPOSITION: [u32; 4] = [1, 2, 1, 1];
+
 impl ValidationStrategy for FleetCompositionValidationStrategy {
     fn validate(&self, input: &ValidationInput) -> Result<(), GameError> {
         let composition = input.fleet_composition.ok_or({
@@ -419,19 +486,18 @@ impl ValidationStrategy for FleetCompositionValidationStrategy {
         })?;
 
         // Standard battleship fleet: 1x5, 1x4, 2x3, 1x2
-        if composition[3] != 1 {
-            return Err(GameError::Invalid("need exactly 1 ship of length 5".into()));
-        }
-        if composition[2] != 1 {
-            return Err(GameError::Invalid("need exactly 1 ship of length 4".into()));
-        }
-        if composition[1] != 2 {
-            return Err(GameError::Invalid(
-                "need exactly 2 ships of length 3".into(),
-            ));
-        }
-        if composition[0] != 1 {
-            return Err(GameError::Invalid("need exactly 1 ship of length 2".into()));
+        let counts: Vec<ShipLengthCount> = CLASSIC_COMPOSITION
+            .iter()
+            .zip(composition)
+            .enumerate()
+            .map(|(idx, (&expected, actual))| ShipLengthCount {
+                length: idx as u8 + 2,
+                expected,
+                actual: actual as u32,
+            })
+            .collect();
+        if counts.iter().any(|c| c.missing() != 0) {
+            return Err(GameError::FleetComposition(counts));
         }
         Ok(())
     }
@@ -439,6 +505,10 @@ impl ValidationStrategy for FleetCompositionValidationStrategy {
     fn name(&self) -> &'static str {
         "FleetCompositionValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_CHEAP
+    }
 }
 
 /// Validates that ships don't overlap with each other
@@ -455,7 +525,10 @@ impl ValidationStrategy for ShipOverlapValidationStrategy {
                 for coord1 in &ships[i] {
                     for coord2 in &ships[j] {
                         if coord1 == coord2 {
-                            return Err(GameError::Invalid("ships overlap".into()));
+                            return Err(GameError::Overlap {
+                                x: coord1.x,
+                                y: coord1.y,
+                            });
                         }
                     }
                 }
@@ -467,6 +540,10 @@ impl ValidationStrategy for ShipOverlapValidationStrategy {
     fn name(&self) -> &'static str {
         "ShipOverlapValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_PAIRWISE
+    }
 }
 
 /// Validates that ships are not adjacent to each other
@@ -477,14 +554,15 @@ impl ValidationStrategy for ShipAdjacencyValidationStrategy {
         let ships = input.ships.as_ref().ok_or(GameError::Invalid(
             "ships required for ship adjacency validation".into(),
         ))?;
+        let size = input.size.ok_or(GameError::Invalid(
+            "size required for ship adjacency validation".into(),
+        ))?;
 
         for i in 0..ships.len() {
             for j in (i + 1)..ships.len() {
                 for coord1 in &ships[i] {
                     for coord2 in &ships[j] {
-                        let dx = (coord1.x as i16 - coord2.x as i16).abs();
-                        let dy = (coord1.y as i16 - coord2.y as i16).abs();
-                        if dx <= 1 && dy <= 1 && !(dx == 0 && dy == 0) {
+                        if coord1.touches(coord2, size, input.wrap) {
                             return Err(GameError::Invalid("ships are adjacent".into()));
                         }
                     }
@@ -497,6 +575,10 @@ impl ValidationStrategy for ShipAdjacencyValidationStrategy {
     fn name(&self) -> &'static str {
         "ShipAdjacencyValidation"
     }
+
+    fn priority(&self) -> u8 {
+        PRIORITY_PAIRWISE
+    }
 }
 
 // ============================================================================
@@ -507,7 +589,9 @@ impl ValidationStrategy for ShipAdjacencyValidationStrategy {
 ///
 /// The ValidationContext allows you to compose multiple validation strategies
 /// and execute them together. This provides flexibility in creating custom
-/// validation flows for different use cases.
+/// validation flows for different use cases. Strategies run in order of
+/// `ValidationStrategy::priority`, cheapest first; strategies of equal
+/// priority run in the order they were added.
 ///
 /// # Example
 /// ```rust
@@ -540,13 +624,18 @@ impl ValidationContext {
         }
     }
 
-    /// Adds a validation strategy to the context
+    /// Adds a validation strategy to the context, after every strategy of
+    /// the same or a lower priority
     pub fn add_strategy(mut self, strategy: Box<dyn ValidationStrategy>) -> Self {
-        self.strategies.push(strategy);
+        let at = self
+            .strategies
+            .partition_point(|s| s.priority() <= strategy.priority());
+        self.strategies.insert(at, strategy);
         self
     }
 
-    /// Executes all validation strategies in sequence
+    /// Executes all validation strategies in priority order, stopping at
+    /// the first failure
     pub fn validate(&self, input: &ValidationInput) -> Result<(), GameError> {
         for strategy in &self.strategies {
             strategy.validate(input)?;
@@ -618,6 +707,7 @@ impl ValidationContext {
 /// * `board` - The game board to validate against
 /// * `coordinates` - The ship coordinates to validate
 /// * `size` - The board size for bounds checking
+/// * `wrap` - Whether the board wraps at its edges
 ///
 /// # Returns
 /// * `Ok(())` - Ship placement is valid
@@ -634,17 +724,19 @@ impl ValidationContext {
 ///     Coordinate::new(0, 1).unwrap(),
 ///     Coordinate::new(0, 2).unwrap(),
 /// ];
-/// let result = validate_ship_placement(&board, &coordinates, BOARD_SIZE);
+/// let result = validate_ship_placement(&board, &coordinates, BOARD_SIZE, false);
 /// ```
 pub fn validate_ship_placement(
     board: &Board,
     coordinates: &[Coordinate],
     size: u8,
+    wrap: bool,
 ) -> Result<(), GameError> {
     let input = ValidationInput::new()
         .with_board(board.clone())
         .with_coordinates(coordinates.to_vec())
-        .with_size(size);
+        .with_size(size)
+        .with_wrap(wrap);
 
     ValidationContext::ship_placement().validate(&input)
 }
@@ -660,6 +752,8 @@ pub fn validate_ship_placement(
 /// # Arguments
 /// * `ship_counts` - Array of ship counts by length \[2,3,4,5\]
 /// * `ships` - Vector of ship coordinate sets
+/// * `size` - The board size, for adjacency across a wrapping edge
+/// * `wrap` - Whether the board wraps at its edges
 ///
 /// # Returns
 /// * `Ok(())` - Fleet composition is valid
@@ -668,22 +762,26 @@ pub fn validate_ship_placement(
 /// # Example
 /// ```rust
 /// use crate::validation::validate_fleet_composition;
-/// use crate::board::Coordinate;
+/// use crate::board::{Coordinate, BOARD_SIZE};
 ///
 /// let ship_counts = [1, 2, 1, 1]; // Standard battleship fleet
 /// let ships = vec![
 ///     vec![Coordinate::new(0, 0).unwrap(), Coordinate::new(0, 1).unwrap()], // Length 2
 ///     // ... more ships
 /// ];
-/// let result = validate_fleet_composition(ship_counts, ships);
+/// let result = validate_fleet_composition(ship_counts, ships, BOARD_SIZE, false);
 /// ```
 pub fn validate_fleet_composition(
     ship_counts: [usize; 4],
     ships: Vec<Vec<Coordinate>>,
+    size: u8,
+    wrap: bool,
 ) -> Result<(), GameError> {
     let input = ValidationInput::new()
         .with_fleet_composition(ship_counts)
-        .with_ships(ships);
+        .with_ships(ships)
+        .with_size(size)
+        .with_wrap(wrap);
 
     ValidationContext::fleet_composition().validate(&input)
 }
@@ -721,3 +819,95 @@ pub fn validate_coordinates(coordinates: &[Coordinate], size: u8) -> Result<(),
 
     ValidationContext::coordinates_only().validate(&input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Cell;
+
+    fn cells(coords: &[(u8, u8)]) -> Vec<Coordinate> {
+        coords.iter().map(|&(x, y)| Coordinate { x, y }).collect()
+    }
+
+    #[test]
+    fn overlap_reports_the_shared_cell() {
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        for c in cells(&[(2, 3), (3, 3), (4, 3)]) {
+            board.set(BOARD_SIZE, c.x, c.y, Cell::Ship);
+        }
+        let crossing = ValidationInput::new()
+            .with_board(board)
+            .with_coordinates(cells(&[(4, 1), (4, 2), (4, 3), (4, 4)]));
+        assert!(matches!(
+            OverlapValidationStrategy.validate(&crossing),
+            Err(GameError::Overlap { x: 4, y: 3 })
+        ));
+
+        let ships = ValidationInput::new().with_ships(vec![
+            cells(&[(0, 0), (1, 0), (2, 0)]),
+            cells(&[(5, 5), (5, 6)]),
+            cells(&[(1, 0), (1, 1)]),
+        ]);
+        assert!(matches!(
+            ShipOverlapValidationStrategy.validate(&ships),
+            Err(GameError::Overlap { x: 1, y: 0 })
+        ));
+    }
+
+    #[test]
+    fn contexts_run_cheap_strategies_first() {
+        assert_eq!(
+            ValidationContext::ship_placement().strategy_names(),
+            vec![
+                "BoundsValidation",
+                "StraightLineValidation",
+                "ShipLengthValidation",
+                "UniquenessValidation",
+                "ContiguityValidation",
+                "OverlapValidation",
+                "AdjacencyValidation",
+            ]
+        );
+        assert_eq!(
+            ValidationContext::fleet_composition().strategy_names(),
+            vec![
+                "FleetCompositionValidation",
+                "ShipOverlapValidation",
+                "ShipAdjacencyValidation",
+            ]
+        );
+
+        // A too-long ship sitting on another one is reported as too long:
+        // the length check no longer waits behind the board scans.
+        let mut board = Board::new_zeroed(BOARD_SIZE);
+        board.set(BOARD_SIZE, 0, 0, Cell::Ship);
+        let input = ValidationInput::new()
+            .with_board(board)
+            .with_coordinates(cells(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0)]))
+            .with_size(BOARD_SIZE);
+        let err = ValidationContext::ship_placement()
+            .validate(&input)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            GameError::Invalid("ship length must be between 2 and 5".into())
+        );
+    }
+
+    #[test]
+    fn fleet_composition_error_breaks_down_every_length() {
+        let input = ValidationInput::new().with_fleet_composition([2, 2, 0, 1]);
+        let Err(GameError::FleetComposition(counts)) =
+            FleetCompositionValidationStrategy.validate(&input)
+        else {
+            panic!("expected a composition breakdown");
+        };
+        let diffs: Vec<(u8, i64)> = counts.iter().map(|c| (c.length, c.missing())).collect();
+        assert_eq!(diffs, vec![(2, -1), (3, 0), (4, 1), (5, 0)]);
+
+        let classic = ValidationInput::new().with_fleet_composition([1, 2, 1, 1]);
+        assert!(FleetCompositionValidationStrategy
+            .validate(&classic)
+            .is_ok());
+    }
+}
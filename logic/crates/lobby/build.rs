@@ -6,7 +6,8 @@ use calimero_wasm_abi::emitter::emit_manifest_from_crate;
 fn main() {
     let src_dir = Path::new("src");
 
-    let module_files = ["lib.rs", "events.rs"];
+    // Template rule types are defined in the types crate.
+    let module_files = ["lib.rs", "events.rs", "../../types/src/rules.rs"];
 
     for name in &module_files {
         println!("cargo:rerun-if-changed=src/{}", name);
@@ -6,6 +6,8 @@ pub enum Event<'a> {
     MatchIdCollision { attempted_id: &'a str },
     /// The Lobby match list changed (created, linked, or finished).
     MatchListUpdated {},
+    /// A player forfeited an unfinished match; their opponent is the winner.
+    MatchForfeited { id: &'a str, player: &'a str },
     /// Lobby player stats were updated after a match finished.
     PlayerStatsUpdated {},
 }
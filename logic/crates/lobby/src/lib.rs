@@ -1,5 +1,6 @@
 //! Lobby service — match directory, player stats, and history.
 
+use battleships_types::rules::{GameRules, PlacementRules};
 use battleships_types::{GameError, PublicKey};
 use calimero_sdk::app;
 use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
@@ -26,6 +27,29 @@ pub enum MatchStatus {
     Finished,
 }
 
+/// Who can see a match in the lobby listing. Fixed at creation.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum Visibility {
+    /// Listed for everyone.
+    #[default]
+    Public,
+    /// Invite-only: listed only for its two players.
+    Private,
+}
+
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
@@ -37,6 +61,37 @@ pub struct MatchSummary {
     pub context_id: Option<String>,
     pub winner: Option<String>,
     pub created_ms: u64,
+    pub visibility: Visibility,
+    /// Base58 key of the player who fires first: `player1` unless the
+    /// creator picked the opponent.
+    pub first_mover: String,
+    /// Human-friendly label picked by the creator, e.g. "Friday Night
+    /// Game"; see `MAX_MATCH_NAME_CHARS`.
+    pub name: Option<String>,
+    /// Whose turn it is, as last reported by the game context. `None` until
+    /// the match is Active, while it is paused, and once it is Finished.
+    pub turn: Option<String>,
+    /// Game-context time of the report `turn` comes from; a later report
+    /// replaces an earlier one, whatever order they arrive or merge in.
+    pub turn_ms: u64,
+    /// Template the match was created from, if any; its `game_rules` and
+    /// `placement_rules` are what the game context is initialised with.
+    pub template: Option<String>,
+}
+
+/// Which of the caller's matches `get_my_matches` returns.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum MatchStatusFilter {
+    All,
+    /// Not finished yet: Pending or Active.
+    Active,
+    Finished,
+    /// Active and waiting on the caller's shot.
+    MyTurn,
 }
 
 impl Mergeable for MatchSummary {
@@ -62,6 +117,18 @@ impl Mergeable for MatchSummary {
             if self.winner.is_none() && other.winner.is_some() {
                 self.winner = other.winner.clone();
             }
+            // Turn reports are last-writer-wins on their timestamp, with the
+            // value as a deterministic tiebreaker.
+            if (other.turn_ms, &other.turn) > (self.turn_ms, &self.turn) {
+                self.turn = other.turn.clone();
+                self.turn_ms = other.turn_ms;
+            }
+            if self.name.is_none() && other.name.is_some() {
+                self.name = other.name.clone();
+            }
+            if self.template.is_none() && other.template.is_some() {
+                self.template = other.template.clone();
+            }
         }
         Ok(())
     }
@@ -72,8 +139,9 @@ impl Mergeable for MatchSummary {
 pub struct PlayerStats {
     pub wins: Counter,
     pub losses: Counter,
-    // games_played is intentionally NOT stored — it's `wins + losses` by
-    // construction (every match increments exactly one), so deriving it in
+    pub draws: Counter,
+    // games_played is intentionally NOT stored — it's `wins + losses + draws`
+    // by construction (every match increments exactly one), so deriving it in
     // `to_view` removes a "can these drift?" question from the data model.
 }
 
@@ -84,6 +152,7 @@ impl PlayerStats {
         PlayerStats {
             wins: Counter::new_with_field_name(&format!("stats:{player_key}:wins")),
             losses: Counter::new_with_field_name(&format!("stats:{player_key}:losses")),
+            draws: Counter::new_with_field_name(&format!("stats:{player_key}:draws")),
         }
     }
 
@@ -96,10 +165,15 @@ impl PlayerStats {
             .losses
             .value_unsigned()
             .map_err(|e| GameError::Invalid(format!("losses read: {e}")))?;
+        let draws = self
+            .draws
+            .value_unsigned()
+            .map_err(|e| GameError::Invalid(format!("draws read: {e}")))?;
         Ok(PlayerStatsView {
             wins,
             losses,
-            games_played: wins.saturating_add(losses),
+            draws,
+            games_played: wins.saturating_add(losses).saturating_add(draws),
         })
     }
 }
@@ -111,6 +185,7 @@ impl PlayerStats {
 pub struct PlayerStatsView {
     pub wins: u64,
     pub losses: u64,
+    pub draws: u64,
     pub games_played: u64,
 }
 
@@ -136,10 +211,82 @@ impl Mergeable for MatchRecord {
     }
 }
 
+/// A named match configuration, so organizers running many identical
+/// matches (say, a tournament) don't resend the rules every time. Fixed
+/// once registered. Its rules are passed to the game context of every
+/// match created from it.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct MatchTemplate {
+    pub name: String,
+    /// Win condition, turn and ammunition rules and the shot timeout.
+    pub game_rules: GameRules,
+    /// Board size and fleet.
+    pub placement_rules: PlacementRules,
+    pub visibility: Visibility,
+    /// Base58 key of the organizer who registered it.
+    pub registered_by: String,
+    pub registered_ms: u64,
+}
+
+impl Mergeable for MatchTemplate {
+    fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        // Templates are write-once, so this only matters when two replicas
+        // registered the same name concurrently: the earlier one wins, with
+        // the serialized template as a deterministic tiebreaker.
+        let key = |t: &MatchTemplate| {
+            (
+                t.registered_ms,
+                calimero_sdk::borsh::to_vec(t).unwrap_or_default(),
+            )
+        };
+        if key(other) < key(self) {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Rejects a match name longer than `MAX_MATCH_NAME_CHARS` or holding
+/// control characters, which would break a lobby listing.
+fn check_match_name(name: &str) -> Result<(), GameError> {
+    if name.chars().count() > MAX_MATCH_NAME_CHARS {
+        return Err(GameError::Invalid(format!(
+            "match name longer than {MAX_MATCH_NAME_CHARS} characters"
+        )));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(GameError::Invalid(
+            "match name contains control characters".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects a report about `summary` that doesn't come from its own game
+/// context. Only that context is told the match id and lobby, so a stray
+/// or forged caller can't steer the listing.
+fn check_reporting_context(summary: &MatchSummary, context_id: &str) -> Result<(), GameError> {
+    if summary.context_id.as_deref() != Some(context_id) {
+        return Err(GameError::Forbidden("not this match's game context".into()));
+    }
+    Ok(())
+}
+
+/// Base58 key of whoever is creating this lobby context. Native tests run
+/// without a runtime to ask, so there the lobby starts without one.
+fn lobby_creator() -> Option<String> {
+    #[cfg(test)]
+    return None;
+    #[cfg(not(test))]
+    from_executor_id().ok().map(|pk| pk.to_base58())
+}
+
 fn from_executor_id() -> Result<PublicKey, GameError> {
     let v = calimero_sdk::env::executor_id();
     if v.len() != 32 {
@@ -154,6 +301,16 @@ fn from_executor_id() -> Result<PublicKey, GameError> {
 // Lobby state
 // ---------------------------------------------------------------------------
 
+/// Hard cap on unfinished (Pending or Active) matches one player may have
+/// created, so no single actor can grow storage without bound. It is per
+/// creator because opponents can be made-up keys: a lobby-wide cap would let
+/// one actor fill every slot. `set_max_concurrent_matches` may lower it,
+/// never raise it.
+pub const MAX_CONCURRENT_MATCHES: u32 = 32;
+
+/// Longest match name `create_match` accepts, in characters.
+pub const MAX_MATCH_NAME_CHARS: usize = 64;
+
 #[app::state(emits = for<'a> Event<'a>)]
 #[derive(BorshSerialize, BorshDeserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
@@ -162,6 +319,15 @@ pub struct LobbyState {
     matches: UnorderedMap<String, MatchSummary>,
     player_stats: UnorderedMap<String, PlayerStats>,
     history: Vector<MatchRecord>,
+    /// Everyone who has taken part in a match, keyed by base58 key, with
+    /// when they first did. Backs `get_player_count`.
+    players: UnorderedMap<String, LwwRegister<u64>>,
+    templates: UnorderedMap<String, MatchTemplate>,
+    /// Key that created the lobby context, the only one that may register
+    /// templates.
+    organizer: LwwRegister<Option<String>>,
+    /// Effective per-creator match cap, at most `MAX_CONCURRENT_MATCHES`.
+    max_concurrent_matches: LwwRegister<u32>,
 }
 
 #[app::logic]
@@ -173,12 +339,30 @@ impl LobbyState {
             matches: UnorderedMap::new_with_field_name("lobby:matches"),
             player_stats: UnorderedMap::new_with_field_name("lobby:player_stats"),
             history: Vector::new_with_field_name("lobby:history"),
+            players: UnorderedMap::new_with_field_name("lobby:players"),
+            templates: UnorderedMap::new_with_field_name("lobby:templates"),
+            organizer: LwwRegister::new(lobby_creator()),
+            max_concurrent_matches: LwwRegister::new(MAX_CONCURRENT_MATCHES),
         }
     }
 
     // ---- Lobby API ----
 
-    pub fn create_match(&mut self, player2: String) -> app::Result<String> {
+    /// `visibility` defaults to `Public` when omitted. `first_mover` (base58)
+    /// picks which player opens, e.g. to alternate across a series; it
+    /// defaults to the creator and must be one of the two players. `name`
+    /// is an optional label for lobby listings.
+    pub fn create_match(
+        &mut self,
+        player2: String,
+        visibility: Option<Visibility>,
+        first_mover: Option<String>,
+        name: Option<String>,
+    ) -> app::Result<String> {
+        // Checked up front so a bad name never leaves a match behind.
+        if let Some(name) = &name {
+            check_match_name(name).map_err(|e| AppError::msg(e.to_string()))?;
+        }
         let caller = from_executor_id().map_err(|e| AppError::msg(e.to_string()))?;
         let caller_b58 = caller.to_base58();
         let now = storage_env::time_now();
@@ -191,8 +375,19 @@ impl LobbyState {
                 acc
             });
         let id = self
-            .create_match_with_id(&caller_b58, &player2, now, &nonce_hex)
+            .create_match_with_id(
+                &caller_b58,
+                &player2,
+                now,
+                &nonce_hex,
+                visibility.unwrap_or_default(),
+                first_mover.as_deref(),
+            )
             .map_err(|e| AppError::msg(e.to_string()))?;
+        if let Some(name) = &name {
+            self.set_match_name_inner(&id, name)
+                .map_err(|e| AppError::msg(e.to_string()))?;
+        }
         app::emit!(Event::MatchCreated { id: &id });
         app::emit!(Event::MatchListUpdated {});
         Ok(id)
@@ -209,6 +404,8 @@ impl LobbyState {
         player2_b58: &str,
         now_ms: u64,
         nonce_hex: &str,
+        visibility: Visibility,
+        first_mover: Option<&str>,
     ) -> Result<String, GameError> {
         // Reject self-matches: the turn protocol assumes two distinct players.
         if caller_b58 == player2_b58 {
@@ -220,6 +417,13 @@ impl LobbyState {
         // its caller against a real key.
         PublicKey::from_base58(player2_b58)
             .map_err(|e| GameError::Invalid(format!("player2 is not a valid base58 key: {e}")))?;
+        let first_mover = first_mover.unwrap_or(caller_b58);
+        if first_mover != caller_b58 && first_mover != player2_b58 {
+            return Err(GameError::Invalid("first_mover is not a player".into()));
+        }
+        if self.unfinished_matches_created_by(caller_b58)? >= *self.max_concurrent_matches.get() {
+            return Err(GameError::Forbidden("match limit reached".into()));
+        }
         let match_id = format!("{caller_b58}-{now_ms}-{nonce_hex}");
         let collides = self
             .matches
@@ -236,13 +440,189 @@ impl LobbyState {
             context_id: None,
             winner: None,
             created_ms: now_ms,
+            visibility,
+            first_mover: first_mover.to_string(),
+            name: None,
+            turn: None,
+            turn_ms: 0,
+            template: None,
         };
         self.matches
             .insert(match_id.clone(), summary)
             .map_err(|e| GameError::Invalid(format!("matches.insert failed: {e}")))?;
+        // The opponent counts once they place ships (`on_ships_placed`):
+        // being named in someone else's match isn't taking part.
+        self.note_player(caller_b58, now_ms)?;
         Ok(match_id)
     }
 
+    /// Adds `player_b58` to the set behind `get_player_count`, keeping the
+    /// first time they were seen.
+    fn note_player(&mut self, player_b58: &str, now_ms: u64) -> Result<(), GameError> {
+        let seen = self
+            .players
+            .contains(player_b58)
+            .map_err(|e| GameError::Invalid(format!("players.contains failed: {e}")))?;
+        if !seen {
+            self.players
+                .insert(player_b58.to_string(), LwwRegister::new(now_ms))
+                .map_err(|e| GameError::Invalid(format!("players.insert failed: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// How many distinct players have ever taken part in a match on this
+    /// lobby: created one, or placed ships as the opponent.
+    pub fn get_player_count(&self) -> app::Result<u64> {
+        let count = self
+            .players
+            .len()
+            .map_err(|e| AppError::msg(format!("players.len: {e}")))?;
+        Ok(count as u64)
+    }
+
+    /// Registers a preset for `create_match_from_template`. Rules left out
+    /// are the classic game's; `visibility` defaults to `Public`. Only the
+    /// lobby's organizer may register. Names are unique and follow the
+    /// match name rules, and the rules must be ones a game context accepts.
+    pub fn register_template(
+        &mut self,
+        name: String,
+        game_rules: Option<GameRules>,
+        placement_rules: Option<PlacementRules>,
+        visibility: Option<Visibility>,
+    ) -> app::Result<()> {
+        let caller = from_executor_id().map_err(|e| AppError::msg(e.to_string()))?;
+        let template = MatchTemplate {
+            name,
+            game_rules: game_rules.unwrap_or_default(),
+            placement_rules: placement_rules.unwrap_or_default(),
+            visibility: visibility.unwrap_or_default(),
+            registered_by: caller.to_base58(),
+            registered_ms: storage_env::time_now(),
+        };
+        self.register_template_inner(template)
+            .map_err(|e| AppError::msg(e.to_string()))
+    }
+
+    pub(crate) fn register_template_inner(
+        &mut self,
+        template: MatchTemplate,
+    ) -> Result<(), GameError> {
+        if self.organizer.get().as_deref() != Some(template.registered_by.as_str()) {
+            return Err(GameError::Forbidden(
+                "only the lobby organizer may register templates".into(),
+            ));
+        }
+        if template.name.is_empty() {
+            return Err(GameError::Invalid("template name is empty".into()));
+        }
+        check_match_name(&template.name)?;
+        template.game_rules.validate()?;
+        template
+            .placement_rules
+            .validate_for(&template.game_rules)?;
+        let exists = self
+            .templates
+            .contains(&template.name)
+            .map_err(|e| GameError::Invalid(format!("templates.contains failed: {e}")))?;
+        if exists {
+            return Err(GameError::Invalid(format!(
+                "template {:?} already exists",
+                template.name
+            )));
+        }
+        self.templates
+            .insert(template.name.clone(), template)
+            .map_err(|e| GameError::Invalid(format!("templates.insert failed: {e}")))?;
+        Ok(())
+    }
+
+    pub fn get_template(&self, name: String) -> app::Result<Option<MatchTemplate>> {
+        self.templates
+            .get(&name)
+            .map_err(|e| AppError::msg(format!("templates.get: {e}")))
+    }
+
+    /// `create_match` with the visibility of template `name`, which the
+    /// match remembers so its game context can be started with the
+    /// template's rules.
+    pub fn create_match_from_template(
+        &mut self,
+        name: String,
+        player2: String,
+    ) -> app::Result<String> {
+        let template = self
+            .template_or_not_found(&name)
+            .map_err(|e| AppError::msg(e.to_string()))?;
+        let id = self.create_match(player2, Some(template.visibility), None, None)?;
+        self.set_match_template_inner(&id, &name)
+            .map_err(|e| AppError::msg(e.to_string()))?;
+        Ok(id)
+    }
+
+    fn template_or_not_found(&self, name: &str) -> Result<MatchTemplate, GameError> {
+        self.templates
+            .get(&name.to_string())
+            .map_err(|e| GameError::Invalid(format!("templates.get failed: {e}")))?
+            .ok_or_else(|| GameError::NotFound(format!("template {name:?}")))
+    }
+
+    /// Records that `match_id` was created from template `name`; the
+    /// testable half of `create_match_from_template`.
+    pub(crate) fn set_match_template_inner(
+        &mut self,
+        match_id: &str,
+        name: &str,
+    ) -> Result<(), GameError> {
+        self.template_or_not_found(name)?;
+        let mut summary = self
+            .matches
+            .get(&match_id.to_string())
+            .map_err(|e| GameError::Invalid(format!("matches.get failed: {e}")))?
+            .ok_or(GameError::Invalid("unknown match_id".into()))?;
+        summary.template = Some(name.to_string());
+        self.matches
+            .insert(match_id.to_string(), summary)
+            .map_err(|e| GameError::Invalid(format!("matches.insert failed: {e}")))?;
+        Ok(())
+    }
+
+    /// Lowers the per-creator cap on unfinished matches, to exercise the
+    /// limit on a dev deployment. Matches already over a new, lower cap are
+    /// kept; only new ones are refused. Only builds with the `dev` feature
+    /// accept it, so no member of a real lobby can shut match creation off.
+    pub fn set_max_concurrent_matches(&mut self, limit: u32) -> app::Result<()> {
+        if !cfg!(feature = "dev") {
+            app::bail!(AppError::msg(
+                GameError::Forbidden("dev builds only".into()).to_string()
+            ));
+        }
+        self.set_max_concurrent_matches_inner(limit)
+            .map_err(|e| AppError::msg(e.to_string()))
+    }
+
+    pub(crate) fn set_max_concurrent_matches_inner(&mut self, limit: u32) -> Result<(), GameError> {
+        if limit > MAX_CONCURRENT_MATCHES {
+            return Err(GameError::Invalid(format!(
+                "limit above the compile-time cap of {MAX_CONCURRENT_MATCHES}"
+            )));
+        }
+        self.max_concurrent_matches.set(limit);
+        Ok(())
+    }
+
+    /// Matches `creator_b58` created that are still Pending or Active.
+    fn unfinished_matches_created_by(&self, creator_b58: &str) -> Result<u32, GameError> {
+        let entries = self
+            .matches
+            .entries()
+            .map_err(|e| GameError::Invalid(format!("matches.entries failed: {e}")))?;
+        Ok(entries
+            .filter(|(_, m)| m.player1 == creator_b58 && m.status != MatchStatus::Finished)
+            .count() as u32)
+    }
+
     pub fn set_match_context_id(
         &mut self,
         match_id: String,
@@ -254,6 +634,26 @@ impl LobbyState {
         Ok(())
     }
 
+    /// Labels `match_id` with `name`; the testable half of naming in
+    /// `create_match`.
+    pub(crate) fn set_match_name_inner(
+        &mut self,
+        match_id: &str,
+        name: &str,
+    ) -> Result<(), GameError> {
+        check_match_name(name)?;
+        let mut summary = self
+            .matches
+            .get(&match_id.to_string())
+            .map_err(|e| GameError::Invalid(format!("matches.get failed: {e}")))?
+            .ok_or(GameError::Invalid("unknown match_id".into()))?;
+        summary.name = Some(name.to_string());
+        self.matches
+            .insert(match_id.to_string(), summary)
+            .map_err(|e| GameError::Invalid(format!("matches.insert failed: {e}")))?;
+        Ok(())
+    }
+
     pub(crate) fn set_match_context_id_inner(
         &mut self,
         match_id: &str,
@@ -272,18 +672,215 @@ impl LobbyState {
         }
         summary.status = MatchStatus::Active;
         summary.context_id = Some(context_id.to_string());
+        // The game context opens with the chosen first mover on turn.
+        summary.turn = Some(summary.first_mover.clone());
         self.matches
             .insert(match_id.to_string(), summary)
             .map_err(|e| GameError::Invalid(format!("matches.insert failed: {e}")))?;
         Ok(())
     }
 
+    /// Public matches plus the caller's own private ones.
     pub fn get_matches(&self) -> app::Result<Vec<MatchSummary>> {
+        let caller = from_executor_id().map_err(|e| AppError::msg(e.to_string()))?;
+        self.matches_visible_to(&caller.to_base58())
+            .map_err(|e| AppError::msg(e.to_string()))
+    }
+
+    pub(crate) fn matches_visible_to(
+        &self,
+        caller_b58: &str,
+    ) -> Result<Vec<MatchSummary>, GameError> {
+        let entries = self
+            .matches
+            .entries()
+            .map_err(|e| GameError::Invalid(format!("matches.entries failed: {e}")))?;
+        Ok(entries
+            .map(|(_, v)| v)
+            .filter(|m| {
+                m.visibility == Visibility::Public
+                    || m.player1 == caller_b58
+                    || m.player2 == caller_b58
+            })
+            .collect())
+    }
+
+    /// Ids of the caller's own matches that pass `status`, sorted.
+    pub fn get_my_matches(&self, status: MatchStatusFilter) -> app::Result<Vec<String>> {
+        let caller = from_executor_id().map_err(|e| AppError::msg(e.to_string()))?;
+        self.my_matches(&caller.to_base58(), status)
+            .map_err(|e| AppError::msg(e.to_string()))
+    }
+
+    pub(crate) fn my_matches(
+        &self,
+        caller_b58: &str,
+        status: MatchStatusFilter,
+    ) -> Result<Vec<String>, GameError> {
         let entries = self
             .matches
             .entries()
-            .map_err(|e| AppError::msg(format!("matches.entries: {e}")))?;
-        Ok(entries.map(|(_, v)| v).collect())
+            .map_err(|e| GameError::Invalid(format!("matches.entries failed: {e}")))?;
+        let mut ids: Vec<String> = entries
+            .filter(|(_, m)| m.player1 == caller_b58 || m.player2 == caller_b58)
+            .filter(|(_, m)| match status {
+                MatchStatusFilter::All => true,
+                MatchStatusFilter::Active => m.status != MatchStatus::Finished,
+                MatchStatusFilter::Finished => m.status == MatchStatus::Finished,
+                MatchStatusFilter::MyTurn => {
+                    m.status == MatchStatus::Active && m.turn.as_deref() == Some(caller_b58)
+                }
+            })
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Called by a game context (via xcall) whenever the turn passes, so
+    /// `get_my_matches(MyTurn)` works without visiting every game context.
+    /// `context_id` must be the match's own game context; `turn` is `None`
+    /// while the match is paused. `at_ms` orders the reports.
+    pub fn on_turn_changed(
+        &mut self,
+        match_id: String,
+        context_id: String,
+        turn: Option<String>,
+        at_ms: u64,
+    ) -> app::Result<()> {
+        self.on_turn_changed_inner(&match_id, &context_id, turn.as_deref(), at_ms)
+            .map_err(|e| AppError::msg(e.to_string()))?;
+        app::emit!(Event::MatchListUpdated {});
+        Ok(())
+    }
+
+    pub(crate) fn on_turn_changed_inner(
+        &mut self,
+        match_id: &str,
+        context_id: &str,
+        turn: Option<&str>,
+        at_ms: u64,
+    ) -> Result<(), GameError> {
+        let mut summary = self
+            .matches
+            .get(&match_id.to_string())
+            .map_err(|e| GameError::Invalid(format!("matches.get failed: {e}")))?
+            .ok_or(GameError::Invalid("unknown match_id".into()))?;
+        if summary.status != MatchStatus::Active {
+            return Err(GameError::Invalid("match not in Active state".into()));
+        }
+        check_reporting_context(&summary, context_id)?;
+        if let Some(turn) = turn {
+            if turn != summary.player1 && turn != summary.player2 {
+                return Err(GameError::Invalid(
+                    "turn is not a player of this match".into(),
+                ));
+            }
+        }
+        // A report that lost a race with a newer one is stale, not wrong.
+        if at_ms < summary.turn_ms {
+            return Ok(());
+        }
+        summary.turn = turn.map(str::to_string);
+        summary.turn_ms = at_ms;
+        self.matches
+            .insert(match_id.to_string(), summary)
+            .map_err(|e| GameError::Invalid(format!("matches.insert failed: {e}")))?;
+        Ok(())
+    }
+
+    /// Called by a game context (via xcall) when a player moves their seat
+    /// to a new key, so the listing, `get_my_matches` and the stats of the
+    /// rest of the match follow them. `context_id` must be the match's own
+    /// game context.
+    pub fn on_player_migrated(
+        &mut self,
+        match_id: String,
+        context_id: String,
+        from: String,
+        to: String,
+    ) -> app::Result<()> {
+        let now = storage_env::time_now();
+        self.on_player_migrated_inner(&match_id, &context_id, &from, &to, now)
+            .map_err(|e| AppError::msg(e.to_string()))?;
+        app::emit!(Event::MatchListUpdated {});
+        Ok(())
+    }
+
+    pub(crate) fn on_player_migrated_inner(
+        &mut self,
+        match_id: &str,
+        context_id: &str,
+        from: &str,
+        to: &str,
+        now_ms: u64,
+    ) -> Result<(), GameError> {
+        let mut summary = self
+            .matches
+            .get(&match_id.to_string())
+            .map_err(|e| GameError::Invalid(format!("matches.get failed: {e}")))?
+            .ok_or(GameError::Invalid("unknown match_id".into()))?;
+        if summary.status != MatchStatus::Active {
+            return Err(GameError::Invalid("match not in Active state".into()));
+        }
+        check_reporting_context(&summary, context_id)?;
+        PublicKey::from_base58(to)
+            .map_err(|e| GameError::Invalid(format!("new key is not valid base58: {e}")))?;
+        let seat = if summary.player1 == from {
+            &mut summary.player1
+        } else if summary.player2 == from {
+            &mut summary.player2
+        } else {
+            return Err(GameError::Invalid(
+                "from is not a player of this match".into(),
+            ));
+        };
+        *seat = to.to_string();
+        if summary.first_mover == from {
+            summary.first_mover = to.to_string();
+        }
+        if summary.turn.as_deref() == Some(from) {
+            summary.turn = Some(to.to_string());
+        }
+        self.matches
+            .insert(match_id.to_string(), summary)
+            .map_err(|e| GameError::Invalid(format!("matches.insert failed: {e}")))?;
+        self.note_player(to, now_ms)
+    }
+
+    /// Called by a game context (via xcall) when `player` has placed their
+    /// ships, which is when an opponent starts counting toward
+    /// `get_player_count`. `context_id` must be the match's own game context.
+    pub fn on_ships_placed(
+        &mut self,
+        match_id: String,
+        context_id: String,
+        player: String,
+    ) -> app::Result<()> {
+        let now = storage_env::time_now();
+        self.on_ships_placed_inner(&match_id, &context_id, &player, now)
+            .map_err(|e| AppError::msg(e.to_string()))
+    }
+
+    pub(crate) fn on_ships_placed_inner(
+        &mut self,
+        match_id: &str,
+        context_id: &str,
+        player: &str,
+        now_ms: u64,
+    ) -> Result<(), GameError> {
+        let summary = self
+            .matches
+            .get(&match_id.to_string())
+            .map_err(|e| GameError::Invalid(format!("matches.get failed: {e}")))?
+            .ok_or(GameError::Invalid("unknown match_id".into()))?;
+        check_reporting_context(&summary, context_id)?;
+        if player != summary.player1 && player != summary.player2 {
+            return Err(GameError::Invalid(
+                "player is not a player of this match".into(),
+            ));
+        }
+        self.note_player(player, now_ms)
     }
 
     pub fn get_player_stats(&self, player: String) -> app::Result<Option<PlayerStatsView>> {
@@ -319,6 +916,76 @@ impl LobbyState {
         Ok(())
     }
 
+    /// Called by a game context (via xcall) when its match ends in a draw.
+    /// `context_id` must be the match's own game context. The match is
+    /// Finished with no winner and counts as a draw for both players; it
+    /// isn't added to `get_history`, whose records name a winner and loser.
+    pub fn on_match_drawn(&mut self, match_id: String, context_id: String) -> app::Result<()> {
+        self.on_match_drawn_inner(&match_id, &context_id)
+            .map_err(|e| AppError::msg(e.to_string()))?;
+        app::emit!(Event::MatchListUpdated {});
+        app::emit!(Event::PlayerStatsUpdated {});
+        Ok(())
+    }
+
+    /// Forfeits every unfinished match the caller plays in, crediting each
+    /// opponent with the win. Returns the affected match ids.
+    ///
+    /// The forfeit is recorded in the lobby only: a game context already
+    /// running for the match is not told and may be played on, but the
+    /// result it reports when it ends is ignored, since the lobby's record
+    /// of a match is settled once.
+    pub fn forfeit_all(&mut self) -> app::Result<Vec<String>> {
+        let caller = from_executor_id().map_err(|e| AppError::msg(e.to_string()))?;
+        let caller_b58 = caller.to_base58();
+        let now = storage_env::time_now();
+        let forfeited = self
+            .forfeit_all_inner(&caller_b58, now)
+            .map_err(|e| AppError::msg(e.to_string()))?;
+        for id in &forfeited {
+            app::emit!(Event::MatchForfeited {
+                id,
+                player: &caller_b58,
+            });
+        }
+        if !forfeited.is_empty() {
+            app::emit!(Event::MatchListUpdated {});
+            app::emit!(Event::PlayerStatsUpdated {});
+        }
+        Ok(forfeited)
+    }
+
+    /// Testable inner for `forfeit_all`: no event emits. Each forfeit is
+    /// recorded exactly like a finished match (summary, history, stats).
+    pub(crate) fn forfeit_all_inner(
+        &mut self,
+        caller_b58: &str,
+        now_ms: u64,
+    ) -> Result<Vec<String>, GameError> {
+        let open: Vec<(String, String)> = self
+            .matches
+            .entries()
+            .map_err(|e| GameError::Invalid(format!("matches.entries failed: {e}")))?
+            .filter(|(_, m)| m.status != MatchStatus::Finished)
+            .filter_map(|(id, m)| {
+                if m.player1 == caller_b58 {
+                    Some((id, m.player2))
+                } else if m.player2 == caller_b58 {
+                    Some((id, m.player1))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut forfeited = Vec::with_capacity(open.len());
+        for (id, opponent) in open {
+            self.on_match_finished_inner(&id, &opponent, caller_b58, now_ms)?;
+            forfeited.push(id);
+        }
+        forfeited.sort();
+        Ok(forfeited)
+    }
+
     pub(crate) fn on_match_finished_inner(
         &mut self,
         match_id: &str,
@@ -334,8 +1001,14 @@ impl LobbyState {
             .get(&match_id.to_string())
             .map_err(|e| GameError::Invalid(format!("matches.get failed: {e}")))?
             .ok_or(GameError::Invalid("unknown match_id".into()))?;
+        // Settled once: a game that ends after its match was forfeited
+        // must not add a second record or count toward stats again.
+        if summary.status == MatchStatus::Finished {
+            return Ok(());
+        }
         summary.status = MatchStatus::Finished;
         summary.winner = Some(winner.to_string());
+        summary.turn = None;
         self.matches
             .insert(match_id.to_string(), summary)
             .map_err(|e| GameError::Invalid(format!("matches.insert failed: {e}")))?;
@@ -349,32 +1022,63 @@ impl LobbyState {
             })
             .map_err(|e| GameError::Invalid(format!("history.push failed: {e}")))?;
 
-        bump_stats(&mut self.player_stats, winner, true)?;
-        bump_stats(&mut self.player_stats, loser, false)?;
+        bump_stats(&mut self.player_stats, winner, Outcome::Win)?;
+        bump_stats(&mut self.player_stats, loser, Outcome::Loss)?;
+        Ok(())
+    }
+
+    pub(crate) fn on_match_drawn_inner(
+        &mut self,
+        match_id: &str,
+        context_id: &str,
+    ) -> Result<(), GameError> {
+        let mut summary = self
+            .matches
+            .get(&match_id.to_string())
+            .map_err(|e| GameError::Invalid(format!("matches.get failed: {e}")))?
+            .ok_or(GameError::Invalid("unknown match_id".into()))?;
+        check_reporting_context(&summary, context_id)?;
+        // Settled once, as in `on_match_finished_inner`.
+        if summary.status == MatchStatus::Finished {
+            return Ok(());
+        }
+        summary.status = MatchStatus::Finished;
+        summary.turn = None;
+        let players = [summary.player1.clone(), summary.player2.clone()];
+        self.matches
+            .insert(match_id.to_string(), summary)
+            .map_err(|e| GameError::Invalid(format!("matches.insert failed: {e}")))?;
+        for player in &players {
+            bump_stats(&mut self.player_stats, player, Outcome::Draw)?;
+        }
         Ok(())
     }
 }
 
+/// What a settled match counts as in one player's stats.
+enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
 fn bump_stats(
     stats_map: &mut UnorderedMap<String, PlayerStats>,
     player_key: &str,
-    is_winner: bool,
+    outcome: Outcome,
 ) -> Result<(), GameError> {
     let mut stats = stats_map
         .get(&player_key.to_string())
         .map_err(|e| GameError::Invalid(format!("stats.get failed: {e}")))?
         .unwrap_or_else(|| PlayerStats::new(player_key));
-    if is_winner {
-        stats
-            .wins
-            .increment()
-            .map_err(|e| GameError::Invalid(format!("wins.increment failed: {e}")))?;
-    } else {
-        stats
-            .losses
-            .increment()
-            .map_err(|e| GameError::Invalid(format!("losses.increment failed: {e}")))?;
-    }
+    let (counter, name) = match outcome {
+        Outcome::Win => (&mut stats.wins, "wins"),
+        Outcome::Loss => (&mut stats.losses, "losses"),
+        Outcome::Draw => (&mut stats.draws, "draws"),
+    };
+    counter
+        .increment()
+        .map_err(|e| GameError::Invalid(format!("{name}.increment failed: {e}")))?;
     stats_map
         .insert(player_key.to_string(), stats)
         .map_err(|e| GameError::Invalid(format!("stats.insert failed: {e}")))?;
@@ -384,6 +1088,7 @@ fn bump_stats(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use battleships_types::rules::WinCondition;
     use battleships_types::GameError;
 
     #[test]
@@ -391,9 +1096,42 @@ mod tests {
         let stats = PlayerStats::new("alice_b58");
         assert_eq!(stats.wins.value_unsigned().unwrap(), 0);
         assert_eq!(stats.losses.value_unsigned().unwrap(), 0);
+        assert_eq!(stats.draws.value_unsigned().unwrap(), 0);
         assert_eq!(stats.to_view().unwrap().games_played, 0);
     }
 
+    #[test]
+    fn a_drawn_match_is_settled_once_for_both_players() {
+        let mut state = LobbyState::init();
+        let [a, b] = [1u8, 2].map(|n| bs58::encode([n; 32]).into_string());
+        let id = state
+            .create_match_with_id(&a, &b, 1, "00000001", Visibility::Public, None)
+            .unwrap();
+        state.set_match_context_id_inner(&id, "ctx").unwrap();
+        assert!(matches!(
+            state.on_match_drawn_inner(&id, "elsewhere"),
+            Err(GameError::Forbidden(_))
+        ));
+        state.on_match_drawn_inner(&id, "ctx").unwrap();
+        state.on_match_drawn_inner(&id, "ctx").unwrap();
+
+        let summary = state.matches.get(&id).unwrap().unwrap();
+        assert!(matches!(summary.status, MatchStatus::Finished));
+        assert!(summary.winner.is_none() && summary.turn.is_none());
+        for player in [&a, &b] {
+            let view = state
+                .player_stats
+                .get(player)
+                .unwrap()
+                .unwrap()
+                .to_view()
+                .unwrap();
+            assert_eq!((view.wins, view.losses, view.draws), (0, 0, 1));
+            assert_eq!(view.games_played, 1);
+        }
+        assert_eq!(state.history.len().unwrap(), 0);
+    }
+
     #[test]
     fn player_stats_increments_accumulate() {
         let mut stats = PlayerStats::new("alice_b58");
@@ -402,7 +1140,7 @@ mod tests {
         stats.losses.increment().unwrap();
         assert_eq!(stats.wins.value_unsigned().unwrap(), 1);
         assert_eq!(stats.losses.value_unsigned().unwrap(), 2);
-        // games_played is derived as wins + losses in the view.
+        // games_played is derived as wins + losses + draws in the view.
         assert_eq!(stats.to_view().unwrap().games_played, 3);
     }
 
@@ -421,7 +1159,14 @@ mod tests {
         let caller_b58 = bs58::encode([1u8; 32]).into_string();
         let player2_b58 = bs58::encode([2u8; 32]).into_string();
         let id = state
-            .create_match_with_id(&caller_b58, &player2_b58, 1_700_000_000_000, "deadbeef")
+            .create_match_with_id(
+                &caller_b58,
+                &player2_b58,
+                1_700_000_000_000,
+                "deadbeef",
+                Visibility::Public,
+                None,
+            )
             .unwrap();
         assert_eq!(id, format!("{caller_b58}-1700000000000-deadbeef"));
         assert!(
@@ -432,6 +1177,34 @@ mod tests {
         assert_eq!(summary.player2, player2_b58);
     }
 
+    #[test]
+    fn player_count_counts_each_key_once() {
+        let mut state = LobbyState::init();
+        assert_eq!(state.get_player_count().unwrap(), 0);
+        let [a, b, c] = [1u8, 2, 3].map(|n| bs58::encode([n; 32]).into_string());
+        for (p1, p2, nonce) in [(&a, &b, "n1"), (&b, &a, "n2"), (&a, &c, "n3")] {
+            state
+                .create_match_with_id(p1, p2, 1_000, nonce, Visibility::Public, None)
+                .unwrap();
+        }
+        // `c` is only named as an opponent so far.
+        assert_eq!(state.get_player_count().unwrap(), 2);
+        assert_eq!(*state.players.get(&a).unwrap().unwrap().get(), 1_000);
+
+        let id = format!("{a}-1000-n3");
+        state.set_match_context_id_inner(&id, "ctx").unwrap();
+        assert!(matches!(
+            state.on_ships_placed_inner(&id, "elsewhere", &c, 2_000),
+            Err(GameError::Forbidden(_))
+        ));
+        assert!(state.on_ships_placed_inner(&id, "ctx", &b, 2_000).is_err());
+        state.on_ships_placed_inner(&id, "ctx", &c, 2_000).unwrap();
+        state.on_ships_placed_inner(&id, "ctx", &a, 3_000).unwrap();
+        assert_eq!(state.get_player_count().unwrap(), 3);
+        assert_eq!(*state.players.get(&c).unwrap().unwrap().get(), 2_000);
+        assert_eq!(*state.players.get(&a).unwrap().unwrap().get(), 1_000);
+    }
+
     #[test]
     fn create_match_rejects_nonce_collision_defensive() {
         // Same (creator, ts, nonce_hex) tuple should be rejected. With a
@@ -441,9 +1214,11 @@ mod tests {
         let a = bs58::encode([1u8; 32]).into_string();
         let b = bs58::encode([2u8; 32]).into_string();
         let ts = 1_700_000_000_000u64;
-        let _ = state.create_match_with_id(&a, &b, ts, "abcd1234").unwrap();
+        let _ = state
+            .create_match_with_id(&a, &b, ts, "abcd1234", Visibility::Public, None)
+            .unwrap();
         let err = state
-            .create_match_with_id(&a, &b, ts, "abcd1234")
+            .create_match_with_id(&a, &b, ts, "abcd1234", Visibility::Public, None)
             .unwrap_err();
         assert!(matches!(err, GameError::MatchIdCollision));
     }
@@ -454,7 +1229,14 @@ mod tests {
         let a = bs58::encode([1u8; 32]).into_string();
         let b = bs58::encode([2u8; 32]).into_string();
         let id = state
-            .create_match_with_id(&a, &b, 1_700_000_000_000, "deadbeef")
+            .create_match_with_id(
+                &a,
+                &b,
+                1_700_000_000_000,
+                "deadbeef",
+                Visibility::Public,
+                None,
+            )
             .unwrap();
         state.set_match_context_id_inner(&id, "ctx_abc").unwrap();
         let summary = state.matches.get(&id).unwrap().unwrap();
@@ -462,13 +1244,177 @@ mod tests {
         assert_eq!(summary.context_id.as_deref(), Some("ctx_abc"));
     }
 
+    #[test]
+    fn create_match_stops_at_the_concurrent_match_limit() {
+        let mut state = LobbyState::init();
+        let a = bs58::encode([1u8; 32]).into_string();
+        let b = bs58::encode([2u8; 32]).into_string();
+        assert!(state
+            .set_max_concurrent_matches_inner(MAX_CONCURRENT_MATCHES + 1)
+            .is_err());
+        state.set_max_concurrent_matches_inner(2).unwrap();
+        let first = state
+            .create_match_with_id(&a, &b, 1, "00000001", Visibility::Public, None)
+            .unwrap();
+        state
+            .create_match_with_id(&a, &b, 2, "00000002", Visibility::Public, None)
+            .unwrap();
+        let err = state
+            .create_match_with_id(&a, &b, 3, "00000003", Visibility::Public, None)
+            .unwrap_err();
+        assert!(matches!(err, GameError::Forbidden(ref m) if m == "match limit reached"));
+        // The cap is per creator: `a` filling theirs doesn't stop `b`.
+        state
+            .create_match_with_id(&b, &a, 3, "00000003", Visibility::Public, None)
+            .unwrap();
+
+        // A finished match frees its slot.
+        state.set_match_context_id_inner(&first, "ctx").unwrap();
+        state.on_match_finished_inner(&first, &a, &b, 10).unwrap();
+        state
+            .create_match_with_id(&a, &b, 4, "00000004", Visibility::Public, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn match_names_are_bounded_and_printable() {
+        let mut state = LobbyState::init();
+        let a = bs58::encode([1u8; 32]).into_string();
+        let b = bs58::encode([2u8; 32]).into_string();
+        let id = state
+            .create_match_with_id(&a, &b, 1, "00000001", Visibility::Public, None)
+            .unwrap();
+        assert_eq!(state.matches.get(&id).unwrap().unwrap().name, None);
+
+        let longest = "é".repeat(MAX_MATCH_NAME_CHARS);
+        state.set_match_name_inner(&id, &longest).unwrap();
+        let err = state
+            .set_match_name_inner(&id, &format!("{longest}x"))
+            .unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("longer than 64")));
+        let err = state
+            .set_match_name_inner(&id, "Friday\nNight")
+            .unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("control")));
+
+        state
+            .set_match_name_inner(&id, "Friday Night Game")
+            .unwrap();
+        let summary = state.matches.get(&id).unwrap().unwrap();
+        assert_eq!(summary.name.as_deref(), Some("Friday Night Game"));
+        assert!(state.set_match_name_inner("nope", "x").is_err());
+    }
+
+    #[test]
+    fn templates_are_unique_and_must_exist() {
+        let mut state = LobbyState::init();
+        let organizer = bs58::encode([9u8; 32]).into_string();
+        let template = |name: &str, shots_per_turn: u32| MatchTemplate {
+            name: name.to_string(),
+            game_rules: GameRules {
+                shots_per_turn,
+                ..GameRules::default()
+            },
+            placement_rules: PlacementRules::default(),
+            visibility: Visibility::Private,
+            registered_by: organizer.clone(),
+            registered_ms: 1,
+        };
+        // A lobby without an organizer takes no templates at all.
+        let err = state
+            .register_template_inner(template("salvo", 3))
+            .unwrap_err();
+        assert!(matches!(err, GameError::Forbidden(_)));
+        state.organizer.set(Some(organizer.clone()));
+
+        let salvo = template("salvo", 3);
+        state.register_template_inner(salvo.clone()).unwrap();
+        assert_eq!(state.get_template("salvo".into()).unwrap(), Some(salvo));
+        let err = state
+            .register_template_inner(template("salvo", 1))
+            .unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("already exists")));
+        let unwinnable = MatchTemplate {
+            game_rules: GameRules {
+                win_condition: WinCondition::FirstToHits(0),
+                ..GameRules::default()
+            },
+            ..template("x", 1)
+        };
+        let huge = MatchTemplate {
+            placement_rules: PlacementRules {
+                board_size: 40,
+                ..PlacementRules::default()
+            },
+            ..template("y", 1)
+        };
+        for bad in [template("", 1), unwinnable, huge] {
+            assert!(matches!(
+                state.register_template_inner(bad),
+                Err(GameError::Invalid(_))
+            ));
+        }
+        let stranger = MatchTemplate {
+            registered_by: bs58::encode([1u8; 32]).into_string(),
+            ..template("blitz", 1)
+        };
+        assert!(matches!(
+            state.register_template_inner(stranger),
+            Err(GameError::Forbidden(_))
+        ));
+
+        let a = bs58::encode([1u8; 32]).into_string();
+        let b = bs58::encode([2u8; 32]).into_string();
+        let id = state
+            .create_match_with_id(&a, &b, 1, "00000001", Visibility::Private, None)
+            .unwrap();
+        let err = state.set_match_template_inner(&id, "blitz").unwrap_err();
+        assert!(matches!(err, GameError::NotFound(_)));
+        state.set_match_template_inner(&id, "salvo").unwrap();
+        let summary = state.matches.get(&id).unwrap().unwrap();
+        assert_eq!(summary.template.as_deref(), Some("salvo"));
+    }
+
+    #[test]
+    fn first_mover_must_be_a_player_and_opens_the_match() {
+        let mut state = LobbyState::init();
+        let a = bs58::encode([1u8; 32]).into_string();
+        let b = bs58::encode([2u8; 32]).into_string();
+        let c = bs58::encode([3u8; 32]).into_string();
+        let err = state
+            .create_match_with_id(&a, &b, 1, "00000001", Visibility::Public, Some(&c))
+            .unwrap_err();
+        assert!(matches!(err, GameError::Invalid(ref m) if m.contains("first_mover")));
+
+        let id = state
+            .create_match_with_id(&a, &b, 2, "00000002", Visibility::Public, Some(&b))
+            .unwrap();
+        state.set_match_context_id_inner(&id, "ctx").unwrap();
+        let summary = state.matches.get(&id).unwrap().unwrap();
+        assert_eq!(summary.first_mover, b);
+        assert_eq!(summary.turn.as_deref(), Some(b.as_str()));
+
+        let default = state
+            .create_match_with_id(&a, &b, 3, "00000003", Visibility::Public, None)
+            .unwrap();
+        let summary = state.matches.get(&default).unwrap().unwrap();
+        assert_eq!(summary.first_mover, a);
+    }
+
     #[test]
     fn on_match_finished_records_winner_and_increments_counters() {
         let mut state = LobbyState::init();
         let winner = bs58::encode([1u8; 32]).into_string();
         let loser = bs58::encode([2u8; 32]).into_string();
         let id = state
-            .create_match_with_id(&winner, &loser, 1_700_000_000_000, "deadbeef")
+            .create_match_with_id(
+                &winner,
+                &loser,
+                1_700_000_000_000,
+                "deadbeef",
+                Visibility::Public,
+                None,
+            )
             .unwrap();
         state
             .on_match_finished_inner(&id, &winner, &loser, 1_700_000_000_999)
@@ -503,12 +1449,238 @@ mod tests {
         assert_eq!(state.history.len().unwrap(), 1);
     }
 
+    #[test]
+    fn forfeit_all_finishes_only_the_callers_open_matches() {
+        let mut state = LobbyState::init();
+        let me = bs58::encode([1u8; 32]).into_string();
+        let b = bs58::encode([2u8; 32]).into_string();
+        let c = bs58::encode([3u8; 32]).into_string();
+        let mine_pending = state
+            .create_match_with_id(&me, &b, 1, "00000001", Visibility::Public, None)
+            .unwrap();
+        let mine_active = state
+            .create_match_with_id(&c, &me, 2, "00000002", Visibility::Public, None)
+            .unwrap();
+        state
+            .set_match_context_id_inner(&mine_active, "ctx")
+            .unwrap();
+        let mine_done = state
+            .create_match_with_id(&me, &c, 3, "00000003", Visibility::Public, None)
+            .unwrap();
+        state
+            .on_match_finished_inner(&mine_done, &me, &c, 4)
+            .unwrap();
+        let others = state
+            .create_match_with_id(&b, &c, 5, "00000005", Visibility::Public, None)
+            .unwrap();
+
+        let mut expected = vec![mine_pending.clone(), mine_active.clone()];
+        expected.sort();
+        assert_eq!(state.forfeit_all_inner(&me, 10).unwrap(), expected);
+
+        let summary = state.matches.get(&mine_active).unwrap().unwrap();
+        assert!(matches!(summary.status, MatchStatus::Finished));
+        assert_eq!(summary.winner.as_deref(), Some(c.as_str()));
+        let summary = state.matches.get(&mine_pending).unwrap().unwrap();
+        assert_eq!(summary.winner.as_deref(), Some(b.as_str()));
+        let others = state.matches.get(&others).unwrap().unwrap();
+        assert!(matches!(others.status, MatchStatus::Pending));
+
+        let my_view = state
+            .player_stats
+            .get(&me)
+            .unwrap()
+            .unwrap()
+            .to_view()
+            .unwrap();
+        assert_eq!((my_view.wins, my_view.losses), (1, 2));
+        assert_eq!(state.history.len().unwrap(), 3);
+
+        // Nothing left to forfeit.
+        assert!(state.forfeit_all_inner(&me, 11).unwrap().is_empty());
+
+        // The forfeited game ending for real later changes nothing.
+        state
+            .on_match_finished_inner(&mine_active, &me, &c, 12)
+            .unwrap();
+        let summary = state.matches.get(&mine_active).unwrap().unwrap();
+        assert_eq!(summary.winner.as_deref(), Some(c.as_str()));
+        assert_eq!(state.history.len().unwrap(), 3);
+        let my_view = state
+            .player_stats
+            .get(&me)
+            .unwrap()
+            .unwrap()
+            .to_view()
+            .unwrap();
+        assert_eq!((my_view.wins, my_view.losses), (1, 2));
+    }
+
+    #[test]
+    fn private_matches_are_listed_only_for_their_players() {
+        let mut state = LobbyState::init();
+        let a = bs58::encode([1u8; 32]).into_string();
+        let b = bs58::encode([2u8; 32]).into_string();
+        let c = bs58::encode([3u8; 32]).into_string();
+        let public = state
+            .create_match_with_id(&a, &b, 1, "00000001", Visibility::Public, None)
+            .unwrap();
+        let private = state
+            .create_match_with_id(&a, &b, 2, "00000002", Visibility::Private, None)
+            .unwrap();
+
+        let ids = |list: Vec<MatchSummary>| {
+            let mut ids: Vec<String> = list.into_iter().map(|m| m.match_id).collect();
+            ids.sort();
+            ids
+        };
+        let mut both = vec![public.clone(), private.clone()];
+        both.sort();
+        assert_eq!(ids(state.matches_visible_to(&a).unwrap()), both);
+        assert_eq!(ids(state.matches_visible_to(&b).unwrap()), both);
+        assert_eq!(ids(state.matches_visible_to(&c).unwrap()), vec![public]);
+    }
+
+    #[test]
+    fn my_matches_filters_by_status_and_turn() {
+        let mut state = LobbyState::init();
+        let me = bs58::encode([1u8; 32]).into_string();
+        let b = bs58::encode([2u8; 32]).into_string();
+        let c = bs58::encode([3u8; 32]).into_string();
+        let pending = state
+            .create_match_with_id(&me, &b, 1, "00000001", Visibility::Public, None)
+            .unwrap();
+        let mine = state
+            .create_match_with_id(&me, &c, 2, "00000002", Visibility::Public, None)
+            .unwrap();
+        let theirs = state
+            .create_match_with_id(&b, &me, 3, "00000003", Visibility::Private, None)
+            .unwrap();
+        let done = state
+            .create_match_with_id(&c, &me, 4, "00000004", Visibility::Public, None)
+            .unwrap();
+        let other = state
+            .create_match_with_id(&b, &c, 5, "00000005", Visibility::Public, None)
+            .unwrap();
+        for id in [&mine, &theirs, &done, &other] {
+            state.set_match_context_id_inner(id, "ctx").unwrap();
+        }
+        state.on_match_finished_inner(&done, &c, &me, 6).unwrap();
+        state
+            .on_turn_changed_inner(&theirs, "ctx", Some(&b), 1)
+            .unwrap();
+        state
+            .on_turn_changed_inner(&other, "ctx", Some(&c), 1)
+            .unwrap();
+
+        let sorted = |mut v: Vec<&String>| {
+            v.sort();
+            v.into_iter().cloned().collect::<Vec<_>>()
+        };
+        assert_eq!(
+            state.my_matches(&me, MatchStatusFilter::All).unwrap(),
+            sorted(vec![&pending, &mine, &theirs, &done])
+        );
+        assert_eq!(
+            state.my_matches(&me, MatchStatusFilter::Active).unwrap(),
+            sorted(vec![&pending, &mine, &theirs])
+        );
+        assert_eq!(
+            state.my_matches(&me, MatchStatusFilter::Finished).unwrap(),
+            vec![done.clone()]
+        );
+        assert_eq!(
+            state.my_matches(&me, MatchStatusFilter::MyTurn).unwrap(),
+            vec![mine]
+        );
+
+        assert!(state
+            .on_turn_changed_inner(&done, "ctx", Some(&me), 2)
+            .is_err());
+        assert!(state
+            .on_turn_changed_inner(&other, "ctx", Some(&me), 2)
+            .is_err());
+    }
+
+    #[test]
+    fn turn_reports_come_from_the_game_context_and_the_latest_wins() {
+        let mut state = LobbyState::init();
+        let a = bs58::encode([1u8; 32]).into_string();
+        let b = bs58::encode([2u8; 32]).into_string();
+        let id = state
+            .create_match_with_id(&a, &b, 1, "00000001", Visibility::Public, None)
+            .unwrap();
+        state.set_match_context_id_inner(&id, "ctx").unwrap();
+        let err = state
+            .on_turn_changed_inner(&id, "elsewhere", Some(&b), 5)
+            .unwrap_err();
+        assert!(matches!(err, GameError::Forbidden(_)));
+
+        state
+            .on_turn_changed_inner(&id, "ctx", Some(&b), 5)
+            .unwrap();
+        // An older report arriving late doesn't roll the turn back.
+        state
+            .on_turn_changed_inner(&id, "ctx", Some(&a), 3)
+            .unwrap();
+        let summary = state.matches.get(&id).unwrap().unwrap();
+        assert_eq!(
+            (summary.turn.as_deref(), summary.turn_ms),
+            (Some(b.as_str()), 5)
+        );
+        // Paused: nobody's turn.
+        state.on_turn_changed_inner(&id, "ctx", None, 6).unwrap();
+        assert!(state
+            .my_matches(&b, MatchStatusFilter::MyTurn)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn migrated_players_keep_their_matches() {
+        let mut state = LobbyState::init();
+        let [a, b, a_new] = [1u8, 2, 7].map(|n| bs58::encode([n; 32]).into_string());
+        let id = state
+            .create_match_with_id(&a, &b, 1, "00000001", Visibility::Public, None)
+            .unwrap();
+        state.set_match_context_id_inner(&id, "ctx").unwrap();
+        assert!(state
+            .on_player_migrated_inner(&id, "elsewhere", &a, &a_new, 2)
+            .is_err());
+        state
+            .on_player_migrated_inner(&id, "ctx", &a, &a_new, 2)
+            .unwrap();
+
+        let summary = state.matches.get(&id).unwrap().unwrap();
+        assert_eq!(summary.player1, a_new);
+        assert_eq!(summary.turn.as_deref(), Some(a_new.as_str()));
+        assert!(state
+            .my_matches(&a, MatchStatusFilter::All)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            state.my_matches(&a_new, MatchStatusFilter::MyTurn).unwrap(),
+            vec![id.clone()]
+        );
+        // A forfeit now settles the new key's record.
+        state.forfeit_all_inner(&a_new, 3).unwrap();
+        let stats = state.player_stats.get(&a_new).unwrap().unwrap();
+        assert_eq!(stats.to_view().unwrap().losses, 1);
+    }
+
     #[test]
     fn create_match_rejects_self_match() {
         let mut state = LobbyState::init();
         let a = bs58::encode([1u8; 32]).into_string();
         let err = state
-            .create_match_with_id(&a, &a, 1_700_000_000_000, "deadbeef")
+            .create_match_with_id(
+                &a,
+                &a,
+                1_700_000_000_000,
+                "deadbeef",
+                Visibility::Public,
+                None,
+            )
             .unwrap_err();
         assert!(matches!(err, GameError::Invalid(_)));
     }
@@ -518,7 +1690,14 @@ mod tests {
         let mut state = LobbyState::init();
         let a = bs58::encode([1u8; 32]).into_string();
         let err = state
-            .create_match_with_id(&a, "!!!not-base58!!!", 1_700_000_000_000, "deadbeef")
+            .create_match_with_id(
+                &a,
+                "!!!not-base58!!!",
+                1_700_000_000_000,
+                "deadbeef",
+                Visibility::Public,
+                None,
+            )
             .unwrap_err();
         assert!(matches!(err, GameError::Invalid(_)));
     }
@@ -529,7 +1708,14 @@ mod tests {
         let a = bs58::encode([1u8; 32]).into_string();
         let b = bs58::encode([2u8; 32]).into_string();
         let id = state
-            .create_match_with_id(&a, &b, 1_700_000_000_000, "deadbeef")
+            .create_match_with_id(
+                &a,
+                &b,
+                1_700_000_000_000,
+                "deadbeef",
+                Visibility::Public,
+                None,
+            )
             .unwrap();
         state.set_match_context_id_inner(&id, "ctx_abc").unwrap();
         let err = state
@@ -558,7 +1744,14 @@ mod tests {
         let winner = bs58::encode([1u8; 32]).into_string();
         let loser = bs58::encode([2u8; 32]).into_string();
         let id = state
-            .create_match_with_id(&winner, &loser, 1_700_000_000_000, "deadbeef")
+            .create_match_with_id(
+                &winner,
+                &loser,
+                1_700_000_000_000,
+                "deadbeef",
+                Visibility::Public,
+                None,
+            )
             .unwrap();
         state
             .on_match_finished_inner(&id, &winner, &loser, 1_700_000_000_999)
@@ -601,6 +1794,12 @@ mod tests {
             context_id: ctx.map(str::to_string),
             winner: winner.map(str::to_string),
             created_ms: 1_700_000_000_000,
+            visibility: Visibility::Public,
+            first_mover: "p1".into(),
+            name: None,
+            turn: None,
+            turn_ms: 0,
+            template: None,
         }
     }
 
@@ -638,6 +1837,21 @@ mod tests {
         assert_eq!(a.winner.as_deref(), Some("p2"));
     }
 
+    #[test]
+    fn merge_match_summary_keeps_the_latest_turn_either_way() {
+        let mut a = sample_summary("m-1", MatchStatus::Active, Some("ctx"), None);
+        a.turn = Some("p1".into());
+        a.turn_ms = 10;
+        let mut b = a.clone();
+        b.turn = Some("p2".into());
+        b.turn_ms = 20;
+        let (mut ab, mut ba) = (a.clone(), b.clone());
+        ab.merge(&b).unwrap();
+        ba.merge(&a).unwrap();
+        assert_eq!(ab.turn.as_deref(), Some("p2"));
+        assert_eq!((ab.turn, ab.turn_ms), (ba.turn, ba.turn_ms));
+    }
+
     #[test]
     fn merge_match_summary_is_idempotent() {
         let a_orig = sample_summary("m-1", MatchStatus::Active, Some("ctx"), None);
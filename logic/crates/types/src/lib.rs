@@ -1,8 +1,12 @@
+pub mod rules;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Debug, Error, Serialize)]
+/// Serialized as `{"kind": <variant>, "data": <payload>}`, and parses back
+/// from the same shape so Rust clients can match on the typed error.
+#[derive(Debug, Error, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "kind", content = "data")]
 pub enum GameError {
     #[error("not found: {0}")]
@@ -27,6 +31,42 @@ pub enum GameError {
     AuditFailed { reason: String },
     #[error("private board not found for this match")]
     BoardNotFound,
+    /// Two ships, or a ship and an already placed one, claim the same cell.
+    #[error("ships overlap at ({x},{y})")]
+    Overlap { x: u8, y: u8 },
+    /// The placed fleet doesn't match the required one. Holds one entry per
+    /// ship length the rules know about, including lengths that are fine.
+    #[error("wrong fleet: {}", describe_fleet_diff(.0))]
+    FleetComposition(Vec<ShipLengthCount>),
+}
+
+/// How many ships of one length a fleet needs and how many it has.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShipLengthCount {
+    pub length: u8,
+    pub expected: u32,
+    pub actual: u32,
+}
+
+impl ShipLengthCount {
+    /// Ships of this length to add (positive) or remove (negative).
+    pub fn missing(&self) -> i64 {
+        i64::from(self.expected) - i64::from(self.actual)
+    }
+}
+
+/// "need 1 more of length 4, remove 1 of length 2" for the lengths that
+/// are off.
+fn describe_fleet_diff(counts: &[ShipLengthCount]) -> String {
+    counts
+        .iter()
+        .filter_map(|c| match c.missing() {
+            0 => None,
+            n if n > 0 => Some(format!("need {n} more of length {}", c.length)),
+            n => Some(format!("remove {} of length {}", -n, c.length)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Player public key — 32-byte Ed25519 key with base58 encoding.
@@ -84,6 +124,51 @@ mod tests {
         assert_eq!(key, decoded);
     }
 
+    #[test]
+    fn game_errors_compare_by_variant_and_payload() {
+        assert_eq!(
+            PublicKey::from_base58(&bs58::encode(&[1u8; 16]).into_string()).unwrap_err(),
+            GameError::Invalid("key length".into())
+        );
+        assert_ne!(
+            GameError::Invalid("a".into()),
+            GameError::Forbidden("a".into())
+        );
+        assert_eq!(
+            GameError::Overlap { x: 1, y: 2 },
+            GameError::Overlap { x: 1, y: 2 }
+        );
+    }
+
+    #[test]
+    fn game_errors_round_trip_through_json() {
+        let errors = [
+            GameError::NotFound("m-1".into()),
+            GameError::Invalid("no pending shot".into()),
+            GameError::Forbidden("not a player".into()),
+            GameError::Finished,
+            GameError::MatchIdCollision,
+            GameError::AlreadyCommitted,
+            GameError::CommitmentMismatch,
+            GameError::AuditFailed {
+                reason: "commitment_mismatch".into(),
+            },
+            GameError::BoardNotFound,
+            GameError::Overlap { x: 3, y: 4 },
+            GameError::FleetComposition(vec![ShipLengthCount {
+                length: 3,
+                expected: 2,
+                actual: 1,
+            }]),
+        ];
+        for err in errors {
+            let json = serde_json::to_string(&err).unwrap();
+            assert_eq!(serde_json::from_str::<GameError>(&json).unwrap(), err);
+        }
+        let parsed: GameError = serde_json::from_str(r#"{"kind":"Invalid","data":"bad"}"#).unwrap();
+        assert_eq!(parsed, GameError::Invalid("bad".into()));
+    }
+
     #[test]
     fn game_error_display() {
         let err = GameError::NotFound("test".into());
@@ -98,5 +183,35 @@ mod tests {
         let _ = GameError::CommitmentMismatch;
         let _ = GameError::AuditFailed { reason: "x".into() };
         let _ = GameError::BoardNotFound;
+        assert_eq!(
+            GameError::Overlap { x: 3, y: 4 }.to_string(),
+            "ships overlap at (3,4)"
+        );
+    }
+
+    #[test]
+    fn fleet_composition_names_what_to_add_and_remove() {
+        let counts = vec![
+            ShipLengthCount {
+                length: 2,
+                expected: 1,
+                actual: 2,
+            },
+            ShipLengthCount {
+                length: 3,
+                expected: 2,
+                actual: 2,
+            },
+            ShipLengthCount {
+                length: 4,
+                expected: 1,
+                actual: 0,
+            },
+        ];
+        assert_eq!(counts[1].missing(), 0);
+        assert_eq!(
+            GameError::FleetComposition(counts).to_string(),
+            "wrong fleet: remove 1 of length 2, need 1 more of length 4"
+        );
     }
 }
@@ -0,0 +1,397 @@
+//! # Rules Module
+//!
+//! The per-match rule set: how big the board is, which fleet must be
+//! placed, and how the match is played and won. It lives here rather than
+//! in the game crate so the lobby can store it in match templates.
+//!
+//! ## Key Types
+//!
+//! - **`PlacementRules`** - Board size and required fleet for a match
+//! - **`GameRules`** - How a match is played and won once ships are placed
+//! - **`WinCondition`** - What ends a match
+//!
+//! ## Defaults
+//!
+//! `PlacementRules::default()` is the classic game: a 10x10 board and a
+//! fleet of ships of length 5, 4, 3, 3 and 2. `GameRules::default()` is
+//! played until one fleet is sunk, one shot per turn, sunk ships are not
+//! revealed, and a pending shot may be forced after
+//! `DEFAULT_SHOT_TIMEOUT_MS`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust
+//! use battleships_types::rules::PlacementRules;
+//!
+//! let rules = PlacementRules::default();
+//! assert_eq!(rules.board_size, 10);
+//! assert_eq!(rules.total_ship_cells(), 17);
+//! ```
+
+use crate::GameError;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Width and height of the classic board.
+pub const CLASSIC_BOARD_SIZE: u8 = 10;
+
+/// Ship lengths of the classic fleet, longest first.
+pub const CLASSIC_FLEET: [u8; 5] = [5, 4, 3, 3, 2];
+
+/// Narrowest board the classic fleet fits on, as ships may not touch, not
+/// even diagonally.
+pub const MIN_BOARD_SIZE: u8 = 7;
+
+/// Narrowest board the classic fleet fits on when the board wraps at its
+/// edges (`GameRules::wrap_edges`), so ships also keep clear across them.
+pub const MIN_WRAPPED_BOARD_SIZE: u8 = 8;
+
+/// Widest board a match may be played on. Shot maps key cells by a single
+/// byte, so a board may have at most 256 cells.
+pub const MAX_BOARD_SIZE: u8 = 16;
+
+/// How long a pending shot goes unacknowledged, by default, before either
+/// player may force it to resolve.
+pub const DEFAULT_SHOT_TIMEOUT_MS: u64 = 5 * 60 * 1000;
+
+/// Board size and fleet a match is played with.
+///
+/// # Fields
+/// * `board_size` - Width and height of the (square) board
+/// * `fleet` - Length of every ship that must be placed, longest first
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct PlacementRules {
+    /// Width and height of the (square) board
+    pub board_size: u8,
+    /// Length of every ship that must be placed, longest first
+    pub fleet: Vec<u8>,
+}
+
+impl Default for PlacementRules {
+    fn default() -> Self {
+        PlacementRules {
+            board_size: CLASSIC_BOARD_SIZE,
+            fleet: CLASSIC_FLEET.to_vec(),
+        }
+    }
+}
+
+impl PlacementRules {
+    /// Number of cells the whole fleet occupies.
+    pub fn total_ship_cells(&self) -> usize {
+        self.fleet.iter().map(|&len| len as usize).sum()
+    }
+
+    /// Rejects a board narrower than `MIN_BOARD_SIZE`, which the fleet
+    /// can't be placed on, or wider than `MAX_BOARD_SIZE`. Placement checks
+    /// the classic composition, so any other fleet is refused rather than
+    /// silently ignored.
+    pub fn validate(&self) -> Result<(), GameError> {
+        if self.fleet != CLASSIC_FLEET {
+            return Err(GameError::Invalid(format!(
+                "only the classic fleet {CLASSIC_FLEET:?} is supported"
+            )));
+        }
+        if self.board_size < MIN_BOARD_SIZE || self.board_size > MAX_BOARD_SIZE {
+            return Err(GameError::Invalid(format!(
+                "board size must be between {MIN_BOARD_SIZE} and {MAX_BOARD_SIZE}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// `validate`, for a match played under `game_rules`: a board that
+    /// wraps at its edges must be at least `MIN_WRAPPED_BOARD_SIZE` wide.
+    pub fn validate_for(&self, game_rules: &GameRules) -> Result<(), GameError> {
+        self.validate()?;
+        if game_rules.wrap_edges && self.board_size < MIN_WRAPPED_BOARD_SIZE {
+            return Err(GameError::Invalid(format!(
+                "a wrapped board must be at least {MIN_WRAPPED_BOARD_SIZE} wide"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What ends a match.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub enum WinCondition {
+    /// Classic: sink every ship of the opponent's fleet.
+    #[default]
+    SinkAll,
+    /// Quick play: the first player to land this many hits wins.
+    FirstToHits(u32),
+}
+
+/// How a match is played once ships are placed. Fixed at match creation.
+/// Fields left out of a JSON rule set take their default.
+///
+/// # Fields
+/// * `win_condition` - What ends the match
+/// * `shots_per_turn` - Shots a player fires before the turn passes
+/// * `max_shots` - Ammunition per player; `None` is unlimited
+/// * `wrap_edges` - Whether the board is a torus
+/// * `extra_turn_on_hit` - Whether a hit that sinks nothing earns another turn
+/// * `extra_turn_on_sink` - Whether a hit that sinks a ship earns another turn
+/// * `reveal_on_sink` - Whether sinking a ship shows the shooter its extent
+/// * `shot_timeout_ms` - How long a pending shot waits before it may be forced
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameRules {
+    /// What ends the match
+    pub win_condition: WinCondition,
+    /// Shots a player fires before the turn passes
+    pub shots_per_turn: u32,
+    /// Ammunition per player; `None` is unlimited. A player who runs out
+    /// sits out while the opponent fires the rest of theirs; if that
+    /// doesn't win it either, the match is drawn.
+    pub max_shots: Option<u32>,
+    /// Toroidal variant: the board wraps at its edges, so a ship may run
+    /// off one edge and continue from the opposite one, and ships may not
+    /// touch across an edge either.
+    pub wrap_edges: bool,
+    /// A hit that doesn't sink its ship keeps the turn with the shooter.
+    pub extra_turn_on_hit: bool,
+    /// A hit that sinks a ship keeps the turn with the shooter. Decides
+    /// sinking shots on its own, whatever `extra_turn_on_hit` says. Under
+    /// salvo rules the turn passes per batch, so one qualifying shot in the
+    /// batch earns the whole next batch.
+    pub extra_turn_on_sink: bool,
+    /// Sinking a ship tells the shooter where all of it was. Off by
+    /// default, as in the classic game, where the shooter learns only that
+    /// the shot hit.
+    pub reveal_on_sink: bool,
+    /// How long a pending shot may go unacknowledged before either player
+    /// may `force_resolve_pending` it. Time spent paused doesn't count.
+    pub shot_timeout_ms: u64,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            win_condition: WinCondition::SinkAll,
+            shots_per_turn: 1,
+            max_shots: None,
+            wrap_edges: false,
+            extra_turn_on_hit: false,
+            extra_turn_on_sink: false,
+            reveal_on_sink: false,
+            shot_timeout_ms: DEFAULT_SHOT_TIMEOUT_MS,
+        }
+    }
+}
+
+impl GameRules {
+    /// Rejects rule sets no match could sensibly be played under: a hit
+    /// threshold of zero would be won before the first shot, and a zero
+    /// timeout would let any pending shot be forced at once.
+    pub fn validate(&self) -> Result<(), GameError> {
+        if self.win_condition == WinCondition::FirstToHits(0) {
+            return Err(GameError::Invalid(
+                "FirstToHits needs a threshold of at least one hit".into(),
+            ));
+        }
+        if self.shot_timeout_ms == 0 {
+            return Err(GameError::Invalid(
+                "shot_timeout_ms must be above zero".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether a player who has fired `fired` shots this turn has used up
+    /// their allowance. A zero allowance is treated as one shot.
+    pub fn turn_exhausted(&self, fired: u32) -> bool {
+        fired >= self.shots_per_turn.max(1)
+    }
+
+    /// Whether a shooter who has now landed `shooter_hits` hits, against a
+    /// target with `target_ships_left` ship cells afloat, has won.
+    pub fn is_won(&self, shooter_hits: u32, target_ships_left: u64) -> bool {
+        match self.win_condition {
+            WinCondition::SinkAll => target_ships_left == 0,
+            // A fleet smaller than the threshold still ends the game.
+            WinCondition::FirstToHits(n) => shooter_hits >= n || target_ships_left == 0,
+        }
+    }
+
+    /// Whether a shot earns its shooter another turn. A sinking shot is a
+    /// hit, but only `extra_turn_on_sink` applies to it.
+    pub fn earns_extra_turn(&self, hit: bool, sunk: bool) -> bool {
+        if sunk {
+            self.extra_turn_on_sink
+        } else {
+            hit && self.extra_turn_on_hit
+        }
+    }
+
+    /// Whether a player who has fired `fired` shots has no ammunition left.
+    pub fn out_of_ammo(&self, fired: u32) -> bool {
+        self.max_shots.is_some_and(|max| fired >= max)
+    }
+
+    /// Hits still needed by a player who has landed `hits` so far, or `None`
+    /// when the match is not won on a hit threshold.
+    pub fn hits_needed(&self, hits: u32) -> Option<u32> {
+        match self.win_condition {
+            WinCondition::SinkAll => None,
+            WinCondition::FirstToHits(n) => Some(n.saturating_sub(hits)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rules_are_the_classic_game() {
+        let rules = PlacementRules::default();
+        assert_eq!(rules.board_size, 10);
+        assert_eq!(rules.fleet, vec![5, 4, 3, 3, 2]);
+        assert_eq!(rules.total_ship_cells(), 17);
+    }
+
+    #[test]
+    fn placement_rules_bound_the_board_and_fix_the_fleet() {
+        assert!(PlacementRules::default().validate().is_ok());
+        for board_size in [MIN_BOARD_SIZE, 8, MAX_BOARD_SIZE] {
+            let rules = PlacementRules {
+                board_size,
+                ..PlacementRules::default()
+            };
+            assert!(rules.validate().is_ok(), "{board_size}");
+        }
+        for board_size in [0, 5, 6, MAX_BOARD_SIZE + 1] {
+            let rules = PlacementRules {
+                board_size,
+                ..PlacementRules::default()
+            };
+            assert!(matches!(rules.validate(), Err(GameError::Invalid(_))));
+        }
+        let other_fleet = PlacementRules {
+            fleet: vec![4, 3, 2],
+            ..PlacementRules::default()
+        };
+        assert!(matches!(other_fleet.validate(), Err(GameError::Invalid(_))));
+
+        let wrapped = GameRules {
+            wrap_edges: true,
+            ..GameRules::default()
+        };
+        let narrow = PlacementRules {
+            board_size: MIN_BOARD_SIZE,
+            ..PlacementRules::default()
+        };
+        assert!(narrow.validate_for(&GameRules::default()).is_ok());
+        assert!(matches!(
+            narrow.validate_for(&wrapped),
+            Err(GameError::Invalid(_))
+        ));
+        let wide_enough = PlacementRules {
+            board_size: MIN_WRAPPED_BOARD_SIZE,
+            ..PlacementRules::default()
+        };
+        assert!(wide_enough.validate_for(&wrapped).is_ok());
+    }
+
+    #[test]
+    fn first_to_hits_ends_at_the_threshold() {
+        let classic = GameRules::default();
+        assert!(!classic.is_won(16, 1));
+        assert!(classic.is_won(17, 0));
+
+        let quick = GameRules {
+            win_condition: WinCondition::FirstToHits(5),
+            ..GameRules::default()
+        };
+        assert!(!quick.is_won(4, 13));
+        assert!(quick.is_won(5, 12));
+        assert!(quick.is_won(2, 0));
+    }
+
+    #[test]
+    fn zero_hit_threshold_is_rejected() {
+        assert!(GameRules::default().validate().is_ok());
+        let instant = GameRules {
+            win_condition: WinCondition::FirstToHits(0),
+            ..GameRules::default()
+        };
+        assert!(matches!(instant.validate(), Err(GameError::Invalid(_))));
+        let no_wait = GameRules {
+            shot_timeout_ms: 0,
+            ..GameRules::default()
+        };
+        assert!(matches!(no_wait.validate(), Err(GameError::Invalid(_))));
+    }
+
+    #[test]
+    fn hits_needed_counts_down_to_zero_under_a_threshold() {
+        assert_eq!(GameRules::default().hits_needed(3), None);
+        let quick = GameRules {
+            win_condition: WinCondition::FirstToHits(5),
+            ..GameRules::default()
+        };
+        assert_eq!(quick.hits_needed(0), Some(5));
+        assert_eq!(quick.hits_needed(4), Some(1));
+        assert_eq!(quick.hits_needed(7), Some(0));
+    }
+
+    #[test]
+    fn default_turn_is_a_single_shot() {
+        let rules = GameRules::default();
+        assert!(!rules.turn_exhausted(0));
+        assert!(rules.turn_exhausted(1));
+
+        let triple = GameRules {
+            shots_per_turn: 3,
+            ..GameRules::default()
+        };
+        assert!(!triple.turn_exhausted(2));
+        assert!(triple.turn_exhausted(3));
+    }
+
+    #[test]
+    fn sinking_shots_follow_their_own_extra_turn_flag() {
+        let classic = GameRules::default();
+        assert!(!classic.earns_extra_turn(true, false));
+        assert!(!classic.earns_extra_turn(true, true));
+
+        let on_hit = GameRules {
+            extra_turn_on_hit: true,
+            ..GameRules::default()
+        };
+        assert!(on_hit.earns_extra_turn(true, false));
+        assert!(!on_hit.earns_extra_turn(true, true));
+        assert!(!on_hit.earns_extra_turn(false, false));
+
+        let on_sink = GameRules {
+            extra_turn_on_sink: true,
+            ..GameRules::default()
+        };
+        assert!(!on_sink.earns_extra_turn(true, false));
+        assert!(on_sink.earns_extra_turn(true, true));
+    }
+
+    #[test]
+    fn ammunition_is_unlimited_by_default() {
+        assert!(!GameRules::default().out_of_ammo(u32::MAX));
+        let limited = GameRules {
+            max_shots: Some(3),
+            ..GameRules::default()
+        };
+        assert!(!limited.out_of_ammo(2));
+        assert!(limited.out_of_ammo(3));
+    }
+}